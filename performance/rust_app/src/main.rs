@@ -7,6 +7,7 @@ use crate::test::test_2_lines_net::LinesNetTest;
 use crate::test::test_3_wind_mill::WindMillTest;
 use crate::test::test_4_windows::WindowsTest;
 use crate::test::test_5_nested_squares::CrossTest;
+use crate::test::test_6_clustered::ClusteredTest;
 
 mod test;
 
@@ -69,6 +70,9 @@ fn main() {
             5 => {
                 run_test_5(multithreading);
             }
+            6 => {
+                run_test_6(multithreading);
+            }
             _ => {
                 println!("Test is not found");
             }
@@ -95,6 +99,9 @@ fn main() {
             5 => {
                 CrossTest::run(count, OverlayRule::Xor, 1.0, multithreading);
             }
+            6 => {
+                ClusteredTest::run(count, OverlayRule::Xor, 1.0, multithreading);
+            }
             _ => {
                 println!("Test is not found");
             }
@@ -148,4 +155,12 @@ fn run_test_5(multithreading: bool) {
         let n = 1 << i;
         CrossTest::run(n, OverlayRule::Xor, 100.0, multithreading)
     }
+}
+
+fn run_test_6(multithreading: bool) {
+    println!("run Clustered test");
+    for i in 1..12 {
+        let n = 1 << i;
+        ClusteredTest::run(n, OverlayRule::Xor, 1000.0, multithreading)
+    }
 }
\ No newline at end of file
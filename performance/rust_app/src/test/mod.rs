@@ -3,4 +3,5 @@ pub(crate) mod test_1_not_overlap;
 pub(crate) mod test_2_lines_net;
 pub(crate) mod test_4_windows;
 pub(crate) mod test_5_nested_squares;
-pub(crate) mod test_3_wind_mill;
\ No newline at end of file
+pub(crate) mod test_3_wind_mill;
+pub(crate) mod test_6_clustered;
\ No newline at end of file
@@ -0,0 +1,73 @@
+use std::time::Instant;
+use x_overlay::core::column_layout::ColumnLayout;
+use x_overlay::core::fill_rule::FillRule;
+use x_overlay::core::overlay_rule::OverlayRule;
+use x_overlay::i_float::int::point::IntPoint;
+use x_overlay::i_shape::int::path::IntPath;
+use x_overlay::ortho::overlay::OrthoOverlay;
+
+pub(crate) struct ClusteredTest;
+
+// A dense checkerboard cluster next to a sparse row of big squares, the row is much wider than the cluster.
+impl ClusteredTest {
+    pub(crate) fn run(n: usize, rule: OverlayRule, scale: f64, multithreading: bool) { // 1000
+        let (subj_paths, clip_paths) = Self::clustered_squares(n);
+
+        let it_count = ((scale / (n as f64)) as usize).max(1);
+        let sq_it_count = it_count * it_count;
+
+        let uniform = Self::measure(&subj_paths, &clip_paths, rule, ColumnLayout::Uniform, sq_it_count, multithreading);
+        let adaptive = Self::measure(&subj_paths, &clip_paths, rule, ColumnLayout::Adaptive, sq_it_count, multithreading);
+
+        let polygons_count = subj_paths.len() + clip_paths.len();
+        println!("{}({})     - uniform: {:.6}, adaptive: {:.6}({:.2}x)", n, polygons_count, uniform, adaptive, uniform / adaptive);
+    }
+
+    fn measure(subj_paths: &[IntPath], clip_paths: &[IntPath], rule: OverlayRule, column_layout: ColumnLayout, count: usize, multithreading: bool) -> f64 {
+        let mut overlay = OrthoOverlay::default();
+        overlay.solver.multithreading = multithreading;
+        overlay.options.column_layout = column_layout;
+
+        let start = Instant::now();
+
+        for _i in 0..count {
+            overlay.init_with_ortho_contours(subj_paths, clip_paths).expect("valid");
+            overlay.overlay(rule, FillRule::NonZero);
+        }
+
+        start.elapsed().as_secs_f64() / count as f64
+    }
+
+    fn clustered_squares(n: usize) -> (Vec<IntPath>, Vec<IntPath>) {
+        let mut subj_paths = Self::many_squares(IntPoint::new(0, 0), 20, 30, n, n);
+        let mut clip_paths = Self::many_squares(IntPoint::new(15, 15), 20, 30, n - 1, n - 1);
+
+        // a sparse row of big squares to the right of the cluster
+        let width = 30 * n as i32;
+        subj_paths.extend(Self::many_squares(IntPoint::new(2 * width, 0), width, 4 * width, 16, 1));
+        clip_paths.extend(Self::many_squares(IntPoint::new(2 * width + width / 2, width / 2), width, 4 * width, 16, 1));
+
+        (subj_paths, clip_paths)
+    }
+
+    fn many_squares(start: IntPoint, size: i32, offset: i32, nx: usize, ny: usize) -> Vec<IntPath> {
+        let mut result = Vec::with_capacity(nx * ny);
+        let mut y = start.y;
+        for _ in 0..ny {
+            let mut x = start.x;
+            for _ in 0..nx {
+                let path: IntPath = vec![
+                    IntPoint::new(x, y),
+                    IntPoint::new(x, y + size),
+                    IntPoint::new(x + size, y + size),
+                    IntPoint::new(x + size, y),
+                ];
+                result.push(path);
+                x += offset;
+            }
+            y += offset;
+        }
+
+        result
+    }
+}
@@ -0,0 +1,177 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use crate::bind::segment::{ContourIndex, IdSegment};
+use crate::bind::solver::{LeftBottomSegment, ShapeBinder, SortByAngle};
+use crate::geom::v_segment::VSegment;
+use i_float::int::point::IntPoint;
+use i_shape::int::shape::IntContour;
+
+pub(crate) trait Keyhole {
+    /// Joins every hole to the shape by a zero-width vertical bridge and returns a single contour.
+    /// A bridge goes down from the left-bottom point of a hole to the nearest edge of the outer contour or of another hole.
+    fn keyhole(&self, clockwise: bool) -> IntContour;
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bridge {
+    edge: usize,
+    point: IntPoint,
+    hole: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BridgeTarget {
+    contour: usize,
+    edge: usize,
+}
+
+struct Frame {
+    contour: usize,
+    start: usize,
+    entry: IntPoint,
+    step: usize,
+    bridge: usize,
+    bridge_end: usize,
+}
+
+impl Frame {
+    #[inline]
+    fn new(contour: usize, start: usize, entry: IntPoint) -> Self {
+        Self { contour, start, entry, step: 0, bridge: 0, bridge_end: 0 }
+    }
+}
+
+impl Keyhole for [IntContour] {
+    fn keyhole(&self, clockwise: bool) -> IntContour {
+        if self.len() == 1 {
+            return self[0].clone();
+        }
+
+        let holes = &self[1..];
+
+        let mut anchors = Vec::with_capacity(holes.len());
+        let mut starts = Vec::with_capacity(holes.len());
+        for (i, hole) in holes.iter().enumerate() {
+            let v_segment = hole.left_bottom_segment();
+            let start = hole.iter().position(|&p| p == v_segment.a).unwrap_or(0);
+            starts.push(start);
+            anchors.push(IdSegment::with_segment(ContourIndex::new_hole(i), v_segment));
+        }
+
+        anchors.sort_by_a_then_by_angle();
+
+        let x_min = anchors[0].v_segment.a.x;
+        let x_max = anchors[anchors.len() - 1].v_segment.a.x;
+
+        // every segment is a parent on its own, so the binder finds the exact edge under a hole
+        let mut targets = Vec::new();
+        let mut segments = Vec::new();
+        for (contour_index, contour) in self.iter().enumerate() {
+            let mut a = contour[contour.len() - 1];
+            let mut edge = contour.len() - 1;
+            for (i, &b) in contour.iter().enumerate() {
+                // the shape is above the edge
+                let is_under = if clockwise { b.x < a.x } else { a.x < b.x };
+                let (min, max) = if a.x < b.x { (a, b) } else { (b, a) };
+                if a.y == b.y && is_under && x_min < max.x && min.x <= x_max {
+                    let id_data = ContourIndex::new_shape(targets.len());
+                    segments.push(IdSegment::with_segment(id_data, VSegment { a: min, b: max }));
+                    targets.push(BridgeTarget { contour: contour_index, edge });
+                }
+                a = b;
+                edge = i;
+            }
+        }
+
+        segments.sort_by_a_then_by_angle();
+
+        let solution = ShapeBinder::bind(targets.len(), anchors, segments);
+
+        let mut bridges: Vec<Vec<Bridge>> = vec![Vec::new(); self.len()];
+        for (hole, &target_index) in solution.parent_for_child.iter().enumerate() {
+            let target = targets[target_index];
+            let contour = &self[target.contour];
+            let a = contour[target.edge];
+            let point = IntPoint::new(holes[hole][starts[hole]].x, a.y);
+            bridges[target.contour].push(Bridge {
+                edge: target.edge,
+                point,
+                hole,
+            });
+        }
+
+        for (contour, list) in self.iter().zip(bridges.iter_mut()) {
+            list.sort_unstable_by(|b0, b1| {
+                let a = contour[b0.edge];
+                let b = contour[(b0.edge + 1) % contour.len()];
+                b0.edge.cmp(&b1.edge).then_with(|| {
+                    if a.x < b.x {
+                        b0.point.x.cmp(&b1.point.x)
+                    } else {
+                        b1.point.x.cmp(&b0.point.x)
+                    }
+                })
+            });
+        }
+
+        let capacity = self.iter().fold(0, |s, c| s + c.len() + 3);
+        let mut result = Vec::with_capacity(capacity);
+
+        let mut stack = Vec::with_capacity(4);
+        stack.push(Frame::new(0, 0, self[0][0]));
+
+        while let Some(frame) = stack.last_mut() {
+            let contour = &self[frame.contour];
+            let list = &bridges[frame.contour];
+
+            if frame.bridge < frame.bridge_end {
+                // go into the hole through the bridge
+                let bridge = list[frame.bridge];
+                frame.bridge += 1;
+                result.push_point(bridge.point);
+                stack.push(Frame::new(bridge.hole + 1, starts[bridge.hole], bridge.point));
+                continue;
+            }
+
+            if frame.step == contour.len() {
+                let start = contour[frame.start];
+                let entry = frame.entry;
+                stack.pop();
+                if !stack.is_empty() {
+                    // go back through the bridge
+                    result.push_point(start);
+                    result.push_point(entry);
+                }
+                continue;
+            }
+
+            let index = (frame.start + frame.step) % contour.len();
+            result.push_point(contour[index]);
+            frame.step += 1;
+
+            if !list.is_empty() {
+                frame.bridge = list.partition_point(|b| b.edge < index);
+                frame.bridge_end = list.partition_point(|b| b.edge <= index);
+            }
+        }
+
+        if result.len() > 1 && result[0] == result[result.len() - 1] {
+            result.pop();
+        }
+
+        result
+    }
+}
+
+trait PushPoint {
+    fn push_point(&mut self, point: IntPoint);
+}
+
+impl PushPoint for IntContour {
+    #[inline]
+    fn push_point(&mut self, point: IntPoint) {
+        if self.last() != Some(&point) {
+            self.push(point);
+        }
+    }
+}
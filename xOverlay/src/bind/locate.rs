@@ -0,0 +1,59 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use crate::bind::segment::{ContourIndex, IdSegment, IdSegments};
+use crate::bind::solver::{LeftBottomSegment, ShapeBinder, SortByAngle};
+use crate::geom::v_segment::VSegment;
+use i_shape::int::shape::IntShape;
+
+pub(crate) trait ShapeLocator {
+    /// For every probe finds the index of the shape which contains it.
+    /// A probe is a segment which starts inside a shape or on its border and goes inside the shape.
+    fn locate(&self, probes: &[VSegment], clockwise: bool) -> Vec<usize>;
+}
+
+impl ShapeLocator for [IntShape] {
+    fn locate(&self, probes: &[VSegment], clockwise: bool) -> Vec<usize> {
+        if probes.is_empty() {
+            return Vec::new();
+        }
+
+        if self.len() == 1 {
+            return vec![0; probes.len()];
+        }
+
+        // holes are bound together with probes, so a probe above a hole gets the hole parent
+        let holes_count = self.iter().fold(0, |s, shape| s + shape.len() - 1);
+        let mut anchors = Vec::with_capacity(holes_count + probes.len());
+        for hole in self.iter().flat_map(|shape| shape.iter().skip(1)) {
+            let id_data = ContourIndex::new_hole(anchors.len());
+            anchors.push(IdSegment::with_segment(id_data, hole.left_bottom_segment()));
+        }
+
+        for (i, &probe) in probes.iter().enumerate() {
+            let id_data = ContourIndex::new_hole(holes_count + i);
+            anchors.push(IdSegment::with_segment(id_data, probe));
+        }
+
+        anchors.sort_by_a_then_by_angle();
+
+        let x_min = anchors[0].v_segment.a.x;
+        let x_max = anchors[anchors.len() - 1].v_segment.a.x;
+
+        let capacity = self.iter().fold(0, |s, shape| s + shape[0].len()) / 2;
+        let mut segments = Vec::with_capacity(capacity);
+        let mut hole_index = 0;
+        for (i, shape) in self.iter().enumerate() {
+            shape[0].append_id_segments(&mut segments, ContourIndex::new_shape(i), x_min, x_max, clockwise);
+            for hole in shape.iter().skip(1) {
+                hole.append_id_segments(&mut segments, ContourIndex::new_hole(hole_index), x_min, x_max, clockwise);
+                hole_index += 1;
+            }
+        }
+
+        segments.sort_by_a_then_by_angle();
+
+        let solution = ShapeBinder::bind_with_probes(self.len(), anchors, segments, holes_count);
+
+        solution.parent_for_child[holes_count..].to_vec()
+    }
+}
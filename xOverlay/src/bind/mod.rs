@@ -1,2 +1,4 @@
+pub(crate) mod keyhole;
+pub(crate) mod locate;
 pub(crate) mod segment;
 pub(crate) mod solver;
\ No newline at end of file
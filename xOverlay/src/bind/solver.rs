@@ -2,6 +2,7 @@ use alloc::vec;
 use alloc::vec::Vec;
 use crate::bind::segment::{ContourIndex, IdSegment, IdSegments};
 use crate::geom::v_segment::VSegment;
+use i_float::int::point::IntPoint;
 use i_shape::int::path::IntPath;
 use i_shape::int::shape::{IntContour, IntShape};
 use i_tree::key::exp::KeyExpCollection;
@@ -23,6 +24,18 @@ impl ShapeBinder {
         shape_count: usize,
         hole_segments: Vec<IdSegment>,
         segments: Vec<IdSegment>,
+    ) -> BindSolution {
+        let probes_start = hole_segments.len();
+        Self::bind_with_probes(shape_count, hole_segments, segments, probes_start)
+    }
+
+    /// Anchors starting from `probes_start` are probes, a probe which starts on a segment belongs to it.
+    #[inline]
+    pub(crate) fn bind_with_probes(
+        shape_count: usize,
+        hole_segments: Vec<IdSegment>,
+        segments: Vec<IdSegment>,
+        probes_start: usize,
     ) -> BindSolution {
         if shape_count < 32 {
             let capacity = segments.len().log2_sqrt().max(4) * 2;
@@ -32,6 +45,7 @@ impl ShapeBinder {
                 shape_count,
                 hole_segments,
                 segments,
+                probes_start,
             )
         } else {
             let capacity = segments.len().log2_sqrt().max(8);
@@ -41,6 +55,7 @@ impl ShapeBinder {
                 shape_count,
                 hole_segments,
                 segments,
+                probes_start,
             )
         }
     }
@@ -50,21 +65,52 @@ impl ShapeBinder {
         shape_count: usize,
         anchors: Vec<IdSegment>,
         segments: Vec<IdSegment>,
+        probes_start: usize,
     ) -> BindSolution {
         let children_count = anchors.len();
-        let mut parent_for_child = {
-            #[cfg(debug_assertions)]
-            {
-                // prefer crash in debug mode
-                vec![usize::MAX; children_count]
-            }
-            #[cfg(not(debug_assertions))]
-            {
-                vec![0; children_count]
-            }
-        };
+        let mut parent_for_child = Self::unbound_parents(children_count);
         let mut children_count_for_parent = vec![0; shape_count];
 
+        Self::scan(&mut scan_list, &anchors, &segments, probes_start, |anchor, target_id| {
+            let parent_index = if target_id.is_hole() {
+                // index is a hole index
+                // at this moment this hole parent is known
+                parent_for_child[target_id.index()]
+            } else {
+                target_id.index()
+            };
+
+            parent_for_child[anchor.contour_index.index()] = parent_index;
+            children_count_for_parent[parent_index] += 1;
+        });
+
+        BindSolution {
+            parent_for_child,
+            children_count_for_parent,
+        }
+    }
+
+    #[inline]
+    fn unbound_parents(children_count: usize) -> Vec<usize> {
+        #[cfg(debug_assertions)]
+        {
+            // prefer crash in debug mode
+            vec![usize::MAX; children_count]
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            vec![0; children_count]
+        }
+    }
+
+    /// Sweeps the anchors from left to right and reports the nearest contour under every anchor.
+    /// Anchors may be any sorted slice of all anchors, because every segment which starts
+    /// before the first anchor is inserted before its query.
+    fn scan<S, F>(scan_list: &mut S, anchors: &[IdSegment], segments: &[IdSegment], probes_start: usize, mut f: F)
+    where
+        S: KeyExpCollection<VSegment, i32, ContourIndex>,
+        F: FnMut(&IdSegment, ContourIndex),
+    {
         let mut j = 0;
 
         for anchor in anchors.iter() {
@@ -82,19 +128,65 @@ impl ShapeBinder {
                 j += 1
             }
 
-            let target_id =
-                scan_list.first_less(anchor.v_segment.a.x, ContourIndex::EMPTY, anchor.v_segment);
+            let target_id = if anchor.contour_index.index() < probes_start {
+                scan_list.first_less(p.x, ContourIndex::EMPTY, anchor.v_segment)
+            } else {
+                scan_list.first_less_or_equal(p.x, ContourIndex::EMPTY, anchor.v_segment)
+            };
+
+            f(anchor, target_id);
+        }
+    }
+
+    /// Binds holes like [`Self::bind`], the anchors are split into `tasks` chunks which are swept in parallel.
+    /// A hole can be found under another hole from a previous chunk, so the parents are resolved in
+    /// a single pass after the sweep and the solution is the same as the serial one.
+    /// Every chunk scans `segments` from the start, because a segment which begins before its first
+    /// anchor may still cover it. The last chunk inserts about as many segments as the serial sweep,
+    /// so only the queries are split and the total work grows to about `tasks / 2` serial sweeps.
+    #[cfg(feature = "multithreading")]
+    pub(crate) fn par_bind(
+        shape_count: usize,
+        hole_segments: Vec<IdSegment>,
+        segments: Vec<IdSegment>,
+        tasks: usize,
+    ) -> BindSolution {
+        use rayon::iter::ParallelIterator;
+        use rayon::slice::ParallelSlice;
+
+        let chunk_size = hole_segments.len().div_ceil(tasks.max(1)).max(1);
+        let probes_start = hole_segments.len();
+
+        let targets: Vec<Vec<ContourIndex>> = hole_segments
+            .par_chunks(chunk_size)
+            .map(|anchors| {
+                let mut targets = Vec::with_capacity(anchors.len());
+                let collect = |_: &IdSegment, target_id| targets.push(target_id);
+                if shape_count < 32 {
+                    let capacity = segments.len().log2_sqrt().max(4) * 2;
+                    let mut list = KeyExpList::<VSegment, i32, ContourIndex>::new(capacity);
+                    Self::scan(&mut list, anchors, &segments, probes_start, collect);
+                } else {
+                    let capacity = segments.len().log2_sqrt().max(8);
+                    let mut tree = KeyExpTree::<VSegment, i32, ContourIndex>::new(capacity);
+                    Self::scan(&mut tree, anchors, &segments, probes_start, collect);
+                }
+                targets
+            })
+            .collect();
+
+        let mut parent_for_child = Self::unbound_parents(hole_segments.len());
+        let mut children_count_for_parent = vec![0; shape_count];
+
+        let all_targets = targets.iter().flat_map(|chunk| chunk.iter());
+        for (anchor, target_id) in hole_segments.iter().zip(all_targets) {
             let parent_index = if target_id.is_hole() {
-                // index is a hole index
-                // at this moment this hole parent is known
                 parent_for_child[target_id.index()]
             } else {
                 target_id.index()
             };
 
-            let child_index = anchor.contour_index.index();
-
-            parent_for_child[child_index] = parent_index;
+            parent_for_child[anchor.contour_index.index()] = parent_index;
             children_count_for_parent[parent_index] += 1;
         }
 
@@ -111,9 +203,10 @@ pub(crate) trait JoinHoles {
         &mut self,
         holes: Vec<IntContour>,
         anchors: Vec<IdSegment>,
-        clockwise: bool
+        clockwise: bool,
+        tasks: usize,
     );
-    fn scan_join(&mut self, holes: Vec<IntPath>, hole_segments: Vec<IdSegment>, clockwise: bool);
+    fn scan_join(&mut self, holes: Vec<IntPath>, hole_segments: Vec<IdSegment>, clockwise: bool, tasks: usize);
 }
 
 impl JoinHoles for Vec<IntShape> {
@@ -141,15 +234,17 @@ impl JoinHoles for Vec<IntShape> {
 
         hole_segments.sort_by_a_then_by_angle();
 
-        self.scan_join(holes, hole_segments, clockwise);
+        self.scan_join(holes, hole_segments, clockwise, 1);
     }
 
+    /// Holes are bound by `tasks` parallel sweeps, `1` means the serial sweep.
     #[inline]
     fn join_sorted_holes(
         &mut self,
         holes: Vec<IntContour>,
         anchors: Vec<IdSegment>,
-        clockwise: bool
+        clockwise: bool,
+        tasks: usize,
     ) {
         if self.is_empty() || holes.is_empty() {
             return;
@@ -164,10 +259,11 @@ impl JoinHoles for Vec<IntShape> {
 
         let mut anchors = anchors;
         anchors.add_sort_by_angle();
-        self.scan_join(holes, anchors, clockwise);
+        self.scan_join(holes, anchors, clockwise, tasks);
     }
 
-    fn scan_join(&mut self, holes: Vec<IntPath>, hole_segments: Vec<IdSegment>, clockwise: bool) {
+    #[cfg_attr(not(feature = "multithreading"), allow(unused_variables))]
+    fn scan_join(&mut self, holes: Vec<IntPath>, hole_segments: Vec<IdSegment>, clockwise: bool, tasks: usize) {
         let x_min = hole_segments[0].v_segment.a.x;
         let x_max = hole_segments[hole_segments.len() - 1].v_segment.a.x;
 
@@ -183,6 +279,14 @@ impl JoinHoles for Vec<IntShape> {
 
         segments.sort_by_a_then_by_angle();
 
+        #[cfg(feature = "multithreading")]
+        let solution = if tasks > 1 {
+            ShapeBinder::par_bind(self.len(), hole_segments, segments, tasks)
+        } else {
+            ShapeBinder::bind(self.len(), hole_segments, segments)
+        };
+
+        #[cfg(not(feature = "multithreading"))]
         let solution = ShapeBinder::bind(self.len(), hole_segments, segments);
 
         for (shape_index, &capacity) in solution.children_count_for_parent.iter().enumerate() {
@@ -200,7 +304,7 @@ pub(crate) trait LeftBottomSegment {
     fn left_bottom_segment(&self) -> VSegment;
 }
 
-impl LeftBottomSegment for IntContour {
+impl LeftBottomSegment for [IntPoint] {
     fn left_bottom_segment(&self) -> VSegment {
         let mut index = 0;
         let mut a = *self.first().unwrap();
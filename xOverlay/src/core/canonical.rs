@@ -0,0 +1,172 @@
+#[cfg(feature = "provenance")]
+use crate::ortho::provenance::{SourcedContour, SourcedShape};
+#[cfg(feature = "provenance")]
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+use i_shape::int::shape::{IntShape, IntShapes};
+
+/// Brings contours and shapes to the canonical form, so equal geometry has equal representation.
+///
+/// - Every contour starts at its minimum point (by `x`, then by `y`), its direction is kept.
+/// - Holes of a shape are sorted, the outer contour stays first.
+/// - Shapes are sorted by their outer contours.
+///
+/// Contours are compared point by point, so the order is defined by the minimum points first.
+pub trait Canonical {
+    fn canonicalize(&mut self);
+}
+
+impl Canonical for [IntPoint] {
+    #[inline]
+    fn canonicalize(&mut self) {
+        let min_index = min_index(self);
+        self.rotate_left(min_index);
+    }
+}
+
+#[cfg(feature = "provenance")]
+impl Canonical for SourcedContour {
+    #[inline]
+    fn canonicalize(&mut self) {
+        // edges go with their start points
+        let min_index = min_index(&self.points);
+        self.points.rotate_left(min_index);
+        self.edges.rotate_left(min_index);
+    }
+}
+
+impl Canonical for IntShape {
+    #[inline]
+    fn canonicalize(&mut self) {
+        for contour in self.iter_mut() {
+            contour.canonicalize();
+        }
+        if self.len() > 2 {
+            self[1..].sort_unstable();
+        }
+    }
+}
+
+impl Canonical for IntShapes {
+    #[inline]
+    fn canonicalize(&mut self) {
+        for shape in self.iter_mut() {
+            shape.canonicalize();
+        }
+        self.sort_unstable_by(|s0, s1| s0[0].cmp(&s1[0]));
+    }
+}
+
+#[cfg(feature = "provenance")]
+impl Canonical for SourcedShape {
+    #[inline]
+    fn canonicalize(&mut self) {
+        for contour in self.iter_mut() {
+            contour.canonicalize();
+        }
+        if self.len() > 2 {
+            self[1..].sort_unstable_by(|c0, c1| c0.points.cmp(&c1.points));
+        }
+    }
+}
+
+#[cfg(feature = "provenance")]
+impl Canonical for Vec<SourcedShape> {
+    #[inline]
+    fn canonicalize(&mut self) {
+        for shape in self.iter_mut() {
+            shape.canonicalize();
+        }
+        self.sort_unstable_by(|s0, s1| s0[0].points.cmp(&s1[0].points));
+    }
+}
+
+#[inline]
+fn min_index(points: &[IntPoint]) -> usize {
+    points.iter().enumerate().min_by_key(|&(_, p)| p).map_or(0, |(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::canonical::Canonical;
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::core::solver::Solver;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::test_util::{random_rects, rect};
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_float::int::point::IntPoint;
+    use i_shape::int::shape::IntShapes;
+
+    #[test]
+    fn test_0() {
+        let hole_0 = vec![
+            IntPoint::new(6, 6),
+            IntPoint::new(6, 8),
+            IntPoint::new(8, 8),
+            IntPoint::new(8, 6),
+        ];
+        let hole_1 = vec![
+            IntPoint::new(4, 2),
+            IntPoint::new(2, 2),
+            IntPoint::new(2, 4),
+            IntPoint::new(4, 4),
+        ];
+        let mut outer = rect(0, 0, 10, 10);
+        outer.rotate_left(2);
+
+        let mut shapes: IntShapes = vec![vec![rect(20, 0, 30, 10)], vec![outer, hole_0, hole_1]];
+        shapes.canonicalize();
+
+        assert_eq!(
+            shapes,
+            vec![
+                vec![
+                    rect(0, 0, 10, 10),
+                    vec![
+                        IntPoint::new(2, 2),
+                        IntPoint::new(2, 4),
+                        IntPoint::new(4, 4),
+                        IntPoint::new(4, 2),
+                    ],
+                    vec![
+                        IntPoint::new(6, 6),
+                        IntPoint::new(6, 8),
+                        IntPoint::new(8, 8),
+                        IntPoint::new(8, 6),
+                    ],
+                ],
+                vec![rect(20, 0, 30, 10)],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_1() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.options.canonical_output = true;
+
+        for _ in 0..100 {
+            let subj = random_rects(30, 100);
+            let clip = random_rects(30, 100);
+
+            for rule in [OverlayRule::Union, OverlayRule::Difference, OverlayRule::Xor] {
+                let mut results = Vec::new();
+                for (power, solver) in [(6, Solver::serial()), (1, Solver::serial()), (2, Solver::default())] {
+                    overlay.options.min_count_per_column_power = power;
+                    overlay.solver = solver;
+                    overlay.init_with_ortho_contours(&subj, &clip).expect("OK");
+                    results.push(overlay.overlay(rule, FillRule::NonZero));
+                }
+
+                let mut expected = results[0].clone();
+                expected.canonicalize();
+                for result in results.iter() {
+                    assert_eq!(result, &expected);
+                }
+            }
+        }
+    }
+}
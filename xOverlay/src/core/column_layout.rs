@@ -0,0 +1,10 @@
+/// Defines how the input is split into columns.
+/// - `Uniform`: Columns of equal power of two width, chosen by the bounding box and the points count.
+/// - `Adaptive`: Column borders follow the distribution of segments along the x axis, so columns
+///   of clustered input get about the same count of segments. It costs an extra pass over the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColumnLayout {
+    Uniform,
+    Adaptive,
+}
@@ -1,5 +1,6 @@
 /// Represents the winding direction of a contour.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ContourDirection {
     CounterClockwise,
     Clockwise,
@@ -15,7 +15,7 @@ pub const BOTH_BOTTOM: SegmentFill = SUBJ_BOTTOM | CLIP_BOTTOM;
 pub const ALL: SegmentFill = SUBJ_BOTH | CLIP_BOTH;
 
 pub(crate) trait FillStrategy<C> {
-    fn add_and_fill(this: C, bot: C) -> (C, SegmentFill);
+    fn add_and_fill(this: C, bot: C) -> Option<(C, SegmentFill)>;
 }
 
 pub(crate) trait InclusionFilterStrategy {
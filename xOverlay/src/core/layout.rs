@@ -1,12 +1,24 @@
+use crate::core::column_layout::ColumnLayout;
+use crate::core::options::IntOverlayOptions;
+use crate::ortho::mapper::Mapper;
+use alloc::vec::Vec;
 use i_float::int::rect::IntRect;
 use i_shape::int::count::PointsCount;
 use i_shape::int::shape::IntContour;
 
+/// Log of the count of cells per uniform column, the adaptive columns are joined from the cells.
+const CELLS_PER_COLUMN_POWER: u32 = 4;
+
 #[derive(Clone)]
 pub(crate) struct Layout {
     rect: IntRect,
     column_width_power: u32,
     columns_count: usize,
+    /// Column index of every cell of a non-uniform layout, it is empty for a uniform one.
+    /// Cells are `1 << column_width_power` wide.
+    cells: Vec<u32>,
+    /// Left borders of the non-uniform columns.
+    lefts: Vec<i32>,
 }
 
 impl Layout {
@@ -18,11 +30,21 @@ impl Layout {
 
     #[inline(always)]
     pub(crate) fn index(&self, pos: i32) -> usize {
-        (pos - self.rect.min_x) as usize >> self.column_width_power
+        let i = (pos - self.rect.min_x) as usize >> self.column_width_power;
+        if self.cells.is_empty() {
+            i
+        } else {
+            self.cells[i] as usize
+        }
     }
 
     #[inline(always)]
     pub(crate) fn index_inner_border_check(&self, pos: i32) -> (usize, bool) {
+        if !self.cells.is_empty() {
+            let i = self.index(pos);
+            return if pos == self.lefts[i] { (i - 1, true) } else { (i, false) };
+        }
+
         let dx = (pos - self.rect.min_x) as usize;
         let i = dx >> self.column_width_power;
         let xi = i << self.column_width_power;
@@ -37,11 +59,20 @@ impl Layout {
 
     #[inline(always)]
     pub(crate) fn left_border(&self, index: usize) -> i32 {
-        self.rect.min_x + (index << self.column_width_power) as i32
+        if self.lefts.is_empty() {
+            self.rect.min_x + (index << self.column_width_power) as i32
+        } else {
+            self.lefts[index]
+        }
     }
 
     #[inline(always)]
     pub(crate) fn borders(&self, index: usize) -> (i32, i32) {
+        if !self.lefts.is_empty() {
+            let max = self.lefts.get(index + 1).copied().unwrap_or(self.rect.max_x);
+            return (self.lefts[index], max);
+        }
+
         let min = self.rect.min_x + (index << self.column_width_power) as i32;
         let max = (min + (1i32 << self.column_width_power)).min(self.rect.max_x);
         (min, max)
@@ -60,6 +91,15 @@ impl Layout {
         (i0, i1, border)
     }
 
+    /// Creates the layout of the options column mode.
+    pub(crate) fn with_options(subj: &[IntContour], clip: &[IntContour], options: &IntOverlayOptions) -> Option<Self> {
+        let layout = Self::with_subj_and_clip(subj, clip, options.min_count_per_column_power)?;
+        match options.column_layout {
+            ColumnLayout::Uniform => Some(layout),
+            ColumnLayout::Adaptive => Some(layout.adaptive(subj, clip)),
+        }
+    }
+
     #[inline]
     pub(crate) fn with_subj_and_clip(subj: &[IntContour], clip: &[IntContour], min_count_per_column_power: u32) -> Option<Self> {
         let subj_rect = IntRect::with_iter(subj.iter().flatten());
@@ -71,8 +111,13 @@ impl Layout {
             _ => return None,
         };
 
+        let count = subj.points_count() + clip.points_count();
+        Some(Self::with_rect(rect, count, min_count_per_column_power))
+    }
+
+    pub(crate) fn with_rect(rect: IntRect, points_count: usize, min_count_per_column_power: u32) -> Self {
         let width = 1 + rect.width() as u32;
-        let count = (subj.points_count() + clip.points_count()) as u32;
+        let count = points_count.clamp(1, u32::MAX as usize) as u32;
         let count_per_column_power = (4 * count.isqrt()).ilog2().max(min_count_per_column_power);
 
         let columns_count_approx = (count >> count_per_column_power).max(1);
@@ -84,11 +129,69 @@ impl Layout {
 
         let columns_count = ((width + column_width - 1) >> column_width_power) as usize;
 
-        Some(Self {
+        Self {
             columns_count,
             rect,
             column_width_power,
-        })
+            cells: Vec::new(),
+            lefts: Vec::new(),
+        }
+    }
+
+    /// Splits the uniform columns into narrow cells, maps the contours by them and joins the cells
+    /// back into the same count of columns by the quantiles of the segments count.
+    fn adaptive(self, subj: &[IntContour], clip: &[IntContour]) -> Self {
+        if self.columns_count < 2 {
+            return self;
+        }
+
+        let mut mapper = Mapper::new(self.with_cells());
+        mapper.add_ortho_contours(subj);
+        mapper.add_ortho_contours(clip);
+
+        let starts = mapper.column_starts(self.columns_count);
+        mapper.layout.join_cells(&starts)
+    }
+
+    /// Uniform layout of the same rect with `1 << CELLS_PER_COLUMN_POWER` cells per column,
+    /// cells are at least 2 wide.
+    fn with_cells(&self) -> Self {
+        let width = 1 + self.rect.width() as u32;
+        let column_width_power = self
+            .column_width_power
+            .saturating_sub(CELLS_PER_COLUMN_POWER)
+            .max(1)
+            .min(self.column_width_power);
+        let column_width = 1 << column_width_power;
+        let columns_count = ((width + column_width - 1) >> column_width_power) as usize;
+
+        Self {
+            rect: self.rect.clone(),
+            column_width_power,
+            columns_count,
+            cells: Vec::new(),
+            lefts: Vec::new(),
+        }
+    }
+
+    /// Joins the columns of a uniform layout into non-uniform ones.
+    /// - `starts`: Index of the first joined column of every new column, it starts with zero.
+    fn join_cells(self, starts: &[usize]) -> Self {
+        let mut cells = Vec::with_capacity(self.columns_count);
+        let mut lefts = Vec::with_capacity(starts.len());
+        for (index, &start) in starts.iter().enumerate() {
+            let end = starts.get(index + 1).copied().unwrap_or(self.columns_count);
+            cells.resize(end, index as u32);
+            lefts.push(self.left_border(start));
+        }
+
+        Self {
+            rect: self.rect,
+            column_width_power: self.column_width_power,
+            columns_count: lefts.len(),
+            cells,
+            lefts,
+        }
     }
 }
 
@@ -109,14 +212,56 @@ impl Default for Layout {
             rect: IntRect::new(0, 0, 0, 0),
             column_width_power: 0,
             columns_count: 0,
+            cells: Vec::new(),
+            lefts: Vec::new(),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::vec::Vec;
     use i_float::int::point::IntPoint;
+    use i_shape::int::shape::IntContour;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use crate::core::canonical::Canonical;
+    use crate::core::column_layout::ColumnLayout;
+    use crate::core::fill_rule::FillRule;
     use crate::core::layout::Layout;
+    use crate::core::options::IntOverlayOptions;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::mapper::Mapper;
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::test_util::rect;
+
+    // a dense cluster of small rects in a sparse field of big ones
+    fn clustered_rects(rng: &mut impl Rng) -> Vec<IntContour> {
+        let mut rects = Vec::new();
+        for _ in 0..2000 {
+            let x = rng.gen_range(0..1000);
+            let y = rng.gen_range(0..1000);
+            rects.push(rect(x, y, x + rng.gen_range(1..20), y + rng.gen_range(1..20)));
+        }
+        for _ in 0..200 {
+            let x = rng.gen_range(0..100_000);
+            let y = rng.gen_range(0..100_000);
+            rects.push(rect(x, y, x + rng.gen_range(1..2000), y + rng.gen_range(1..2000)));
+        }
+        rects
+    }
+
+    fn max_column_segments(subj: &[IntContour], column_layout: ColumnLayout) -> (usize, usize) {
+        let options = IntOverlayOptions {
+            column_layout,
+            ..Default::default()
+        };
+        let mut mapper = Mapper::new(Layout::with_options(subj, &[], &options).unwrap());
+        mapper.add_ortho_contours(subj);
+        let max = mapper.columns.iter().map(|c| c.hz + c.vr).max().unwrap();
+        (mapper.columns.len(), max)
+    }
 
     #[test]
     fn test_0() {
@@ -145,8 +290,60 @@ mod tests {
             ].to_vec()
         ];
 
+        // a column takes at least `4 * sqrt(points)` points, so a lower minimum does not split a single rect
         let layout = Layout::with_subj_and_clip(&subj, &[], 1).unwrap();
 
-        assert_eq!(layout.columns_count, 2);
+        assert_eq!(layout.columns_count, 1);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_2() {
+        // a fixed seed, so the column sizes below are reproducible
+        let subj = clustered_rects(&mut StdRng::seed_from_u64(48));
+        let layout = Layout::with_options(&subj, &[], &IntOverlayOptions {
+            column_layout: ColumnLayout::Adaptive,
+            ..Default::default()
+        }).unwrap();
+
+        // columns cover the rect without gaps
+        assert!(layout.count() > 1);
+        assert_eq!(layout.borders(0).0, layout.rect.min_x);
+        assert_eq!(layout.borders(layout.count() - 1).1, layout.rect.max_x);
+        for i in 1..layout.count() {
+            let (min, max) = layout.borders(i);
+            assert!(min < max);
+            assert_eq!(layout.borders(i - 1).1, min);
+            assert_eq!(layout.index(min), i);
+            assert_eq!(layout.index(min - 1), i - 1);
+            assert_eq!(layout.index_inner_border_check(min), (i - 1, true));
+        }
+
+        let (uniform_count, uniform_max) = max_column_segments(&subj, ColumnLayout::Uniform);
+        let (adaptive_count, adaptive_max) = max_column_segments(&subj, ColumnLayout::Adaptive);
+        assert!(adaptive_count <= uniform_count);
+        assert!(2 * adaptive_max < uniform_max);
+    }
+
+    #[test]
+    fn test_3() {
+        let mut rng = StdRng::seed_from_u64(48);
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.options.min_count_per_column_power = 2;
+
+        for _ in 0..10 {
+            let subj = clustered_rects(&mut rng);
+            let clip = clustered_rects(&mut rng);
+
+            let mut results = Vec::new();
+            for column_layout in [ColumnLayout::Uniform, ColumnLayout::Adaptive] {
+                overlay.options.column_layout = column_layout;
+                overlay.init_with_ortho_contours(&subj, &clip).expect("OK");
+                let mut shapes = overlay.overlay(OverlayRule::Xor, FillRule::NonZero);
+                shapes.canonicalize();
+                results.push(shapes);
+            }
+
+            assert_eq!(results[0], results[1]);
+        }
+    }
+}
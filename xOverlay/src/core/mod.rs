@@ -1,9 +1,13 @@
+pub mod canonical;
+pub mod column_layout;
 pub mod direction;
 pub mod fill;
 pub mod fill_rule;
 pub(crate) mod layout;
 pub mod options;
 pub mod overlay_rule;
+pub mod progress;
 pub mod shape_type;
+pub mod sweep;
 pub mod winding;
 pub mod solver;
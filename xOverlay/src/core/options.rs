@@ -1,3 +1,4 @@
+use crate::core::column_layout::ColumnLayout;
 use crate::core::direction::ContourDirection;
 
 /// Configuration options for polygon Boolean operations using [`Overlay`].
@@ -6,6 +7,7 @@ use crate::core::direction::ContourDirection;
 /// during the Boolean operation process. You can use this to adjust output
 /// direction, eliminate small artifacts, or retain collinear points.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IntOverlayOptions {
     /// Preserve collinear points in the input before Boolean operations.
     pub preserve_input_collinear: bool,
@@ -20,7 +22,19 @@ pub struct IntOverlayOptions {
     pub min_output_area: u64,
 
     /// Log of minimum segments count per column
-    pub min_count_per_column_power: u32
+    pub min_count_per_column_power: u32,
+
+    /// How the input is split into columns.
+    pub column_layout: ColumnLayout,
+
+    /// Track which input contours produce every segment, required by `overlay_with_sources`.
+    /// Available with the `provenance` feature, segments store no sources without it.
+    #[cfg(feature = "provenance")]
+    pub track_sources: bool,
+
+    /// Output shapes in the canonical order, see [`Canonical`](crate::core::canonical::Canonical).
+    /// The result does not depend on the solver and the column layout.
+    pub canonical_output: bool,
 }
 
 impl Default for IntOverlayOptions {
@@ -31,6 +45,10 @@ impl Default for IntOverlayOptions {
             preserve_output_collinear: false,
             min_output_area: 0,
             min_count_per_column_power: 6,
+            column_layout: ColumnLayout::Uniform,
+            #[cfg(feature = "provenance")]
+            track_sources: false,
+            canonical_output: false,
         }
     }
 }
@@ -43,6 +61,10 @@ impl IntOverlayOptions {
             preserve_output_collinear: true,
             min_output_area: 0,
             min_count_per_column_power: 6,
+            column_layout: ColumnLayout::Uniform,
+            #[cfg(feature = "provenance")]
+            track_sources: false,
+            canonical_output: false,
         }
     }
     pub fn keep_output_points() -> Self {
@@ -52,6 +74,10 @@ impl IntOverlayOptions {
             preserve_output_collinear: true,
             min_output_area: 0,
             min_count_per_column_power: 6,
+            column_layout: ColumnLayout::Uniform,
+            #[cfg(feature = "provenance")]
+            track_sources: false,
+            canonical_output: false,
         }
     }
 }
\ No newline at end of file
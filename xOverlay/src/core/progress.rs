@@ -0,0 +1,348 @@
+use crate::core::solver::Solver;
+use alloc::sync::Arc;
+use core::fmt;
+#[cfg(feature = "stats")]
+use core::sync::atomic::AtomicU64;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+#[cfg(feature = "stats")]
+use core::time::Duration;
+
+/// A flag shared between threads to abort running overlays.
+///
+/// Clones share the same flag, so a token can be cancelled from any thread while an overlay
+/// which holds a clone in its [`Solver`] is running.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    flag: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests all overlays which use the token to stop.
+    #[inline]
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+
+    /// Clears the flag, so the token can be used for the next run.
+    #[inline]
+    pub fn reset(&self) {
+        self.flag.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Phases of an overlay in the order of execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Computing the column layout of the input.
+    Layout,
+    /// Distributing the input segments by columns.
+    Mapping,
+    /// Splitting and filling segments, the steps are columns.
+    Fill,
+    /// Building the graph links and nodes.
+    Graph,
+    /// Extracting contours from the graph.
+    Extraction,
+}
+
+/// Progress of an overlay phase.
+/// - `phase`: The running phase.
+/// - `done`: Count of finished steps of the phase.
+/// - `total`: Count of all steps of the phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub phase: Phase,
+    pub done: usize,
+    pub total: usize,
+}
+
+/// Receives the progress of overlays. Column phases run in parallel, so it can be called from
+/// several threads at once.
+pub trait ProgressListener: Send + Sync {
+    fn on_progress(&self, progress: Progress);
+}
+
+impl<F: Fn(Progress) + Send + Sync> ProgressListener for F {
+    #[inline]
+    fn on_progress(&self, progress: Progress) {
+        self(progress)
+    }
+}
+
+impl fmt::Debug for dyn ProgressListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressListener")
+    }
+}
+
+/// Count of progress reports inside a phase, apart from its start and finish.
+const REPORTS_PER_PHASE: usize = 100;
+
+/// Reports the progress of a phase and checks the cancellation.
+pub(crate) struct PhaseMonitor {
+    cancel: Option<CancelToken>,
+    progress: Option<Arc<dyn ProgressListener>>,
+    phase: Phase,
+    total: usize,
+    step: usize,
+    done: AtomicUsize,
+    #[cfg(feature = "stats")]
+    started: std::time::Instant,
+    /// Duration of the finished phase in nanoseconds.
+    #[cfg(feature = "stats")]
+    elapsed: AtomicU64,
+}
+
+impl PhaseMonitor {
+    /// Starts a phase of `total` steps.
+    /// - Returns: `None` if the run is already cancelled.
+    pub(crate) fn start(solver: &Solver, phase: Phase, total: usize) -> Option<Self> {
+        let monitor = Self {
+            cancel: solver.cancel.clone(),
+            progress: solver.progress.clone(),
+            phase,
+            total,
+            step: (total / REPORTS_PER_PHASE).max(1),
+            done: AtomicUsize::new(0),
+            #[cfg(feature = "stats")]
+            started: std::time::Instant::now(),
+            #[cfg(feature = "stats")]
+            elapsed: AtomicU64::new(0),
+        };
+
+        if monitor.is_cancelled() {
+            return None;
+        }
+        monitor.report(0);
+
+        Some(monitor)
+    }
+
+    #[inline]
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(CancelToken::is_cancelled)
+    }
+
+    /// Marks one step as done.
+    /// - Returns: `false` if the run is cancelled.
+    #[inline]
+    pub(crate) fn tick(&self) -> bool {
+        if self.progress.is_some() {
+            let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+            if done.is_multiple_of(self.step) && done < self.total {
+                self.report(done);
+            }
+        }
+        !self.is_cancelled()
+    }
+
+    /// Finishes the phase.
+    /// - Returns: `false` if the run is cancelled.
+    #[inline]
+    pub(crate) fn finish(&self) -> bool {
+        if self.is_cancelled() {
+            return false;
+        }
+        #[cfg(feature = "stats")]
+        self.elapsed
+            .store(self.started.elapsed().as_nanos() as u64, Ordering::Relaxed);
+        self.report(self.total);
+        true
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub(crate) fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// Duration of the phase, it is zero until the phase is finished.
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub(crate) fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.elapsed.load(Ordering::Relaxed))
+    }
+
+    #[inline]
+    fn report(&self, done: usize) {
+        if let Some(progress) = &self.progress {
+            progress.on_progress(Progress {
+                phase: self.phase,
+                done,
+                total: self.total,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::core::progress::{CancelToken, Phase, Progress};
+    use crate::core::solver::Solver;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::error::OrthoError;
+    use crate::ortho::fracture::FractureOptions;
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::ortho::raster::RasterLayout;
+    use crate::test_util::grid;
+    use alloc::sync::Arc;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_float::int::point::IntPoint;
+    use std::sync::Mutex;
+
+    fn overlay(solver: Solver) -> OrthoOverlay<ShapeCountBoolean> {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean> {
+            solver,
+            ..Default::default()
+        };
+        overlay.options.min_count_per_column_power = 2;
+        overlay
+    }
+
+    #[test]
+    fn test_0() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let listener = reports.clone();
+
+        let mut overlay = overlay(Solver::serial());
+        overlay.solver.progress = Some(Arc::new(move |progress: Progress| {
+            listener.lock().unwrap().push(progress);
+        }));
+
+        overlay.init_with_ortho_contours(&grid(16, 0), &grid(16, 5)).expect("OK");
+        let shapes = overlay.try_overlay(OverlayRule::Xor, FillRule::EvenOdd).expect("OK");
+        assert!(!shapes.is_empty());
+
+        let reports = reports.lock().unwrap();
+        let mut phases: Vec<Phase> = reports.iter().map(|p| p.phase).collect();
+        phases.dedup();
+        assert_eq!(
+            phases,
+            vec![Phase::Layout, Phase::Mapping, Phase::Fill, Phase::Graph, Phase::Extraction]
+        );
+
+        let fill: Vec<_> = reports.iter().filter(|p| p.phase == Phase::Fill).collect();
+        assert!(fill.len() > 2);
+        assert_eq!(fill[0].done, 0);
+        assert!(fill.windows(2).all(|w| w[0].done < w[1].done));
+        assert_eq!(fill[fill.len() - 1].done, fill[0].total);
+        assert_eq!(fill[0].total, overlay.columns.len());
+    }
+
+    #[test]
+    fn test_1() {
+        let token = CancelToken::new();
+        let mut overlay = overlay(Solver {
+            cancel: Some(token.clone()),
+            ..Solver::serial()
+        });
+
+        token.cancel();
+        assert!(matches!(
+            overlay.init_with_ortho_contours(&grid(4, 0), &[]),
+            Err(OrthoError::Cancelled)
+        ));
+        assert!(overlay.overlay(OverlayRule::Subject, FillRule::NonZero).is_empty());
+
+        token.reset();
+        overlay.init_with_ortho_contours(&grid(4, 0), &[]).expect("OK");
+        assert_eq!(overlay.overlay(OverlayRule::Subject, FillRule::NonZero).len(), 16);
+    }
+
+    #[test]
+    fn test_2() {
+        for solver in [Solver::serial(), Solver::default()] {
+            let token = CancelToken::new();
+            let canceller = token.clone();
+
+            let mut overlay = overlay(Solver {
+                cancel: Some(token.clone()),
+                progress: Some(Arc::new(move |progress: Progress| {
+                    // cancel in the middle of the fill phase
+                    if progress.phase == Phase::Fill && 2 * progress.done >= progress.total {
+                        canceller.cancel();
+                    }
+                })),
+                min_parallel_columns: 2,
+                ..solver
+            });
+
+            let subj = grid(16, 0);
+            let clip = grid(16, 5);
+            overlay.init_with_ortho_contours(&subj, &clip).expect("OK");
+            assert!(matches!(
+                overlay.try_overlay(OverlayRule::Xor, FillRule::EvenOdd),
+                Err(OrthoError::Cancelled)
+            ));
+
+            // the overlay is reusable after a new init
+            overlay.solver.progress = None;
+            token.reset();
+            overlay.init_with_ortho_contours(&subj, &clip).expect("OK");
+            let shapes = overlay.try_overlay(OverlayRule::Xor, FillRule::EvenOdd).expect("OK");
+
+            let mut expected = self::overlay(Solver::serial());
+            expected.init_with_ortho_contours(&subj, &clip).expect("OK");
+            assert_eq!(shapes, expected.overlay(OverlayRule::Xor, FillRule::EvenOdd));
+        }
+    }
+
+    #[test]
+    fn test_3() {
+        let token = CancelToken::new();
+        let mut overlay = overlay(Solver {
+            cancel: Some(token.clone()),
+            ..Solver::serial()
+        });
+        let subj = grid(4, 0);
+        let layout = RasterLayout::new(IntPoint::new(0, 0), 10, 10, 8, 8).expect("OK");
+
+        // every try_ entry point reports the cancellation, the others return an empty result
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
+        token.cancel();
+        assert!(matches!(
+            overlay.try_fracture(OverlayRule::Subject, FillRule::NonZero, &FractureOptions::default()),
+            Err(OrthoError::Cancelled)
+        ));
+        assert!(overlay.fracture(OverlayRule::Subject, FillRule::NonZero, &FractureOptions::default()).is_empty());
+
+        token.reset();
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
+        token.cancel();
+        assert!(matches!(
+            overlay.try_overlay_keyhole(OverlayRule::Subject, FillRule::NonZero),
+            Err(OrthoError::Cancelled)
+        ));
+        assert!(overlay.overlay_keyhole(OverlayRule::Subject, FillRule::NonZero).is_empty());
+
+        token.reset();
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
+        token.cancel();
+        assert!(matches!(
+            overlay.try_rasterize(OverlayRule::Subject, FillRule::NonZero, layout),
+            Err(OrthoError::Cancelled)
+        ));
+        let raster = overlay.rasterize(OverlayRule::Subject, FillRule::NonZero, layout);
+        assert!(raster.areas().iter().all(|&area| area == 0));
+
+        token.reset();
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
+        let raster = overlay.try_rasterize(OverlayRule::Subject, FillRule::NonZero, layout).expect("OK");
+        assert_eq!(raster.areas().iter().sum::<u64>(), 16 * 100);
+    }
+}
@@ -1,11 +1,246 @@
+use crate::core::progress::{CancelToken, ProgressListener};
+use crate::core::sweep::SweepAxis;
+use alloc::sync::Arc;
+#[cfg(feature = "multithreading")]
+use core::fmt::Debug;
+
+/// Runs the parallel stages of an overlay.
+///
+/// Every parallel stage is started inside [`Executor::install`], so rayon iterators and joins
+/// inside it run on the executor threads. [`rayon::ThreadPool`] is an executor, a dedicated pool
+/// can be shared by many overlays.
+#[cfg(feature = "multithreading")]
+pub trait Executor: Debug + Send + Sync {
+    /// Runs `op` and returns when it is finished.
+    fn install(&self, op: &mut (dyn FnMut() + Send));
+}
+
+#[cfg(feature = "multithreading")]
+impl Executor for rayon::ThreadPool {
+    #[inline]
+    fn install(&self, op: &mut (dyn FnMut() + Send)) {
+        rayon::ThreadPool::install(self, op)
+    }
+}
+
+/// Controls how an overlay uses threads.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Solver {
-    pub multithreading: bool
+    /// Process columns, links and tiles in parallel. Ignored if the crate is built without
+    /// the `multithreading` feature.
+    pub multithreading: bool,
+
+    /// Approximate maximum count of tasks a parallel stage is split into, `0` means no limit.
+    /// The value `1` makes every stage serial.
+    pub max_parallelism: usize,
+
+    /// Minimum count of columns to prepare them and build their links in parallel.
+    pub min_parallel_columns: usize,
+
+    /// Minimum count of graph links to sort their ends in parallel, the columns must be parallel too.
+    pub min_parallel_links: usize,
+
+    /// Minimum count of tiles to process them in parallel.
+    pub min_parallel_tiles: usize,
+
+    /// Minimum count of graph links to extract contours in parallel.
+    pub min_parallel_extract_links: usize,
+
+    /// Minimum count of holes to bind them to shapes in parallel.
+    pub min_parallel_holes: usize,
+
+    /// Axis along which the input is split. The result of rows is always in the canonical form,
+    /// so it is identical to the result of columns if `options.canonical_output` is set.
+    /// The debug picture and streamed input (`init_with_source`, `stream`, `init_with_iter`)
+    /// are always split by columns.
+    pub sweep_axis: SweepAxis,
+
+    /// Executor of the parallel stages, the rayon global pool is used if it is `None`.
+    /// It is not serialized.
+    #[cfg(feature = "multithreading")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub executor: Option<Arc<dyn Executor>>,
+
+    /// Token to abort a run, it is checked between phases and inside column loops.
+    /// A cancelled run returns `OrthoError::Cancelled`, infallible methods return an empty result.
+    /// It is not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub cancel: Option<CancelToken>,
+
+    /// Receives the progress of every phase. It is not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub progress: Option<Arc<dyn ProgressListener>>,
 }
 
 impl Default for Solver {
     fn default() -> Self {
         Self {
             multithreading: true,
+            max_parallelism: 0,
+            min_parallel_columns: 5,
+            min_parallel_links: 0,
+            min_parallel_tiles: 2,
+            min_parallel_extract_links: 1 << 16,
+            min_parallel_holes: 1 << 12,
+            sweep_axis: SweepAxis::Columns,
+            #[cfg(feature = "multithreading")]
+            executor: None,
+            cancel: None,
+            progress: None,
+        }
+    }
+}
+
+impl Solver {
+    /// Creates a solver which never goes parallel.
+    #[inline]
+    pub fn serial() -> Self {
+        Self {
+            multithreading: false,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(CancelToken::is_cancelled)
+    }
+
+    /// Checks if a stage over `count` items with the threshold `min_count` must be parallel.
+    #[inline]
+    pub(crate) fn is_parallel(&self, count: usize, min_count: usize) -> bool {
+        cfg!(feature = "multithreading") && self.multithreading && self.max_parallelism != 1 && count >= min_count
+    }
+
+    /// Minimum count of items per task, so `count` items are split into at most `max_parallelism` tasks.
+    #[cfg(feature = "multithreading")]
+    #[inline]
+    pub(crate) fn min_task_len(&self, count: usize) -> usize {
+        if self.max_parallelism == 0 {
+            1
+        } else {
+            count.div_ceil(self.max_parallelism).max(1)
         }
     }
-}
\ No newline at end of file
+
+    /// Count of tasks to split a stage into. It must be called inside [`Self::install`]
+    /// to count the threads of the executor.
+    #[cfg(feature = "multithreading")]
+    #[inline]
+    pub(crate) fn tasks_count(&self) -> usize {
+        let threads = rayon::current_num_threads();
+        if self.max_parallelism == 0 {
+            threads
+        } else {
+            self.max_parallelism.min(threads)
+        }
+    }
+
+    /// Runs `op` on the executor if it is set, otherwise on the current thread.
+    #[cfg(feature = "multithreading")]
+    pub(crate) fn install<R: Send, F: FnOnce() -> R + Send>(&self, op: F) -> R {
+        let executor = if let Some(executor) = &self.executor {
+            executor
+        } else {
+            return op();
+        };
+
+        let mut op = Some(op);
+        let mut result = None;
+        executor.install(&mut || {
+            if let Some(op) = op.take() {
+                result = Some(op());
+            }
+        });
+
+        result.expect("executor must run the operation")
+    }
+}
+
+#[cfg(all(test, feature = "multithreading"))]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::core::solver::{Executor, Solver};
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::test_util::grid;
+    use alloc::sync::Arc;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use i_shape::int::shape::IntShapes;
+
+    #[derive(Debug)]
+    struct CountingExecutor {
+        pool: rayon::ThreadPool,
+        count: AtomicUsize,
+    }
+
+    impl Executor for CountingExecutor {
+        fn install(&self, op: &mut (dyn FnMut() + Send)) {
+            self.count.fetch_add(1, Ordering::Relaxed);
+            self.pool.install(op)
+        }
+    }
+
+    fn overlay(solver: Solver) -> IntShapes {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean> {
+            solver,
+            ..Default::default()
+        };
+        overlay.options.min_count_per_column_power = 2;
+        overlay.init_with_ortho_contours(&grid(16, 0), &grid(16, 5)).expect("OK");
+        overlay.overlay(OverlayRule::Xor, FillRule::EvenOdd)
+    }
+
+    #[test]
+    fn test_0() {
+        let expected = overlay(Solver::serial());
+
+        let executor = Arc::new(CountingExecutor {
+            pool: rayon::ThreadPoolBuilder::new().num_threads(2).build().expect("OK"),
+            count: AtomicUsize::new(0),
+        });
+
+        let solver = Solver {
+            max_parallelism: 2,
+            min_parallel_columns: 2,
+            executor: Some(executor.clone()),
+            ..Default::default()
+        };
+
+        assert_eq!(overlay(solver), expected);
+        assert!(executor.count.load(Ordering::Relaxed) > 0);
+    }
+
+    #[test]
+    fn test_1() {
+        let executor = Arc::new(CountingExecutor {
+            pool: rayon::ThreadPoolBuilder::new().num_threads(2).build().expect("OK"),
+            count: AtomicUsize::new(0),
+        });
+
+        // the thresholds are never reached
+        let solver = Solver {
+            min_parallel_columns: usize::MAX,
+            min_parallel_links: usize::MAX,
+            executor: Some(executor.clone()),
+            ..Default::default()
+        };
+
+        assert_eq!(overlay(solver), overlay(Solver::serial()));
+        assert_eq!(executor.count.load(Ordering::Relaxed), 0);
+
+        let solver = Solver::default();
+        assert!(!solver.is_parallel(4, solver.min_parallel_columns));
+        assert!(solver.is_parallel(5, solver.min_parallel_columns));
+        assert_eq!(
+            Solver {
+                max_parallelism: 3,
+                ..Default::default()
+            }
+            .min_task_len(10),
+            4
+        );
+    }
+}
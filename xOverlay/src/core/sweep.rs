@@ -0,0 +1,325 @@
+#[cfg(feature = "provenance")]
+use crate::ortho::provenance::SourcedContour;
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+use i_float::int::rect::IntRect;
+use i_shape::int::shape::{IntContour, IntShape, IntShapes};
+
+/// Defines the axis along which the overlay splits the input.
+/// - `Columns`: The input is split by x into vertical columns.
+/// - `Rows`: The input is split by y into horizontal rows, it suits tall and narrow input.
+///   The input is transposed before the run and the result is transposed back to the canonical form,
+///   the canonical form does not depend on the axis. Column results are canonical only with
+///   `options.canonical_output`.
+/// - `Auto`: Rows are taken if the bounding box is taller than wide, where the height is weighted
+///   by the count of horizontal segments and the width by the count of vertical ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SweepAxis {
+    Columns,
+    Rows,
+    Auto,
+}
+
+impl SweepAxis {
+    /// Checks if the input must be swept by rows.
+    pub(crate) fn is_rows(&self, subj: &[IntContour], clip: &[IntContour]) -> bool {
+        match self {
+            SweepAxis::Columns => false,
+            SweepAxis::Rows => true,
+            SweepAxis::Auto => Self::is_tall(subj, clip),
+        }
+    }
+
+    fn is_tall(subj: &[IntContour], clip: &[IntContour]) -> bool {
+        let rect = if let Some(rect) = IntRect::with_iter(subj.iter().chain(clip.iter()).flatten()) {
+            rect
+        } else {
+            return false;
+        };
+
+        let mut hz_count = 0u64;
+        let mut vr_count = 0u64;
+        for contour in subj.iter().chain(clip.iter()) {
+            let mut p0 = if let Some(&p) = contour.last() { p } else { continue };
+            for &p in contour.iter() {
+                if p.x == p0.x {
+                    vr_count += 1;
+                } else {
+                    hz_count += 1;
+                }
+                p0 = p;
+            }
+        }
+
+        rect.height() as u64 * hz_count > rect.width() as u64 * vr_count
+    }
+}
+
+/// Swaps x and y of geometry. Swapping mirrors the geometry, so contours are also reversed
+/// to keep their direction, the first point stays first. Transposing twice gives the same geometry.
+pub(crate) trait Transpose {
+    fn transpose(&mut self);
+}
+
+impl Transpose for [IntPoint] {
+    #[inline]
+    fn transpose(&mut self) {
+        for p in self.iter_mut() {
+            *p = IntPoint::new(p.y, p.x);
+        }
+        if self.len() > 2 {
+            self[1..].reverse();
+        }
+    }
+}
+
+impl Transpose for IntShape {
+    #[inline]
+    fn transpose(&mut self) {
+        for contour in self.iter_mut() {
+            contour.transpose();
+        }
+    }
+}
+
+impl Transpose for IntShapes {
+    #[inline]
+    fn transpose(&mut self) {
+        for shape in self.iter_mut() {
+            shape.transpose();
+        }
+    }
+}
+
+#[cfg(feature = "provenance")]
+impl Transpose for SourcedContour {
+    #[inline]
+    fn transpose(&mut self) {
+        self.points.transpose();
+        // edge `i` goes from `points[i]` to the next point, reversed contours walk the edges backwards
+        self.edges.reverse();
+    }
+}
+
+#[cfg(feature = "provenance")]
+impl Transpose for [SourcedContour] {
+    #[inline]
+    fn transpose(&mut self) {
+        for contour in self.iter_mut() {
+            contour.transpose();
+        }
+    }
+}
+
+impl Transpose for IntRect {
+    #[inline]
+    fn transpose(&mut self) {
+        *self = IntRect::new(self.min_y, self.max_y, self.min_x, self.max_x);
+    }
+}
+
+/// Returns transposed copies of the contours.
+pub(crate) fn transposed(contours: &[IntContour]) -> Vec<IntContour> {
+    contours
+        .iter()
+        .map(|contour| {
+            let mut contour = contour.clone();
+            contour.transpose();
+            contour
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::core::sweep::SweepAxis;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::fracture::{FractureOptions, Slicing};
+    use crate::ortho::overlay::OrthoOverlay;
+    #[cfg(feature = "provenance")]
+    use crate::ortho::provenance::{InputContour, SourcedShape};
+    use crate::ortho::raster::RasterLayout;
+    use crate::test_util::rect;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_float::int::point::IntPoint;
+    use i_float::int::rect::IntRect;
+    use i_shape::int::shape::IntContour;
+    use rand::{Rng, thread_rng};
+
+    // tall rects, some of them are clockwise
+    fn tall_rects(n: usize) -> Vec<IntContour> {
+        let mut rng = thread_rng();
+        (0..n)
+            .map(|_| {
+                let x = rng.gen_range(-20..20);
+                let y = rng.gen_range(-200..200);
+                let w = rng.gen_range(1..10);
+                let h = rng.gen_range(1..100);
+                let mut contour = rect(x, y, x + w, y + h);
+                if rng.gen_bool(0.3) {
+                    contour.reverse();
+                }
+                contour
+            })
+            .collect()
+    }
+
+    // every output edge with its ends in the ascending order and its sources
+    #[cfg(feature = "provenance")]
+    fn sourced_edges(shapes: &[SourcedShape]) -> Vec<(IntPoint, IntPoint, Vec<InputContour>)> {
+        let mut edges = Vec::new();
+        for contour in shapes.iter().flatten() {
+            let n = contour.points.len();
+            for (i, sources) in contour.edges.iter().enumerate() {
+                let (a, b) = (contour.points[i], contour.points[(i + 1) % n]);
+                edges.push((a.min(b), a.max(b), sources.clone()));
+            }
+        }
+        edges.sort();
+        edges
+    }
+
+    fn rect_keys(rects: &[IntRect]) -> Vec<(i32, i32, i32, i32)> {
+        let mut keys: Vec<_> = rects.iter().map(|r| (r.min_x, r.min_y, r.max_x, r.max_y)).collect();
+        keys.sort();
+        keys
+    }
+
+    fn overlay(sweep_axis: SweepAxis) -> OrthoOverlay<ShapeCountBoolean> {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.solver.sweep_axis = sweep_axis;
+        overlay.options.min_count_per_column_power = 2;
+        overlay.options.canonical_output = true;
+        overlay
+    }
+
+    #[test]
+    fn test_0() {
+        let tall = [rect(0, 0, 10, 100)];
+        let wide = [rect(0, 0, 100, 10)];
+
+        assert!(SweepAxis::Auto.is_rows(&tall, &[]));
+        assert!(!SweepAxis::Auto.is_rows(&wide, &[]));
+        assert!(!SweepAxis::Auto.is_rows(&[], &[]));
+        assert!(SweepAxis::Rows.is_rows(&wide, &[]));
+        assert!(!SweepAxis::Columns.is_rows(&tall, &[]));
+
+        let mut overlay = overlay(SweepAxis::Auto);
+        overlay.init_with_ortho_contours(&tall, &[]).expect("OK");
+        assert!(overlay.transposed);
+        assert_eq!(
+            overlay.overlay(OverlayRule::Subject, FillRule::NonZero),
+            vec![vec![tall[0].clone()]]
+        );
+    }
+
+    #[test]
+    fn test_1() {
+        let mut columns = overlay(SweepAxis::Columns);
+        let mut rows = overlay(SweepAxis::Rows);
+
+        for _ in 0..100 {
+            let subj = tall_rects(20);
+            let clip = tall_rects(20);
+
+            for fill_rule in [
+                FillRule::EvenOdd,
+                FillRule::NonZero,
+                FillRule::Positive,
+                FillRule::Negative,
+            ] {
+                for overlay_rule in [OverlayRule::Union, OverlayRule::Difference, OverlayRule::Xor] {
+                    columns.init_with_ortho_contours(&subj, &clip).expect("OK");
+                    rows.init_with_ortho_contours(&subj, &clip).expect("OK");
+                    let expected = columns.overlay(overlay_rule, fill_rule);
+                    assert_eq!(rows.overlay(overlay_rule, fill_rule), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_2() {
+        let mut columns = overlay(SweepAxis::Columns);
+        let mut rows = overlay(SweepAxis::Rows);
+        #[cfg(feature = "provenance")]
+        {
+            columns.options.track_sources = true;
+            rows.options.track_sources = true;
+        }
+
+        let fracture = FractureOptions {
+            slicing: Slicing::Vertical,
+            max_height: 30,
+            ..Default::default()
+        };
+        let layout = RasterLayout::new(IntPoint::new(-25, -205), 7, 13, 8, 32).expect("OK");
+
+        for _ in 0..20 {
+            let subj = tall_rects(20);
+            let clip = tall_rects(20);
+
+            columns.init_with_ortho_contours(&subj, &clip).expect("OK");
+            rows.init_with_ortho_contours(&subj, &clip).expect("OK");
+
+            #[cfg(feature = "provenance")]
+            {
+                let expected = columns.overlay_with_sources(OverlayRule::Xor, FillRule::NonZero).expect("OK");
+                let result = rows.overlay_with_sources(OverlayRule::Xor, FillRule::NonZero).expect("OK");
+                let points = |shapes: &[SourcedShape]| -> Vec<Vec<IntContour>> {
+                    shapes.iter().map(|shape| shape.iter().map(|c| c.points.clone()).collect()).collect()
+                };
+                assert_eq!(points(&expected), points(&result));
+                assert_eq!(sourced_edges(&expected), sourced_edges(&result));
+            }
+
+            let expected = rect_keys(&columns.fracture(OverlayRule::Xor, FillRule::NonZero, &fracture));
+            let result = rect_keys(&rows.fracture(OverlayRule::Xor, FillRule::NonZero, &fracture));
+            assert_eq!(expected, result);
+
+            let expected = columns.rasterize(OverlayRule::Xor, FillRule::NonZero, layout);
+            let result = rows.rasterize(OverlayRule::Xor, FillRule::NonZero, layout);
+            assert_eq!(expected.layout, result.layout);
+            assert_eq!(expected.areas(), result.areas());
+
+            let expected = columns.overlay_keyhole(OverlayRule::Xor, FillRule::NonZero);
+            let result = rows.overlay_keyhole(OverlayRule::Xor, FillRule::NonZero);
+            assert_eq!(expected, result);
+        }
+    }
+
+    #[test]
+    fn test_3() {
+        // rows are taken without the canonical output too, their result is always canonical
+        let mut columns = overlay(SweepAxis::Columns);
+        let mut rows = overlay(SweepAxis::Rows);
+        let mut auto = overlay(SweepAxis::Auto);
+        rows.options.canonical_output = false;
+        auto.options.canonical_output = false;
+
+        for _ in 0..100 {
+            let subj = tall_rects(20);
+            let clip = tall_rects(20);
+
+            columns.init_with_ortho_contours(&subj, &clip).expect("OK");
+            rows.init_with_ortho_contours(&subj, &clip).expect("OK");
+            assert!(!columns.transposed);
+            assert!(rows.transposed);
+
+            let expected = columns.overlay(OverlayRule::Xor, FillRule::NonZero);
+            assert_eq!(rows.overlay(OverlayRule::Xor, FillRule::NonZero), expected);
+
+            let expected = columns.overlay_keyhole(OverlayRule::Difference, FillRule::EvenOdd);
+            assert_eq!(rows.overlay_keyhole(OverlayRule::Difference, FillRule::EvenOdd), expected);
+        }
+
+        let tall = [rect(0, 0, 10, 100)];
+        auto.init_with_ortho_contours(&tall, &[]).expect("OK");
+        assert!(auto.transposed);
+        assert_eq!(auto.overlay(OverlayRule::Subject, FillRule::NonZero), vec![vec![tall[0].clone()]]);
+    }
+}
@@ -8,7 +8,8 @@ where
     fn empty() -> Self;
     fn new(subj: i16, clip: i16) -> Self;
     fn with_shape_type(shape_type: ShapeType) -> (Self, Self);
-    fn add(self, count: Self) -> Self;
+    /// - Returns: The sum, or `None` if a count overflows.
+    fn add(self, count: Self) -> Option<Self>;
     fn apply(&mut self, count: Self);
     fn invert(self) -> Self;
 }
\ No newline at end of file
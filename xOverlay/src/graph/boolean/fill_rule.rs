@@ -3,6 +3,7 @@ use crate::core::fill_rule::FillRule;
 use crate::core::winding::WindingCount;
 use crate::graph::boolean::winding_count::ShapeCountBoolean;
 use crate::ortho::column::Column;
+use crate::ortho::error::OrthoError;
 
 struct EvenOddStrategy;
 struct NonZeroStrategy;
@@ -14,8 +15,8 @@ impl FillStrategy<ShapeCountBoolean> for EvenOddStrategy {
     fn add_and_fill(
         this: ShapeCountBoolean,
         bot: ShapeCountBoolean,
-    ) -> (ShapeCountBoolean, SegmentFill) {
-        let top = bot.add(this);
+    ) -> Option<(ShapeCountBoolean, SegmentFill)> {
+        let top = bot.add(this)?;
         let subj_top = 1 & top.subj as SegmentFill;
         let subj_bot = 1 & bot.subj as SegmentFill;
         let clip_top = 1 & top.clip as SegmentFill;
@@ -23,7 +24,7 @@ impl FillStrategy<ShapeCountBoolean> for EvenOddStrategy {
 
         let fill = subj_top | (subj_bot << 1) | (clip_top << 2) | (clip_bot << 3);
 
-        (top, fill)
+        Some((top, fill))
     }
 }
 
@@ -32,8 +33,8 @@ impl FillStrategy<ShapeCountBoolean> for NonZeroStrategy {
     fn add_and_fill(
         this: ShapeCountBoolean,
         bot: ShapeCountBoolean,
-    ) -> (ShapeCountBoolean, SegmentFill) {
-        let top = bot.add(this);
+    ) -> Option<(ShapeCountBoolean, SegmentFill)> {
+        let top = bot.add(this)?;
         let subj_top = (top.subj != 0) as SegmentFill;
         let subj_bot = (bot.subj != 0) as SegmentFill;
         let clip_top = (top.clip != 0) as SegmentFill;
@@ -41,7 +42,7 @@ impl FillStrategy<ShapeCountBoolean> for NonZeroStrategy {
 
         let fill = subj_top | (subj_bot << 1) | (clip_top << 2) | (clip_bot << 3);
 
-        (top, fill)
+        Some((top, fill))
     }
 }
 
@@ -50,8 +51,8 @@ impl FillStrategy<ShapeCountBoolean> for PositiveStrategy {
     fn add_and_fill(
         this: ShapeCountBoolean,
         bot: ShapeCountBoolean,
-    ) -> (ShapeCountBoolean, SegmentFill) {
-        let top = bot.add(this);
+    ) -> Option<(ShapeCountBoolean, SegmentFill)> {
+        let top = bot.add(this)?;
         let subj_top = (top.subj > 0) as SegmentFill;
         let subj_bot = (bot.subj > 0) as SegmentFill;
         let clip_top = (top.clip > 0) as SegmentFill;
@@ -59,7 +60,7 @@ impl FillStrategy<ShapeCountBoolean> for PositiveStrategy {
 
         let fill = subj_top | (subj_bot << 1) | (clip_top << 2) | (clip_bot << 3);
 
-        (top, fill)
+        Some((top, fill))
     }
 }
 
@@ -68,8 +69,8 @@ impl FillStrategy<ShapeCountBoolean> for NegativeStrategy {
     fn add_and_fill(
         this: ShapeCountBoolean,
         bot: ShapeCountBoolean,
-    ) -> (ShapeCountBoolean, SegmentFill) {
-        let top = bot.add(this);
+    ) -> Option<(ShapeCountBoolean, SegmentFill)> {
+        let top = bot.add(this)?;
         let subj_top = (top.subj < 0) as SegmentFill;
         let subj_bot = (bot.subj < 0) as SegmentFill;
         let clip_top = (top.clip < 0) as SegmentFill;
@@ -77,12 +78,13 @@ impl FillStrategy<ShapeCountBoolean> for NegativeStrategy {
 
         let fill = subj_top | (subj_bot << 1) | (clip_top << 2) | (clip_bot << 3);
 
-        (top, fill)
+        Some((top, fill))
     }
 }
 
 impl Column<ShapeCountBoolean> {
-    pub(crate) fn fill_boolean(&mut self, fill_rule: FillRule) {
+    /// - Returns: `OrthoError::WindingOverflow` if a count overflows.
+    pub(crate) fn fill_boolean(&mut self, fill_rule: FillRule) -> Result<(), OrthoError> {
         match fill_rule {
             FillRule::EvenOdd => self.fill_with_strategy::<EvenOddStrategy>(),
             FillRule::NonZero => self.fill_with_strategy::<NonZeroStrategy>(),
@@ -5,6 +5,7 @@ use crate::core::overlay_rule::OverlayRule;
 use crate::graph::boolean::winding_count::ShapeCountBoolean;
 use crate::graph::link::{OverlayLink, OverlayLinkFilter};
 use crate::ortho::column::Column;
+use crate::ortho::raster::RasterBuffer;
 
 struct SubjectFilter;
 struct ClipFilter;
@@ -304,4 +305,22 @@ impl Column<ShapeCountBoolean> {
             }
         }
     }
+
+    pub(crate) fn rasterize_into(
+        &self,
+        overlay_rule: OverlayRule,
+        buffer: &mut RasterBuffer,
+    ) {
+        match overlay_rule {
+            OverlayRule::Subject => self.rasterize_with_filter::<SubjectFilter>(overlay_rule, buffer),
+            OverlayRule::Clip => self.rasterize_with_filter::<ClipFilter>(overlay_rule, buffer),
+            OverlayRule::Intersect => self.rasterize_with_filter::<IntersectFilter>(overlay_rule, buffer),
+            OverlayRule::Union => self.rasterize_with_filter::<UnionFilter>(overlay_rule, buffer),
+            OverlayRule::Difference => self.rasterize_with_filter::<DifferenceFilter>(overlay_rule, buffer),
+            OverlayRule::Xor => self.rasterize_with_filter::<XorFilter>(overlay_rule, buffer),
+            OverlayRule::InverseDifference => {
+                self.rasterize_with_filter::<InverseDifferenceFilter>(overlay_rule, buffer)
+            }
+        }
+    }
 }
\ No newline at end of file
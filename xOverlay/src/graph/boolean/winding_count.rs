@@ -2,6 +2,7 @@ use crate::core::shape_type::ShapeType;
 use crate::core::winding::WindingCount;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ShapeCountBoolean {
     pub subj: i16,
     pub clip: i16,
@@ -45,11 +46,11 @@ impl WindingCount for ShapeCountBoolean {
     }
 
     #[inline(always)]
-    fn add(self, count: Self) -> Self {
-        let subj = self.subj + count.subj;
-        let clip = self.clip + count.clip;
+    fn add(self, count: Self) -> Option<Self> {
+        let subj = self.subj.checked_add(count.subj)?;
+        let clip = self.clip.checked_add(count.clip)?;
 
-        Self { subj, clip }
+        Some(Self { subj, clip })
     }
 
     #[inline(always)]
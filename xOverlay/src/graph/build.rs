@@ -7,6 +7,7 @@ use alloc::vec::Vec;
 use i_float::int::point::IntPoint;
 use i_key_sort::bin_key::index::BinLayout;
 use i_shape::util::reserve::Reserve;
+#[cfg(feature = "multithreading")]
 use rayon::prelude::ParallelSliceMut;
 
 impl OverlayGraph {
@@ -77,6 +78,7 @@ impl OverlayGraph {
         }
     }
 
+    #[cfg_attr(not(feature = "multithreading"), allow(unused_variables))]
     fn build_ends(&mut self, multithreading: bool) {
         if let Some(layout) = self.bin_layout() {
             self.bin_store.init(layout);
@@ -115,11 +117,13 @@ impl OverlayGraph {
                 });
             }
 
+            #[cfg(feature = "multithreading")]
             if multithreading {
                 self.ends.par_sort_unstable_by(|a, b| a.point.cmp(&b.point));
-            } else {
-                self.ends.sort_unstable_by(|a, b| a.point.cmp(&b.point));
+                return;
             }
+
+            self.ends.sort_unstable_by(|a, b| a.point.cmp(&b.point));
         }
     }
 
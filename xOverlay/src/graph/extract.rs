@@ -7,15 +7,22 @@ use i_shape::int::path::ContourExtension;
 use i_shape::int::shape::{IntContour, IntShapes};
 use i_shape::int::simple::Simplify;
 use i_shape::util::reserve::Reserve;
+use crate::bind::keyhole::Keyhole;
 use crate::bind::segment::{ContourIndex, IdSegment};
 use crate::bind::solver::{JoinHoles, LeftBottomSegment};
+use crate::core::canonical::Canonical;
 use crate::core::direction::ContourDirection;
 use crate::core::overlay_rule::OverlayRule;
+use crate::core::solver::Solver;
 use crate::geom::v_segment::VSegment;
 use crate::graph::link::{OverlayLink, OverlayLinkFilter};
 use crate::graph::{BooleanExtractionBuffer, OverlayGraph};
 use crate::graph::nearest_vector::NearestVector;
 use crate::graph::node::OverlayNode;
+#[cfg(feature = "multithreading")]
+use crate::sub::disjoint_set::DisjointSet;
+#[cfg(feature = "multithreading")]
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 impl OverlayGraph {
     /// Extracts shapes from the overlay graph based on the specified overlay rule. This method is used to retrieve the final geometric shapes after boolean operations have been applied. It's suitable for most use cases where the minimum area of shapes is not a concern.
@@ -28,12 +35,16 @@ impl OverlayGraph {
     /// - Each path `Vec<IntPoint>` is a sequence of points, forming a closed path.
     ///
     /// Note: Outer boundary paths have a counterclockwise order, and holes have a clockwise order.
+    /// If `options.canonical_output` is on, the shapes are in the canonical form, see [`Canonical`].
     #[inline]
     pub fn extract_shapes(&mut self, overlay_rule: OverlayRule) -> IntShapes {
         let mut buffer = self.buffer.take().unwrap_or_default();
         self.links.filter_by_overlay_into(overlay_rule, &mut buffer.visited);
-        let shapes = self.extract(overlay_rule, &mut buffer);
+        let mut shapes = self.extract(overlay_rule, &mut buffer);
         self.buffer = Some(buffer);
+        if self.options.canonical_output {
+            shapes.canonicalize();
+        }
         shapes
     }
 
@@ -55,6 +66,19 @@ impl OverlayGraph {
         self.buffer = Some(buffer);
     }
 
+    /// Extracts shapes from the overlay graph and joins every hole to its outer boundary through a zero-width
+    /// orthogonal bridge. It's suitable for consumers which can't handle holes.
+    /// - `overlay_rule`: The boolean operation rule to apply when extracting shapes from the graph.
+    /// - Returns: A single simple contour per shape.
+    ///
+    /// Note: Bridges go down from the left-bottom point of a hole to the nearest edge of the same shape.
+    #[inline]
+    pub fn extract_keyhole_contours(&mut self, overlay_rule: OverlayRule) -> Vec<IntContour> {
+        let clockwise = self.options.output_direction == ContourDirection::Clockwise;
+        let shapes = self.extract_shapes(overlay_rule);
+        shapes.iter().map(|shape| shape.keyhole(clockwise)).collect()
+    }
+
     fn extract(
         &self,
         overlay_rule: OverlayRule,
@@ -62,17 +86,57 @@ impl OverlayGraph {
     ) -> IntShapes {
         let clockwise = self.options.output_direction == ContourDirection::Clockwise;
 
-        let mut shapes = Vec::new();
-        let mut holes = Vec::new();
-        let mut anchors = Vec::new();
+        #[cfg(feature = "multithreading")]
+        if self.solver.is_parallel(buffer.visited.len(), self.solver.min_parallel_extract_links) {
+            let visited = &buffer.visited;
+            let contours = self.solver.install(|| {
+                let tasks = self.solver.tasks_count();
+                if tasks > 1 {
+                    Some(self.parallel_collect_contours(overlay_rule, visited, clockwise, tasks))
+                } else {
+                    None
+                }
+            });
+            if let Some(contours) = contours {
+                return contours.into_shapes(clockwise, &self.solver);
+            }
+        }
+
+        let mut contours = ExtractedContours::default();
 
         buffer.points.reserve_capacity(buffer.visited.len());
 
-        let mut link_index = 0;
-        let mut anchors_already_sorted = true;
-        while link_index < buffer.visited.len() {
+        self.walk_contours(
+            overlay_rule,
+            0..buffer.visited.len(),
+            buffer,
+            |_, contour, is_hole, is_modified| {
+                let anchor = is_hole.then(|| contour.hole_anchor(clockwise, is_modified));
+                contours.push(contour.to_vec(), anchor);
+            },
+        );
+
+        contours.into_shapes(clockwise, &self.solver)
+    }
+
+    /// Walks every contour which starts from the not visited links of `indices`, in the order of `indices`.
+    /// - `f`: Receives the start link index, the contour, whether it is a hole and whether it was simplified.
+    fn walk_contours<I, F>(
+        &self,
+        overlay_rule: OverlayRule,
+        indices: I,
+        buffer: &mut BooleanExtractionBuffer,
+        mut f: F,
+    ) where
+        I: Iterator<Item = usize>,
+        F: FnMut(usize, &[IntPoint], bool, bool),
+    {
+        let clockwise = self.options.output_direction == ContourDirection::Clockwise;
+
+        let mut indices = indices.peekable();
+        while let Some(&link_index) = indices.peek() {
             if buffer.visited.is_visited(link_index) {
-                link_index += 1;
+                indices.next();
                 continue;
             }
 
@@ -88,48 +152,114 @@ impl OverlayGraph {
                 buffer.points.validate(self.options.min_output_area, self.options.preserve_output_collinear);
 
             if !is_valid {
-                link_index += 1;
+                indices.next();
                 continue;
             }
 
-            let contour = buffer.points.as_slice().to_vec();
+            // the start link can be still free, so it is checked again
+            f(link_index, buffer.points.as_slice(), is_hole, is_modified);
+        }
+    }
 
-            if is_hole {
-                let mut v_segment = if clockwise {
-                    VSegment {
-                        a: contour[1],
-                        b: contour[2],
-                    }
-                } else {
-                    VSegment {
-                        a: contour[0],
-                        b: contour[contour.len() - 1],
-                    }
-                };
-                if is_modified {
-                    let most_left = contour.left_bottom_segment();
-                    if most_left != v_segment {
-                        v_segment = most_left;
-                        anchors_already_sorted = false;
-                    }
+    /// Links of different connected components never meet at a node, so every component is walked
+    /// independently with its own visited marks. Contours are merged by their start links and go
+    /// in the same order as the serial walk produces them.
+    #[cfg(feature = "multithreading")]
+    fn parallel_collect_contours(
+        &self,
+        overlay_rule: OverlayRule,
+        visited: &[bool],
+        clockwise: bool,
+        tasks: usize,
+    ) -> ExtractedContours {
+        let task_links = self.split_by_components(visited, tasks);
+
+        let mut records: Vec<StartedContour> = task_links
+            .par_iter()
+            .map(|links| {
+                let mut buffer = BooleanExtractionBuffer {
+                    points: Vec::new(),
+                    visited: vec![true; visited.len()],
                 };
+                for &i in links.iter() {
+                    buffer.visited[i] = false;
+                }
 
-                debug_assert_eq!(v_segment, contour.left_bottom_segment());
-                let id_data = ContourIndex::new_hole(holes.len());
-                anchors.push(IdSegment::with_segment(id_data, v_segment));
-                holes.push(contour);
-            } else {
-                shapes.push(vec![contour]);
+                let mut records = Vec::new();
+                self.walk_contours(
+                    overlay_rule,
+                    links.iter().copied(),
+                    &mut buffer,
+                    |link, contour, is_hole, is_modified| {
+                        records.push(StartedContour {
+                            link,
+                            anchor: is_hole.then(|| contour.hole_anchor(clockwise, is_modified)),
+                            contour: contour.to_vec(),
+                        });
+                    },
+                );
+                records
+            })
+            .flatten()
+            .collect();
+
+        // stable, contours which start from the same link keep their order
+        records.sort_by_key(|record| record.link);
+
+        let mut contours = ExtractedContours::default();
+        for record in records {
+            contours.push(record.contour, record.anchor);
+        }
+
+        contours
+    }
+
+    /// Splits the free links into at most `tasks` groups of whole connected components.
+    /// - Returns: The link indices of every group in ascending order.
+    #[cfg(feature = "multithreading")]
+    fn split_by_components(&self, visited: &[bool], tasks: usize) -> Vec<Vec<usize>> {
+        let mut roots = DisjointSet::new(self.nodes.len());
+        let mut count: usize = 0;
+        for (i, link) in self.links.iter().enumerate() {
+            if visited.is_visited(i) {
+                continue;
             }
+            count += 1;
+            roots.union(link.a.id, link.b.id);
         }
 
-        if !anchors_already_sorted {
-            anchors.sort_by(|s0, s1| s0.v_segment.a.cmp(&s1.v_segment.a));
+        let mut sizes = vec![0; self.nodes.len()];
+        for (i, link) in self.links.iter().enumerate() {
+            if visited.is_not_visited(i) {
+                let root = roots.root(link.a.id);
+                sizes[root] += 1;
+            }
         }
 
-        shapes.join_sorted_holes(holes, anchors, clockwise);
+        let target = count.div_ceil(tasks);
+        let mut groups = vec![Vec::new(); tasks];
+        let mut group_for_root = vec![usize::MAX; self.nodes.len()];
+        let mut group = 0;
+        let mut load = 0;
 
-        shapes
+        for (i, link) in self.links.iter().enumerate() {
+            if visited.is_visited(i) {
+                continue;
+            }
+            let root = roots.root(link.a.id);
+            if group_for_root[root] == usize::MAX {
+                if load >= target && group + 1 < tasks {
+                    group += 1;
+                    load = 0;
+                }
+                group_for_root[root] = group;
+                load += sizes[root];
+            }
+            groups[group_for_root[root]].push(i);
+        }
+
+        groups.retain(|links| !links.is_empty());
+        groups
     }
 
     fn find_contour(
@@ -170,36 +300,100 @@ impl OverlayGraph {
         buffer: &mut BooleanExtractionBuffer,
         output: &mut FlatContoursBuffer
     ) {
-        let clockwise = self.options.output_direction == ContourDirection::Clockwise;
         let len = buffer.visited.len();
         buffer.points.reserve_capacity(len);
         output.clear_and_reserve(len, 4);
 
-        let mut link_index = 0;
-        while link_index < len {
-            if buffer.visited.is_visited(link_index) {
-                link_index += 1;
-                continue;
-            }
+        self.walk_contours(overlay_rule, 0..len, buffer, |_, contour, _, _| output.add_contour(contour));
+    }
+}
 
-            let left_top_link = GraphUtil::find_left_top_link(&self.links, &self.nodes, link_index, &buffer.visited);
-            let link = unsafe { self.links.get_unchecked(left_top_link) };
-            let is_hole = overlay_rule.is_fill_top(link.fill);
+/// A contour found by a parallel walk.
+#[cfg(feature = "multithreading")]
+struct StartedContour {
+    link: usize,
+    contour: IntContour,
+    anchor: Option<(VSegment, bool)>,
+}
 
-            let direction = is_hole == clockwise;
-            let start_data = StartPathData::new(direction, link, left_top_link);
+#[derive(Default)]
+struct ExtractedContours {
+    shapes: IntShapes,
+    holes: Vec<IntContour>,
+    anchors: Vec<IdSegment>,
+    anchors_need_sort: bool,
+}
 
-            self.find_contour(&start_data, direction, buffer);
-            let (is_valid, _) =
-                buffer.points.validate(self.options.min_output_area, self.options.preserve_output_collinear);
+impl ExtractedContours {
+    /// - `anchor`: The left-bottom segment of a hole and whether it differs from the start segment,
+    ///   `None` for an outer contour.
+    #[inline]
+    fn push(&mut self, contour: IntContour, anchor: Option<(VSegment, bool)>) {
+        if let Some((v_segment, is_moved)) = anchor {
+            self.anchors_need_sort |= is_moved;
+            let id_data = ContourIndex::new_hole(self.holes.len());
+            self.anchors.push(IdSegment::with_segment(id_data, v_segment));
+            self.holes.push(contour);
+        } else {
+            self.shapes.push(vec![contour]);
+        }
+    }
 
-            if !is_valid {
-                link_index += 1;
-                continue;
-            }
+    fn into_shapes(self, clockwise: bool, solver: &Solver) -> IntShapes {
+        let Self {
+            mut shapes,
+            holes,
+            mut anchors,
+            anchors_need_sort,
+        } = self;
+
+        if anchors_need_sort {
+            anchors.sort_by(|s0, s1| s0.v_segment.a.cmp(&s1.v_segment.a));
+        }
 
-            output.add_contour(buffer.points.as_slice());
+        #[cfg(feature = "multithreading")]
+        if solver.is_parallel(holes.len(), solver.min_parallel_holes) {
+            solver.install(|| shapes.join_sorted_holes(holes, anchors, clockwise, solver.tasks_count()));
+            return shapes;
         }
+
+        #[cfg(not(feature = "multithreading"))]
+        let _ = solver;
+
+        shapes.join_sorted_holes(holes, anchors, clockwise, 1);
+
+        shapes
+    }
+}
+
+trait HoleAnchor {
+    fn hole_anchor(&self, clockwise: bool, is_modified: bool) -> (VSegment, bool);
+}
+
+impl HoleAnchor for [IntPoint] {
+    /// The anchor is the start segment of a hole if it was not simplified.
+    #[inline]
+    fn hole_anchor(&self, clockwise: bool, is_modified: bool) -> (VSegment, bool) {
+        let v_segment = if clockwise {
+            VSegment {
+                a: self[1],
+                b: self[2],
+            }
+        } else {
+            VSegment {
+                a: self[0],
+                b: self[self.len() - 1],
+            }
+        };
+        if is_modified {
+            let most_left = self.left_bottom_segment();
+            if most_left != v_segment {
+                return (most_left, true);
+            }
+        };
+
+        debug_assert_eq!(v_segment, self.left_bottom_segment());
+        (v_segment, false)
     }
 }
 
@@ -436,4 +630,80 @@ impl GraphUtil {
 
         vector_solver.best_id
     }
-}
\ No newline at end of file
+}
+#[cfg(all(test, feature = "multithreading"))]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::core::solver::Solver;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::test_util::{random_rects, rect};
+    use alloc::sync::Arc;
+    use alloc::vec::Vec;
+    use i_shape::int::shape::{IntContour, IntShapes};
+
+    fn parallel_solver() -> Solver {
+        Solver {
+            min_parallel_extract_links: 0,
+            min_parallel_holes: 0,
+            executor: Some(Arc::new(
+                rayon::ThreadPoolBuilder::new().num_threads(4).build().expect("OK"),
+            )),
+            ..Default::default()
+        }
+    }
+
+    fn overlay(
+        solver: Solver,
+        subj: &[IntContour],
+        clip: &[IntContour],
+        rule: OverlayRule,
+    ) -> IntShapes {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean> {
+            solver,
+            ..Default::default()
+        };
+        overlay.options.min_count_per_column_power = 2;
+        overlay.init_with_ortho_contours(subj, clip).expect("OK");
+        overlay.overlay(rule, FillRule::NonZero)
+    }
+
+    #[test]
+    fn test_0() {
+        // frames with holes and squares which touch each other by corners
+        let mut subj = Vec::new();
+        let mut clip = Vec::new();
+        for i in 0..24 {
+            for j in 0..24 {
+                let (x, y) = (10 * i, 10 * j);
+                if (i + j) % 2 == 0 {
+                    subj.push(rect(x, y, x + 10, y + 10));
+                }
+                // the top hole is found above the bottom one
+                clip.push(rect(x + 3, y + 1, x + 6, y + 4));
+                clip.push(rect(x + 3, y + 6, x + 6, y + 9));
+            }
+        }
+
+        let serial = overlay(Solver::serial(), &subj, &clip, OverlayRule::Difference);
+        let parallel = overlay(parallel_solver(), &subj, &clip, OverlayRule::Difference);
+
+        assert!(serial.iter().all(|shape| shape.len() == 3));
+        assert_eq!(parallel, serial);
+    }
+
+    #[test]
+    fn test_1() {
+        for _ in 0..100 {
+            let subj = random_rects(40, 100);
+            let clip = random_rects(20, 100);
+
+            for rule in [OverlayRule::Union, OverlayRule::Difference, OverlayRule::Xor] {
+                let serial = overlay(Solver::serial(), &subj, &clip, rule);
+                let parallel = overlay(parallel_solver(), &subj, &clip, rule);
+                assert_eq!(parallel, serial);
+            }
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use crate::core::overlay_rule::OverlayRule;
+use crate::graph::OverlayGraph;
+use crate::ortho::fracture::{FractureOptions, Slicing};
+use alloc::vec::Vec;
+use i_float::int::rect::IntRect;
+use i_shape::int::shape::IntContour;
+
+impl OverlayGraph {
+    pub(crate) fn fracture(&mut self, overlay_rule: OverlayRule, options: &FractureOptions) -> Vec<IntRect> {
+        let shapes = self.extract_shapes(overlay_rule);
+
+        let mut buffer = FractureBuffer::default();
+        let mut result = Vec::new();
+        for shape in shapes.iter() {
+            buffer.fracture_shape(shape, options, &mut result);
+        }
+
+        result
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    pos: i32,
+    min: i32,
+    max: i32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Strip {
+    min: i32,
+    max: i32,
+    start: i32,
+}
+
+#[derive(Default)]
+struct FractureBuffer {
+    edges: Vec<Edge>,
+    borders: Vec<i32>,
+    strips: Vec<Strip>,
+    next_strips: Vec<Strip>,
+    rects: Vec<IntRect>,
+    other_rects: Vec<IntRect>,
+}
+
+impl FractureBuffer {
+    fn fracture_shape(&mut self, shape: &[IntContour], options: &FractureOptions, output: &mut Vec<IntRect>) {
+        let mut rects = core::mem::take(&mut self.rects);
+        rects.clear();
+        self.slice(shape, options.slicing, &mut rects);
+
+        if options.sliver_size > 0 {
+            let slivers = rects.slivers_count(options.sliver_size);
+            if slivers > 0 {
+                let mut other = core::mem::take(&mut self.other_rects);
+                other.clear();
+                self.slice(shape, options.slicing.invert(), &mut other);
+                if other.slivers_count(options.sliver_size) < slivers {
+                    core::mem::swap(&mut rects, &mut other);
+                }
+                self.other_rects = other;
+            }
+        }
+
+        for rect in rects.iter() {
+            rect.split_into(options.max_width, options.max_height, output);
+        }
+
+        self.rects = rects;
+    }
+
+    fn slice(&mut self, shape: &[IntContour], slicing: Slicing, rects: &mut Vec<IntRect>) {
+        self.edges.clear();
+        for contour in shape.iter() {
+            let mut p0 = contour[contour.len() - 1];
+            for &p1 in contour.iter() {
+                match slicing {
+                    Slicing::Horizontal => if p0.y == p1.y && p0.x != p1.x {
+                        self.edges.push(Edge { pos: p0.y, min: p0.x.min(p1.x), max: p0.x.max(p1.x) });
+                    },
+                    Slicing::Vertical => if p0.x == p1.x && p0.y != p1.y {
+                        self.edges.push(Edge { pos: p0.x, min: p0.y.min(p1.y), max: p0.y.max(p1.y) });
+                    },
+                }
+                p0 = p1;
+            }
+        }
+
+        self.edges.sort_unstable_by_key(|e| e.pos);
+        self.borders.clear();
+        self.strips.clear();
+
+        let mut i = 0;
+        while i < self.edges.len() {
+            let pos = self.edges[i].pos;
+
+            // every boundary edge flips the inside state of its range
+            while i < self.edges.len() && self.edges[i].pos == pos {
+                let e = self.edges[i];
+                self.borders.toggle(e.min);
+                self.borders.toggle(e.max);
+                i += 1;
+            }
+
+            debug_assert!(self.borders.len().is_multiple_of(2));
+
+            // keep strips which are not changed, close the rest and open the new ones
+            self.next_strips.clear();
+            let mut j = 0;
+            for pair in self.borders.chunks_exact(2) {
+                let (min, max) = (pair[0], pair[1]);
+                while j < self.strips.len() {
+                    let s = self.strips[j];
+                    if s.min > min || s.min == min && s.max == max {
+                        break;
+                    }
+                    rects.push(s.rect(pos, slicing));
+                    j += 1;
+                }
+
+                if j < self.strips.len() && self.strips[j].min == min && self.strips[j].max == max {
+                    self.next_strips.push(self.strips[j]);
+                    j += 1;
+                } else {
+                    self.next_strips.push(Strip { min, max, start: pos });
+                }
+            }
+
+            for s in self.strips[j..].iter() {
+                rects.push(s.rect(pos, slicing));
+            }
+
+            core::mem::swap(&mut self.strips, &mut self.next_strips);
+        }
+
+        debug_assert!(self.strips.is_empty());
+    }
+}
+
+impl Strip {
+    #[inline]
+    fn rect(&self, end: i32, slicing: Slicing) -> IntRect {
+        match slicing {
+            Slicing::Horizontal => IntRect::new(self.min, self.max, self.start, end),
+            Slicing::Vertical => IntRect::new(self.start, end, self.min, self.max),
+        }
+    }
+}
+
+trait Toggle {
+    fn toggle(&mut self, value: i32);
+}
+
+impl Toggle for Vec<i32> {
+    #[inline]
+    fn toggle(&mut self, value: i32) {
+        match self.binary_search(&value) {
+            Ok(index) => _ = self.remove(index),
+            Err(index) => self.insert(index, value),
+        }
+    }
+}
+
+trait Slivers {
+    fn slivers_count(&self, sliver_size: u32) -> usize;
+}
+
+impl Slivers for [IntRect] {
+    #[inline]
+    fn slivers_count(&self, sliver_size: u32) -> usize {
+        let sliver_size = sliver_size as i64;
+        self.iter()
+            .filter(|r| (r.width() as i64).min(r.height() as i64) < sliver_size)
+            .count()
+    }
+}
+
+trait SplitRect {
+    fn split_into(&self, max_width: u32, max_height: u32, output: &mut Vec<IntRect>);
+}
+
+impl SplitRect for IntRect {
+    fn split_into(&self, max_width: u32, max_height: u32, output: &mut Vec<IntRect>) {
+        // split into equal parts, so the last part is never a sliver
+        let nx = parts_count(self.min_x, self.max_x, max_width);
+        let ny = parts_count(self.min_y, self.max_y, max_height);
+        for iy in 0..ny {
+            let y0 = part_border(self.min_y, self.max_y, iy, ny);
+            let y1 = part_border(self.min_y, self.max_y, iy + 1, ny);
+            for ix in 0..nx {
+                let x0 = part_border(self.min_x, self.max_x, ix, nx);
+                let x1 = part_border(self.min_x, self.max_x, ix + 1, nx);
+                output.push(IntRect::new(x0, x1, y0, y1));
+            }
+        }
+    }
+}
+
+#[inline]
+fn parts_count(min: i32, max: i32, max_size: u32) -> i64 {
+    let size = max as i64 - min as i64;
+    if max_size == 0 {
+        1
+    } else {
+        (size + max_size as i64 - 1) / max_size as i64
+    }
+}
+
+#[inline]
+fn part_border(min: i32, max: i32, index: i64, count: i64) -> i32 {
+    let size = max as i64 - min as i64;
+    (min as i64 + size * index / count) as i32
+}
@@ -9,12 +9,20 @@ pub(crate) struct OverlayLink {
     pub(crate) a: IdPoint,
     pub(crate) b: IdPoint,
     pub(crate) fill: SegmentFill,
+    #[cfg(feature = "provenance")]
+    pub(crate) source: u32,
 }
 
 impl OverlayLink {
     #[inline(always)]
     pub(crate) fn new(a: IdPoint, b: IdPoint, fill: SegmentFill) -> OverlayLink {
-        OverlayLink { a, b, fill }
+        OverlayLink {
+            a,
+            b,
+            fill,
+            #[cfg(feature = "provenance")]
+            source: 0,
+        }
     }
 
     #[inline(always)]
@@ -46,6 +54,8 @@ impl OverlayLink {
                 point: IntPoint::new(x, max_y),
             },
             fill,
+            #[cfg(feature = "provenance")]
+            source: 0,
         }
     }
 
@@ -61,6 +71,8 @@ impl OverlayLink {
                 point: IntPoint::new(max_x, y),
             },
             fill,
+            #[cfg(feature = "provenance")]
+            source: 0,
         }
     }
 }
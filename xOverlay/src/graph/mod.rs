@@ -1,19 +1,26 @@
 pub(crate) mod end;
 pub(crate) mod link;
-mod node;
+pub(crate) mod node;
 pub(crate) mod boolean;
 pub(crate) mod column;
 mod build;
 mod extract;
+mod fracture;
 mod nearest_vector;
+#[cfg(feature = "provenance")]
+mod provenance;
 
 use alloc::vec::Vec;
 use i_float::int::point::IntPoint;
+use i_key_sort::sort::key_sort::Bin;
 use i_key_sort::sort::layout::BinStore;
 use crate::core::options::IntOverlayOptions;
+use crate::core::solver::Solver;
 use crate::graph::end::End;
 use crate::graph::link::OverlayLink;
 use crate::graph::node::OverlayNode;
+use crate::ortho::memory::MemoryUsage;
+use crate::sub::source::SourceGroups;
 
 #[derive(Default)]
 pub(crate) struct BooleanExtractionBuffer {
@@ -23,9 +30,11 @@ pub(crate) struct BooleanExtractionBuffer {
 
 pub struct OverlayGraph {
     pub(crate) options: IntOverlayOptions,
+    pub(crate) solver: Solver,
     pub(crate) nodes: Vec<OverlayNode>,
     pub(crate) links: Vec<OverlayLink>,
     pub(crate) ends: Vec<End>,
+    pub(crate) sources: SourceGroups,
     pub(crate) buffer: Option<BooleanExtractionBuffer>,
     pub(super) bin_store: BinStore<i32>,
 }
@@ -44,13 +53,42 @@ impl OverlayGraph {
     }
 }
 
+impl OverlayGraph {
+    /// Adds the bytes allocated by the graph buffers to `usage`.
+    pub(crate) fn add_held_bytes(&self, usage: &mut MemoryUsage) {
+        usage.links += self.links.capacity() * size_of::<OverlayLink>() + self.sources.held_bytes();
+        usage.ends += self.ends.capacity() * size_of::<End>() + self.bin_store.bins.capacity() * size_of::<Bin>();
+        usage.nodes += self.nodes.capacity() * size_of::<OverlayNode>();
+        for node in self.nodes.iter() {
+            if let OverlayNode::Cross(indices) = node {
+                usage.nodes += indices.capacity() * size_of::<usize>();
+            }
+        }
+        if let Some(buffer) = &self.buffer {
+            usage.extraction += buffer.points.capacity() * size_of::<IntPoint>() + buffer.visited.capacity();
+        }
+    }
+
+    /// Releases the spare capacity, the extraction buffer holds no data between runs and is dropped.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.nodes.shrink_to_fit();
+        self.links.shrink_to_fit();
+        self.ends.shrink_to_fit();
+        self.sources.shrink_to_fit();
+        self.bin_store.bins.shrink_to_fit();
+        self.buffer = None;
+    }
+}
+
 impl Default for OverlayGraph {
     fn default() -> Self {
         Self {
             options: Default::default(),
+            solver: Default::default(),
             nodes: Vec::new(),
             links: Vec::new(),
             ends: Vec::new(),
+            sources: Default::default(),
             buffer: None,
             bin_store: BinStore::empty(0, 0),
         }
@@ -0,0 +1,62 @@
+use crate::core::overlay_rule::OverlayRule;
+use crate::graph::OverlayGraph;
+use crate::ortho::provenance::{InputContour, SourcedContour, SourcedShape};
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+
+impl OverlayGraph {
+    /// Extracts shapes like [`Self::extract_shapes`], every contour edge reports the input contours which produce it.
+    pub(crate) fn extract_shapes_with_sources(&mut self, overlay_rule: OverlayRule) -> Vec<SourcedShape> {
+        let shapes = self.extract_shapes(overlay_rule);
+
+        let mut buffer = Vec::new();
+        shapes
+            .into_iter()
+            .map(|shape| {
+                shape
+                    .into_iter()
+                    .map(|points| {
+                        let mut edges = Vec::with_capacity(points.len());
+                        let mut a = points[points.len() - 1];
+                        for &b in points.iter() {
+                            buffer.clear();
+                            self.collect_edge_sources(a, b, &mut buffer);
+                            let mut sources: Vec<_> = buffer.iter().map(|&s| InputContour::with_source(s)).collect();
+                            sources.sort_unstable();
+                            edges.push(sources);
+                            a = b;
+                        }
+                        // edges[i] must start at points[i]
+                        edges.rotate_left(1);
+                        SourcedContour { points, edges }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn collect_edge_sources(&self, p0: IntPoint, p1: IntPoint, buffer: &mut Vec<u32>) {
+        let (mut a, b) = if p0 < p1 { (p0, p1) } else { (p1, p0) };
+        let vertical = a.x == b.x;
+
+        // the edge is covered by a chain of links, each link is unique by its geometry
+        while a != b {
+            let start = self.links.partition_point(|link| link.a.point < a);
+            let link = self.links[start..]
+                .iter()
+                .take_while(|link| link.a.point == a)
+                .find(|link| (link.b.point.x == a.x) == vertical);
+
+            if let Some(link) = link {
+                self.sources.extend_into(link.source, buffer);
+                a = link.b.point;
+            } else {
+                debug_assert!(false, "edge is not covered by links");
+                break;
+            }
+        }
+
+        buffer.sort_unstable();
+        buffer.dedup();
+    }
+}
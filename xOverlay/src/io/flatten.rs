@@ -0,0 +1,226 @@
+use crate::io::IoError;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+use i_shape::int::shape::IntContour;
+
+const MAX_DEPTH: usize = 64;
+
+/// A cell of a hierarchical layout: polygons by layer and references to other cells.
+pub(super) struct Cell<L> {
+    pub(super) polygons: Vec<(L, Vec<IntPoint>)>,
+    pub(super) references: Vec<Reference>,
+}
+
+impl<L> Default for Cell<L> {
+    fn default() -> Self {
+        Self {
+            polygons: Vec::new(),
+            references: Vec::new(),
+        }
+    }
+}
+
+/// Instances of a cell, every offset is added to the translation of `transform`.
+pub(super) struct Reference {
+    pub(super) name: String,
+    pub(super) transform: Transform,
+    pub(super) offsets: Vec<[f64; 2]>,
+}
+
+/// Orthogonal affine transform: `p' = m * p + t`.
+#[derive(Clone, Copy)]
+pub(super) struct Transform {
+    m: [f64; 4],
+    t: [f64; 2],
+}
+
+impl Transform {
+    pub(super) const IDENTITY: Self = Self {
+        m: [1.0, 0.0, 0.0, 1.0],
+        t: [0.0, 0.0],
+    };
+
+    /// Reflection about the x axis goes first, then magnification, rotation and translation.
+    /// - Returns: `None` if the angle is not a multiple of 90 degrees.
+    pub(super) fn new(origin: [f64; 2], reflect: bool, mag: f64, angle: f64) -> Option<Self> {
+        // `rem_euclid` needs std
+        let angle = angle % 360.0;
+        let angle = if angle < 0.0 { angle + 360.0 } else { angle };
+        let (cos, sin) = match angle {
+            0.0 => (1.0, 0.0),
+            90.0 => (0.0, 1.0),
+            180.0 => (-1.0, 0.0),
+            270.0 => (0.0, -1.0),
+            _ => return None,
+        };
+        let r = if reflect { -1.0 } else { 1.0 };
+        Some(Self {
+            m: [mag * cos, -mag * sin * r, mag * sin, mag * cos * r],
+            t: origin,
+        })
+    }
+
+    #[inline]
+    fn apply(&self, x: f64, y: f64) -> [f64; 2] {
+        [
+            self.m[0] * x + self.m[1] * y + self.t[0],
+            self.m[2] * x + self.m[3] * y + self.t[1],
+        ]
+    }
+
+    #[inline]
+    fn then(&self, parent: &Transform) -> Transform {
+        let p = &parent.m;
+        let m = &self.m;
+        Transform {
+            m: [
+                p[0] * m[0] + p[1] * m[2],
+                p[0] * m[1] + p[1] * m[3],
+                p[2] * m[0] + p[3] * m[2],
+                p[2] * m[1] + p[3] * m[3],
+            ],
+            t: parent.apply(self.t[0], self.t[1]),
+        }
+    }
+
+    #[inline]
+    fn translated(&self, offset: [f64; 2]) -> Transform {
+        Transform {
+            m: self.m,
+            t: [self.t[0] + offset[0], self.t[1] + offset[1]],
+        }
+    }
+}
+
+/// Collects the polygons of the top cells and of every cell they reference.
+/// - `order`: Cell names in the file order, top cells are flattened in this order.
+/// - `top`: The cell to flatten, or `None` to flatten every cell which is not referenced by another one.
+/// - Returns: Contours by layer, or an error if a cell is missing, references form a cycle
+///   or a polygon is not rectilinear.
+pub(super) fn flatten<L: Ord + Copy>(
+    cells: &BTreeMap<String, Cell<L>>,
+    order: &[String],
+    top: Option<&str>,
+) -> Result<BTreeMap<L, Vec<IntContour>>, IoError> {
+    let tops: Vec<&str> = if let Some(top) = top {
+        vec![top]
+    } else {
+        let mut is_child: BTreeMap<&str, bool> = BTreeMap::new();
+        for cell in cells.values() {
+            for r in cell.references.iter() {
+                is_child.insert(r.name.as_str(), true);
+            }
+        }
+        order
+            .iter()
+            .map(|name| name.as_str())
+            .filter(|name| !is_child.contains_key(name))
+            .collect()
+    };
+
+    let mut layers = BTreeMap::new();
+    let mut stack = Vec::new();
+    for name in tops {
+        flatten_cell(cells, name, &Transform::IDENTITY, &mut stack, &mut layers)?;
+    }
+
+    Ok(layers)
+}
+
+fn flatten_cell<'a, L: Ord + Copy>(
+    cells: &'a BTreeMap<String, Cell<L>>,
+    name: &'a str,
+    transform: &Transform,
+    stack: &mut Vec<&'a str>,
+    layers: &mut BTreeMap<L, Vec<IntContour>>,
+) -> Result<(), IoError> {
+    let cell = if let Some(cell) = cells.get(name) {
+        cell
+    } else {
+        return Err(IoError::UnsupportedGeometry(format!("cell {} is not found", name)));
+    };
+
+    if stack.len() >= MAX_DEPTH || stack.contains(&name) {
+        return Err(IoError::UnsupportedGeometry(format!("cell {} references itself", name)));
+    }
+    stack.push(name);
+
+    for (index, (layer, points)) in cell.polygons.iter().enumerate() {
+        let contour = to_contour(points, transform, index)?;
+        layers.entry(*layer).or_default().push(contour);
+    }
+
+    for r in cell.references.iter() {
+        for &offset in r.offsets.iter() {
+            let instance = r.transform.translated(offset).then(transform);
+            flatten_cell(cells, &r.name, &instance, stack, layers)?;
+        }
+    }
+
+    stack.pop();
+    Ok(())
+}
+
+/// `polygon` is the index of the polygon in its cell.
+fn to_contour(points: &[IntPoint], transform: &Transform, polygon: usize) -> Result<IntContour, IoError> {
+    let mut contour: IntContour = Vec::with_capacity(points.len());
+    for (index, p) in points.iter().enumerate() {
+        let [x, y] = transform.apply(p.x as f64, p.y as f64);
+        let p = match (round(x), round(y)) {
+            (Some(x), Some(y)) => IntPoint::new(x, y),
+            _ => {
+                return Err(IoError::OutOfRange {
+                    polygon,
+                    ring: 0,
+                    point: index,
+                });
+            }
+        };
+        if let Some(&last) = contour.last() {
+            if last == p {
+                continue;
+            }
+            if last.x != p.x && last.y != p.y {
+                return Err(IoError::NotRectilinear {
+                    polygon,
+                    ring: 0,
+                    point: index,
+                });
+            }
+        }
+        contour.push(p);
+    }
+
+    if contour.len() > 1 && contour[0] == contour[contour.len() - 1] {
+        contour.pop();
+    }
+
+    if contour.len() < 4 {
+        return Err(IoError::NotValidRing { polygon, ring: 0 });
+    }
+
+    let (first, last) = (contour[0], contour[contour.len() - 1]);
+    if first.x != last.x && first.y != last.y {
+        return Err(IoError::NotRectilinear {
+            polygon,
+            ring: 0,
+            point: 0,
+        });
+    }
+
+    Ok(contour)
+}
+
+#[inline]
+fn round(value: f64) -> Option<i32> {
+    let rounded = if value < 0.0 { value - 0.5 } else { value + 0.5 };
+    if i32::MIN as f64 <= rounded && rounded < i32::MAX as f64 + 1.0 {
+        Some(rounded as i32)
+    } else {
+        None
+    }
+}
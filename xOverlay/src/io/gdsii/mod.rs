@@ -0,0 +1,315 @@
+//! GDSII stream format, `BOUNDARY` and `BOX` elements grouped by layer and datatype.
+//!
+//! The hierarchy of `SREF` and `AREF` references is flattened on import, references may be
+//! reflected, magnified and rotated by a multiple of 90 degrees. Coordinates are kept in database units.
+
+mod reader;
+mod record;
+mod writer;
+
+use crate::io::IoError;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use i_shape::int::shape::{IntContour, IntShapes};
+
+/// A layer and a datatype of an element, `BOX` elements use their boxtype as a datatype.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GdsLayer {
+    pub layer: u16,
+    pub datatype: u16,
+}
+
+impl GdsLayer {
+    #[inline]
+    pub fn new(layer: u16, datatype: u16) -> Self {
+        Self { layer, datatype }
+    }
+}
+
+/// Size of a database unit.
+/// - `user`: A database unit in user units.
+/// - `meters`: A database unit in meters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GdsUnits {
+    pub user: f64,
+    pub meters: f64,
+}
+
+impl Default for GdsUnits {
+    fn default() -> Self {
+        Self {
+            user: 1e-3,
+            meters: 1e-9,
+        }
+    }
+}
+
+/// A flattened library: contours of every top cell grouped by layer.
+/// Contours keep the direction they have in the file, pass them to
+/// [`OrthoOverlay::init_with_ortho_contours`](crate::ortho::overlay::OrthoOverlay) as they are.
+#[derive(Debug, Clone, Default)]
+pub struct GdsLibrary {
+    pub name: String,
+    pub units: GdsUnits,
+    pub layers: BTreeMap<GdsLayer, Vec<IntContour>>,
+}
+
+/// Configuration options for writing a library.
+#[derive(Debug, Clone)]
+pub struct GdsOptions {
+    /// Name of the library.
+    pub library: String,
+
+    /// Name of the single cell which holds all the boundaries.
+    pub cell: String,
+
+    pub units: GdsUnits,
+
+    /// Maximum number of points in a `BOUNDARY`, including the closing point.
+    /// Bigger shapes are cut into pieces. The stream format allows 8191 points.
+    pub max_points: usize,
+}
+
+impl Default for GdsOptions {
+    fn default() -> Self {
+        Self {
+            library: "LIB".into(),
+            cell: "TOP".into(),
+            units: GdsUnits::default(),
+            max_points: 8191,
+        }
+    }
+}
+
+/// Reads a library and flattens it into contours.
+/// - `bytes`: GDSII stream.
+/// - `top`: The cell to flatten, or `None` to flatten every cell which is not referenced by another one.
+/// - Returns: The contours by layer, or an error if the stream is broken, a reference is rotated
+///   by an angle which is not a multiple of 90 degrees, references form a cycle
+///   or a boundary is not rectilinear. Ring errors refer to the element index in its cell.
+pub fn read(bytes: &[u8], top: Option<&str>) -> Result<GdsLibrary, IoError> {
+    reader::read(bytes, top)
+}
+
+/// Writes shapes as boundaries of a single cell. Holes are joined to their outer contour by
+/// zero-width bridges, shapes with too many points are cut into pieces.
+/// - `layers`: Shapes by layer, e.g. results of `overlay`.
+/// - `options`: Names, units and the point limit.
+/// - Returns: GDSII stream, or an error if a shape has to be cut and its coordinates do not fit
+///   in the half of the `i32` range.
+pub fn write(layers: &BTreeMap<GdsLayer, IntShapes>, options: &GdsOptions) -> Result<Vec<u8>, IoError> {
+    writer::write(layers, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::io::IoError;
+    use crate::io::gdsii::record::*;
+    use crate::io::gdsii::{GdsLayer, GdsOptions, read, write};
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::test_util::rect;
+    use alloc::collections::BTreeMap;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_float::int::point::IntPoint;
+    use i_shape::int::area::Area;
+    use i_shape::int::shape::{IntContour, IntShapes};
+
+    fn union(contours: &[IntContour]) -> IntShapes {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.init_with_ortho_contours(contours, &[]).expect("OK");
+        overlay.overlay(OverlayRule::Subject, FillRule::NonZero)
+    }
+
+    fn bounds(contour: &IntContour) -> [i32; 4] {
+        let xs = contour.iter().map(|p| p.x);
+        let ys = contour.iter().map(|p| p.y);
+        [
+            xs.clone().min().unwrap(),
+            ys.clone().min().unwrap(),
+            xs.max().unwrap(),
+            ys.max().unwrap(),
+        ]
+    }
+
+    fn area(shapes: &IntShapes) -> i64 {
+        shapes.iter().map(|shape| shape.area()).sum()
+    }
+
+    fn begin(writer: &mut RecordWriter) {
+        writer.i16s(HEADER, &[600]);
+        writer.i16s(BGNLIB, &[0; 12]);
+        writer.string(LIBNAME, "FIXTURE");
+        writer.reals(UNITS, &[1e-3, 1e-9]);
+    }
+
+    fn cell(writer: &mut RecordWriter, name: &str) {
+        writer.i16s(BGNSTR, &[0; 12]);
+        writer.string(STRNAME, name);
+    }
+
+    fn boundary(writer: &mut RecordWriter, kind: u8, layer: i16, contour: &IntContour) {
+        writer.empty(kind);
+        writer.i16s(LAYER, &[layer]);
+        writer.i16s(if kind == BOX { BOXTYPE } else { DATATYPE }, &[0]);
+        let xy: Vec<i32> = contour.iter().chain(contour.first()).flat_map(|p| [p.x, p.y]).collect();
+        writer.i32s(XY, xy.into_iter());
+        writer.empty(ENDEL);
+    }
+
+    fn reference(writer: &mut RecordWriter, name: &str, strans: i16, angle: f64, colrow: Option<[i16; 2]>, xy: &[i32]) {
+        writer.empty(if colrow.is_some() { AREF } else { SREF });
+        writer.string(SNAME, name);
+        writer.i16s(STRANS, &[strans]);
+        writer.reals(ANGLE, &[angle]);
+        if let Some(colrow) = colrow {
+            writer.i16s(COLROW, &colrow);
+        }
+        writer.i32s(XY, xy.iter().copied());
+        writer.empty(ENDEL);
+    }
+
+    #[test]
+    fn test_0() {
+        // a shape with a hole goes through a keyhole and back
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay
+            .init_with_ortho_contours(&[rect(0, 0, 100, 100), rect(200, 0, 300, 50)], &[rect(20, 20, 40, 40)])
+            .expect("OK");
+        let shapes = overlay.overlay(OverlayRule::Difference, FillRule::NonZero);
+
+        let mut layers = BTreeMap::new();
+        layers.insert(GdsLayer::new(1, 0), shapes.clone());
+        layers.insert(GdsLayer::new(2, 5), vec![vec![rect(0, 0, 10, 10)]]);
+
+        let bytes = write(&layers, &GdsOptions::default()).expect("OK");
+        let library = read(&bytes, None).expect("OK");
+
+        assert_eq!(library.name, "LIB");
+        assert!((library.units.user - 1e-3).abs() < 1e-15);
+        assert!((library.units.meters - 1e-9).abs() < 1e-21);
+        assert_eq!(library.layers.len(), 2);
+
+        let contours = &library.layers[&GdsLayer::new(1, 0)];
+        assert_eq!(contours.len(), 2);
+        let restored = union(contours);
+        assert_eq!(area(&restored), area(&shapes));
+        assert_eq!(restored.iter().map(|s| s.len()).sum::<usize>(), 3);
+
+        assert_eq!(library.layers[&GdsLayer::new(2, 5)], vec![rect(0, 0, 10, 10)]);
+    }
+
+    #[test]
+    fn test_1() {
+        let mut writer = RecordWriter { bytes: Vec::new() };
+        begin(&mut writer);
+
+        cell(&mut writer, "A");
+        boundary(&mut writer, BOUNDARY, 1, &rect(0, 0, 10, 20));
+        writer.empty(ENDSTR);
+
+        cell(&mut writer, "B");
+        boundary(&mut writer, BOX, 2, &rect(0, 0, 5, 5));
+        // rotated by 90 degrees
+        reference(&mut writer, "A", 0, 90.0, None, &[100, 0]);
+        writer.empty(ENDSTR);
+
+        cell(&mut writer, "TOP");
+        // reflected about the x axis
+        reference(&mut writer, "A", i16::MIN, 0.0, None, &[0, -100]);
+        // 2 columns with a step of 30 and 3 rows with a step of 40
+        reference(&mut writer, "B", 0, 0.0, Some([2, 3]), &[1000, 0, 1060, 0, 1000, 120]);
+        writer.empty(ENDSTR);
+        writer.empty(ENDLIB);
+
+        let library = read(&writer.bytes, None).expect("OK");
+        let layer_1 = &library.layers[&GdsLayer::new(1, 0)];
+        let layer_2 = &library.layers[&GdsLayer::new(2, 0)];
+        assert_eq!(layer_1.len(), 7);
+        assert_eq!(layer_2.len(), 6);
+
+        assert_eq!(bounds(&layer_1[0]), [0, -120, 10, -100]);
+        assert_eq!(bounds(&layer_1[1]), [1080, 0, 1100, 10]);
+        assert_eq!(bounds(&layer_1[6]), [1110, 80, 1130, 90]);
+        assert_eq!(bounds(&layer_2[5]), [1030, 80, 1035, 85]);
+
+        // a single cell
+        let library = read(&writer.bytes, Some("B")).expect("OK");
+        assert_eq!(library.layers[&GdsLayer::new(1, 0)].len(), 1);
+        assert_eq!(library.layers[&GdsLayer::new(2, 0)], vec![rect(0, 0, 5, 5)]);
+
+        assert!(read(&writer.bytes, Some("C")).is_err());
+    }
+
+    #[test]
+    fn test_2() {
+        // a comb with 4 * 50 teeth corners
+        let mut comb = vec![IntPoint::new(0, 0), IntPoint::new(1000, 0), IntPoint::new(1000, 10)];
+        for i in (0..50).rev() {
+            let x = 20 * i;
+            comb.push(IntPoint::new(x + 10, 10));
+            comb.push(IntPoint::new(x + 10, 20 + i));
+            comb.push(IntPoint::new(x, 20 + i));
+            comb.push(IntPoint::new(x, 10));
+        }
+        comb.pop();
+
+        let shapes = union(&[comb]);
+        assert_eq!(shapes.len(), 1);
+
+        let mut layers = BTreeMap::new();
+        layers.insert(GdsLayer::new(0, 0), shapes.clone());
+
+        let options = GdsOptions {
+            max_points: 21,
+            ..Default::default()
+        };
+        let bytes = write(&layers, &options).expect("OK");
+        let contours = read(&bytes, None)
+            .expect("OK")
+            .layers
+            .remove(&GdsLayer::new(0, 0))
+            .unwrap();
+
+        assert!(contours.len() > 10);
+        assert!(contours.iter().all(|c| c.len() < 21));
+        assert_eq!(union(&contours), shapes);
+    }
+
+    #[test]
+    fn test_3() {
+        let fixture = |angle: f64, target: &str| {
+            let mut writer = RecordWriter { bytes: Vec::new() };
+            begin(&mut writer);
+            cell(&mut writer, "A");
+            boundary(&mut writer, BOUNDARY, 1, &rect(0, 0, 10, 10));
+            reference(&mut writer, target, 0, angle, None, &[0, 0]);
+            writer.empty(ENDSTR);
+            cell(&mut writer, "TOP");
+            reference(&mut writer, "A", 0, 0.0, None, &[0, 0]);
+            writer.empty(ENDSTR);
+            writer.empty(ENDLIB);
+            writer.bytes
+        };
+
+        // a reference to itself
+        assert!(matches!(
+            read(&fixture(0.0, "A"), None),
+            Err(IoError::UnsupportedGeometry(_))
+        ));
+        // a rotation by 45 degrees
+        assert!(matches!(
+            read(&fixture(45.0, "TOP"), None),
+            Err(IoError::UnsupportedGeometry(_))
+        ));
+
+        // a broken record
+        let mut bytes = fixture(0.0, "TOP");
+        bytes.truncate(bytes.len() - 2);
+        assert!(matches!(read(&bytes, None), Err(IoError::Syntax { .. })));
+    }
+}
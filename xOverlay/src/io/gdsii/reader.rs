@@ -0,0 +1,166 @@
+use crate::io::IoError;
+use crate::io::flatten::{Cell, Reference, Transform, flatten};
+use crate::io::gdsii::record::*;
+use crate::io::gdsii::{GdsLayer, GdsLibrary, GdsUnits};
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+
+#[derive(Default)]
+struct Element {
+    kind: u8,
+    layer: u16,
+    datatype: u16,
+    xy: Vec<i32>,
+    name: String,
+    reflect: bool,
+    mag: f64,
+    angle: f64,
+    colrow: [i32; 2],
+    position: usize,
+}
+
+pub(super) fn read(bytes: &[u8], top: Option<&str>) -> Result<GdsLibrary, IoError> {
+    let mut reader = RecordReader::new(bytes);
+    let mut library = GdsLibrary::default();
+    let mut cells: BTreeMap<String, Cell<GdsLayer>> = BTreeMap::new();
+    let mut order = Vec::new();
+
+    let mut current: Option<(String, Cell<GdsLayer>)> = None;
+    let mut element: Option<Element> = None;
+
+    while let Some(record) = reader.next_record()? {
+        match record.kind {
+            LIBNAME => library.name = record.string(),
+            UNITS => {
+                let mut reals = record.reals();
+                library.units = GdsUnits {
+                    user: reals.next().unwrap_or(1.0),
+                    meters: reals.next().unwrap_or(1e-9),
+                };
+            }
+            BGNSTR => current = Some((String::new(), Cell::default())),
+            STRNAME => {
+                if let Some((name, _)) = &mut current {
+                    *name = record.string();
+                }
+            }
+            ENDSTR => {
+                if let Some((name, cell)) = current.take() {
+                    order.push(name.clone());
+                    cells.insert(name, cell);
+                }
+            }
+            BOUNDARY | BOX | SREF | AREF => {
+                element = Some(Element {
+                    kind: record.kind,
+                    mag: 1.0,
+                    position: record.position,
+                    ..Default::default()
+                })
+            }
+            LAYER => with(&mut element, |e| e.layer = record.i16().unwrap_or(0) as u16),
+            DATATYPE | BOXTYPE => with(&mut element, |e| e.datatype = record.i16().unwrap_or(0) as u16),
+            XY => with(&mut element, |e| e.xy = record.i32s().collect()),
+            SNAME => with(&mut element, |e| e.name = record.string()),
+            STRANS => with(&mut element, |e| {
+                e.reflect = record.i16().unwrap_or(0) as u16 & 0x8000 != 0
+            }),
+            MAG => with(&mut element, |e| e.mag = record.real().unwrap_or(1.0)),
+            ANGLE => with(&mut element, |e| e.angle = record.real().unwrap_or(0.0)),
+            COLROW => with(&mut element, |e| {
+                let mut values = record
+                    .data
+                    .chunks_exact(2)
+                    .map(|c| i16::from_be_bytes([c[0], c[1]]) as i32);
+                e.colrow = [values.next().unwrap_or(1), values.next().unwrap_or(1)];
+            }),
+            ENDEL => {
+                if let (Some(e), Some((_, cell))) = (element.take(), &mut current) {
+                    add_element(e, cell)?;
+                }
+            }
+            ENDLIB => break,
+            _ => {}
+        }
+    }
+
+    library.layers = flatten(&cells, &order, top)?;
+
+    Ok(library)
+}
+
+#[inline]
+fn with<F: FnOnce(&mut Element)>(element: &mut Option<Element>, f: F) {
+    if let Some(e) = element {
+        f(e);
+    }
+}
+
+fn add_element(e: Element, cell: &mut Cell<GdsLayer>) -> Result<(), IoError> {
+    let layer = GdsLayer {
+        layer: e.layer,
+        datatype: e.datatype,
+    };
+    match e.kind {
+        BOUNDARY | BOX => {
+            let points = e.xy.chunks_exact(2).map(|c| IntPoint::new(c[0], c[1])).collect();
+            cell.polygons.push((layer, points));
+        }
+        _ => {
+            if e.xy.len() < 2 {
+                return Err(IoError::syntax(e.position, "reference has no position"));
+            }
+            let origin = [e.xy[0] as f64, e.xy[1] as f64];
+            let transform = if let Some(transform) = Transform::new(origin, e.reflect, e.mag, e.angle) {
+                transform
+            } else {
+                return Err(IoError::UnsupportedGeometry(format!(
+                    "reference to {} is rotated by {} degrees",
+                    e.name, e.angle
+                )));
+            };
+
+            let offsets = if e.kind == AREF {
+                if e.xy.len() < 6 || e.colrow[0] <= 0 || e.colrow[1] <= 0 {
+                    return Err(IoError::syntax(
+                        e.position,
+                        "array reference needs 3 points and positive COLROW",
+                    ));
+                }
+                let [cols, rows] = e.colrow;
+                let col_step = [
+                    (e.xy[2] as f64 - origin[0]) / cols as f64,
+                    (e.xy[3] as f64 - origin[1]) / cols as f64,
+                ];
+                let row_step = [
+                    (e.xy[4] as f64 - origin[0]) / rows as f64,
+                    (e.xy[5] as f64 - origin[1]) / rows as f64,
+                ];
+                let mut offsets = Vec::with_capacity((cols * rows) as usize);
+                for row in 0..rows {
+                    for col in 0..cols {
+                        let (col, row) = (col as f64, row as f64);
+                        offsets.push([
+                            col * col_step[0] + row * row_step[0],
+                            col * col_step[1] + row * row_step[1],
+                        ]);
+                    }
+                }
+                offsets
+            } else {
+                vec![[0.0, 0.0]]
+            };
+
+            cell.references.push(Reference {
+                name: e.name,
+                transform,
+                offsets,
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use crate::io::IoError;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub(super) const HEADER: u8 = 0x00;
+pub(super) const BGNLIB: u8 = 0x01;
+pub(super) const LIBNAME: u8 = 0x02;
+pub(super) const UNITS: u8 = 0x03;
+pub(super) const ENDLIB: u8 = 0x04;
+pub(super) const BGNSTR: u8 = 0x05;
+pub(super) const STRNAME: u8 = 0x06;
+pub(super) const ENDSTR: u8 = 0x07;
+pub(super) const BOUNDARY: u8 = 0x08;
+pub(super) const SREF: u8 = 0x0A;
+pub(super) const AREF: u8 = 0x0B;
+pub(super) const LAYER: u8 = 0x0D;
+pub(super) const DATATYPE: u8 = 0x0E;
+pub(super) const XY: u8 = 0x10;
+pub(super) const ENDEL: u8 = 0x11;
+pub(super) const SNAME: u8 = 0x12;
+pub(super) const COLROW: u8 = 0x13;
+pub(super) const STRANS: u8 = 0x1A;
+pub(super) const MAG: u8 = 0x1B;
+pub(super) const ANGLE: u8 = 0x1C;
+pub(super) const BOX: u8 = 0x2D;
+pub(super) const BOXTYPE: u8 = 0x2E;
+
+pub(super) const NO_DATA: u8 = 0;
+pub(super) const INT16: u8 = 2;
+pub(super) const INT32: u8 = 3;
+pub(super) const REAL8: u8 = 5;
+pub(super) const ASCII: u8 = 6;
+
+pub(super) struct Record<'a> {
+    pub(super) kind: u8,
+    pub(super) data: &'a [u8],
+    pub(super) position: usize,
+}
+
+pub(super) struct RecordReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> RecordReader<'a> {
+    #[inline]
+    pub(super) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    pub(super) fn next_record(&mut self) -> Result<Option<Record<'a>>, IoError> {
+        // some writers pad the stream with zeros up to a block size
+        if self.bytes[self.pos..].iter().all(|&b| b == 0) {
+            return Ok(None);
+        }
+        let position = self.pos;
+        if self.bytes.len() - position < 4 {
+            return Err(IoError::syntax(position, "broken record header"));
+        }
+        let len = u16::from_be_bytes([self.bytes[position], self.bytes[position + 1]]) as usize;
+        if len < 4 || position + len > self.bytes.len() {
+            return Err(IoError::syntax(position, "broken record length"));
+        }
+        self.pos += len;
+        Ok(Some(Record {
+            kind: self.bytes[position + 2],
+            data: &self.bytes[position + 4..position + len],
+            position,
+        }))
+    }
+}
+
+impl Record<'_> {
+    pub(super) fn i16(&self) -> Result<i16, IoError> {
+        if self.data.len() < 2 {
+            return Err(IoError::syntax(self.position, "int16 is expected"));
+        }
+        Ok(i16::from_be_bytes([self.data[0], self.data[1]]))
+    }
+
+    pub(super) fn i32s(&self) -> impl Iterator<Item = i32> + '_ {
+        self.data
+            .chunks_exact(4)
+            .map(|c| i32::from_be_bytes([c[0], c[1], c[2], c[3]]))
+    }
+
+    pub(super) fn reals(&self) -> impl Iterator<Item = f64> + '_ {
+        self.data.chunks_exact(8).map(|c| {
+            let mut buf = [0; 8];
+            buf.copy_from_slice(c);
+            decode_real(u64::from_be_bytes(buf))
+        })
+    }
+
+    pub(super) fn real(&self) -> Result<f64, IoError> {
+        self.reals()
+            .next()
+            .ok_or_else(|| IoError::syntax(self.position, "real8 is expected"))
+    }
+
+    pub(super) fn string(&self) -> String {
+        let end = self.data.iter().position(|&b| b == 0).unwrap_or(self.data.len());
+        String::from_utf8_lossy(&self.data[..end]).into()
+    }
+}
+
+pub(super) struct RecordWriter {
+    pub(super) bytes: Vec<u8>,
+}
+
+impl RecordWriter {
+    #[inline]
+    fn head(&mut self, kind: u8, data_type: u8, len: usize) {
+        self.bytes.extend_from_slice(&((4 + len) as u16).to_be_bytes());
+        self.bytes.push(kind);
+        self.bytes.push(data_type);
+    }
+
+    pub(super) fn empty(&mut self, kind: u8) {
+        self.head(kind, NO_DATA, 0);
+    }
+
+    pub(super) fn i16s(&mut self, kind: u8, values: &[i16]) {
+        self.head(kind, INT16, 2 * values.len());
+        for v in values.iter() {
+            self.bytes.extend_from_slice(&v.to_be_bytes());
+        }
+    }
+
+    pub(super) fn i32s<I: ExactSizeIterator<Item = i32>>(&mut self, kind: u8, values: I) {
+        self.head(kind, INT32, 4 * values.len());
+        for v in values {
+            self.bytes.extend_from_slice(&v.to_be_bytes());
+        }
+    }
+
+    pub(super) fn reals(&mut self, kind: u8, values: &[f64]) {
+        self.head(kind, REAL8, 8 * values.len());
+        for &v in values.iter() {
+            self.bytes.extend_from_slice(&encode_real(v).to_be_bytes());
+        }
+    }
+
+    pub(super) fn string(&mut self, kind: u8, value: &str) {
+        let len = value.len() + value.len() % 2;
+        self.head(kind, ASCII, len);
+        self.bytes.extend_from_slice(value.as_bytes());
+        if value.len() % 2 == 1 {
+            self.bytes.push(0);
+        }
+    }
+}
+
+const MANTISSA: f64 = (1u64 << 56) as f64;
+
+/// GDSII real: a sign bit, a 7-bit exponent of 16 in excess 64 and a 56-bit mantissa.
+pub(super) fn decode_real(bits: u64) -> f64 {
+    let mantissa = (bits & 0x00FF_FFFF_FFFF_FFFF) as f64 / MANTISSA;
+    let exp = ((bits >> 56) & 0x7F) as i32 - 64;
+    let mut value = mantissa;
+    if exp > 0 {
+        (0..exp).for_each(|_| value *= 16.0);
+    } else {
+        (exp..0).for_each(|_| value /= 16.0);
+    }
+    if bits >> 63 == 1 { -value } else { value }
+}
+
+pub(super) fn encode_real(value: f64) -> u64 {
+    if value == 0.0 || !value.is_finite() {
+        return 0;
+    }
+    let sign = if value < 0.0 { 1u64 << 63 } else { 0 };
+    let mut mantissa = value.abs();
+    let mut exp = 64i32;
+    while mantissa >= 1.0 && exp < 127 {
+        mantissa /= 16.0;
+        exp += 1;
+    }
+    while mantissa < 1.0 / 16.0 && exp > 0 {
+        mantissa *= 16.0;
+        exp -= 1;
+    }
+    let bits = ((mantissa * MANTISSA + 0.5) as u64).min((1 << 56) - 1);
+    sign | ((exp as u64) << 56) | bits
+}
@@ -0,0 +1,111 @@
+use crate::bind::keyhole::Keyhole;
+use crate::core::fill_rule::FillRule;
+use crate::graph::boolean::winding_count::ShapeCountBoolean;
+use crate::io::IoError;
+use crate::io::gdsii::record::*;
+use crate::io::gdsii::{GdsLayer, GdsOptions};
+use crate::ortho::overlay::OrthoOverlay;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+use i_shape::int::area::Area;
+use i_shape::int::shape::{IntContour, IntShape, IntShapes};
+
+// modification time is not kept, every date field is zero
+const DATES: [i16; 12] = [0; 12];
+
+pub(super) fn write(layers: &BTreeMap<GdsLayer, IntShapes>, options: &GdsOptions) -> Result<Vec<u8>, IoError> {
+    let mut writer = RecordWriter { bytes: Vec::new() };
+    writer.i16s(HEADER, &[600]);
+    writer.i16s(BGNLIB, &DATES);
+    writer.string(LIBNAME, &options.library);
+    writer.reals(UNITS, &[options.units.user, options.units.meters]);
+    writer.i16s(BGNSTR, &DATES);
+    writer.string(STRNAME, &options.cell);
+
+    // a boundary keeps the closing point
+    let max_points = options.max_points.max(5) - 1;
+    let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+
+    for (layer, shapes) in layers.iter() {
+        for (index, shape) in shapes.iter().enumerate() {
+            let mut contours = Vec::new();
+            split_shape(shape, max_points, &mut overlay, &mut contours).map_err(|_| IoError::OutOfRange {
+                polygon: index,
+                ring: 0,
+                point: 0,
+            })?;
+            for contour in contours.iter() {
+                writer.empty(BOUNDARY);
+                writer.i16s(LAYER, &[layer.layer as i16]);
+                writer.i16s(DATATYPE, &[layer.datatype as i16]);
+                let closed = contour.iter().chain(contour.first());
+                writer.i32s(XY, closed.flat_map(|p| [p.x, p.y]).collect::<Vec<_>>().into_iter());
+                writer.empty(ENDEL);
+            }
+        }
+    }
+
+    writer.empty(ENDSTR);
+    writer.empty(ENDLIB);
+
+    Ok(writer.bytes)
+}
+
+/// Keyholes a shape and cuts it in halves until every piece fits in `max_points`.
+fn split_shape(
+    shape: &IntShape,
+    max_points: usize,
+    overlay: &mut OrthoOverlay<ShapeCountBoolean>,
+    contours: &mut Vec<IntContour>,
+) -> Result<(), crate::ortho::error::OrthoError> {
+    if shape.is_empty() {
+        return Ok(());
+    }
+
+    // outer contour is clockwise if its area is positive
+    let contour = shape.as_slice().keyhole(shape[0].area() > 0);
+    if contour.len() <= max_points {
+        contours.push(contour);
+        return Ok(());
+    }
+
+    let line = if let Some(line) = median_line(shape) {
+        line
+    } else {
+        // a shape with two distinct values on both axes is a rectangle
+        contours.push(contour);
+        return Ok(());
+    };
+
+    let pieces = overlay.slice_by_lines(shape, &[line], FillRule::NonZero)?;
+    for piece in pieces.iter() {
+        split_shape(piece, max_points, overlay, contours)?;
+    }
+
+    Ok(())
+}
+
+/// A cut line through the middle of the axis with more distinct coordinates.
+fn median_line(shape: &IntShape) -> Option<Vec<IntPoint>> {
+    let mut xs: Vec<i32> = shape.iter().flatten().map(|p| p.x).collect();
+    let mut ys: Vec<i32> = shape.iter().flatten().map(|p| p.y).collect();
+    xs.sort_unstable();
+    xs.dedup();
+    ys.sort_unstable();
+    ys.dedup();
+
+    let (x_min, x_max) = (xs[0], xs[xs.len() - 1]);
+    let (y_min, y_max) = (ys[0], ys[ys.len() - 1]);
+
+    if xs.len() >= ys.len() && xs.len() > 2 {
+        let x = xs[xs.len() / 2];
+        Some(vec![IntPoint::new(x, y_min), IntPoint::new(x, y_max)])
+    } else if ys.len() > 2 {
+        let y = ys[ys.len() / 2];
+        Some(vec![IntPoint::new(x_min, y), IntPoint::new(x_max, y)])
+    } else {
+        None
+    }
+}
@@ -0,0 +1,165 @@
+//! GeoJSON, `Polygon` and `MultiPolygon` geometries, features and feature collections.
+
+use crate::io::ring::{RawPolygon, closed_rings, to_shapes, write_coord};
+use crate::io::{IoError, IoOptions};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use i_shape::int::shape::{IntShape, IntShapes};
+use serde_json::Value;
+
+/// Reads a `Polygon` or a `MultiPolygon` geometry, a `Feature` or a `FeatureCollection`.
+/// Features with other geometries are an error.
+/// - `text`: GeoJSON text.
+/// - `options`: Scale and direction of the result.
+/// - Returns: Shapes, or an error if the text is malformed or a ring is not rectilinear.
+pub fn read(text: &str, options: &IoOptions) -> Result<IntShapes, IoError> {
+    let value: Value = serde_json::from_str(text).map_err(|e| IoError::Syntax {
+        position: e.column(),
+        message: e.to_string(),
+    })?;
+
+    let mut polygons = Vec::new();
+    collect(&value, &mut polygons)?;
+
+    to_shapes(polygons, options)
+}
+
+/// Writes shapes as a `MultiPolygon` geometry, rings are closed.
+/// - `shapes`: Shapes, e.g. a result of `extract_shapes`.
+/// - `options`: Scale and direction of the rings.
+pub fn write(shapes: &[IntShape], options: &IoOptions) -> String {
+    let mut out = String::from(r#"{"type":"MultiPolygon","coordinates":["#);
+    for (i, shape) in shapes.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push('[');
+        for (j, ring) in closed_rings(shape, options.direction).enumerate() {
+            if j > 0 {
+                out.push(',');
+            }
+            out.push('[');
+            for (k, p) in ring.iter().enumerate() {
+                if k > 0 {
+                    out.push(',');
+                }
+                out.push('[');
+                write_coord(&mut out, p.x, options.scale);
+                out.push(',');
+                write_coord(&mut out, p.y, options.scale);
+                out.push(']');
+            }
+            out.push(']');
+        }
+        out.push(']');
+    }
+    out.push_str("]}");
+    out
+}
+
+fn collect(value: &Value, polygons: &mut Vec<RawPolygon>) -> Result<(), IoError> {
+    let kind = value.get("type").and_then(Value::as_str).unwrap_or_default();
+    match kind {
+        "Polygon" => polygons.push(polygon(coordinates(value)?)?),
+        "MultiPolygon" => {
+            for item in array(coordinates(value)?)? {
+                polygons.push(polygon(item)?);
+            }
+        }
+        "Feature" => {
+            let geometry = value
+                .get("geometry")
+                .ok_or_else(|| IoError::syntax(0, "a feature without a geometry"))?;
+            collect(geometry, polygons)?;
+        }
+        "FeatureCollection" => {
+            let features = value
+                .get("features")
+                .ok_or_else(|| IoError::syntax(0, "a collection without features"))?;
+            for feature in array(features)? {
+                collect(feature, polygons)?;
+            }
+        }
+        _ => return Err(IoError::UnsupportedGeometry(kind.into())),
+    }
+    Ok(())
+}
+
+#[inline]
+fn coordinates(value: &Value) -> Result<&Value, IoError> {
+    value
+        .get("coordinates")
+        .ok_or_else(|| IoError::syntax(0, "a geometry without coordinates"))
+}
+
+#[inline]
+fn array(value: &Value) -> Result<&Vec<Value>, IoError> {
+    value
+        .as_array()
+        .ok_or_else(|| IoError::syntax(0, "an array is expected"))
+}
+
+fn polygon(value: &Value) -> Result<RawPolygon, IoError> {
+    let mut polygon = vec![];
+    for ring in array(value)? {
+        let mut points = vec![];
+        for point in array(ring)? {
+            let coords = array(point)?;
+            let x = coords.first().and_then(Value::as_f64);
+            let y = coords.get(1).and_then(Value::as_f64);
+            if let (Some(x), Some(y)) = (x, y) {
+                points.push([x, y]);
+            } else {
+                return Err(IoError::syntax(0, "a position must have two numbers"));
+            }
+        }
+        polygon.push(points);
+    }
+    Ok(polygon)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::geojson::{read, write};
+    use crate::io::{IoError, IoOptions};
+    use i_shape::int::area::Area;
+
+    #[test]
+    fn test_0() {
+        let text = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {"type": "Feature", "properties": {}, "geometry": {
+                    "type": "Polygon",
+                    "coordinates": [[[0, 0], [0, 1], [1, 1], [1, 0], [0, 0]]]
+                }},
+                {"type": "Feature", "properties": {}, "geometry": {
+                    "type": "MultiPolygon",
+                    "coordinates": [[[[2, 0], [3, 0], [3, 1], [2, 1], [2, 0]]]]
+                }}
+            ]
+        }"#;
+
+        let options = IoOptions {
+            scale: 100.0,
+            ..Default::default()
+        };
+        let shapes = read(text, &options).expect("OK");
+        assert_eq!(shapes.len(), 2);
+        assert!(shapes.iter().all(|shape| shape[0].area() == -10_000));
+
+        let json = write(&shapes, &options);
+        assert_eq!(
+            json,
+            r#"{"type":"MultiPolygon","coordinates":[[[[0,0],[1,0],[1,1],[0,1],[0,0]]],[[[2,0],[3,0],[3,1],[2,1],[2,0]]]]}"#
+        );
+        assert_eq!(read(&json, &options).expect("OK"), shapes);
+
+        assert!(matches!(read("{", &options), Err(IoError::Syntax { .. })));
+        assert!(matches!(
+            read(r#"{"type": "LineString", "coordinates": []}"#, &options),
+            Err(IoError::UnsupportedGeometry(_))
+        ));
+    }
+}
@@ -0,0 +1,636 @@
+//! Gerber RS-274X, regions (`G36`/`G37`) and flashes of rectangular apertures.
+//!
+//! Integer coordinates are the coordinates of the file: a unit is `10^-decimal_digits` of a millimeter
+//! or an inch, as set by `%FS` and `%MO`. Dark and clear objects are combined in the file order.
+
+use crate::core::direction::ContourDirection;
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay_rule::OverlayRule;
+use crate::graph::boolean::winding_count::ShapeCountBoolean;
+use crate::io::IoError;
+use crate::io::ring::closed_rings;
+use crate::ortho::overlay::OrthoOverlay;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use i_float::int::point::IntPoint;
+use i_shape::int::area::Area;
+use i_shape::int::shape::{IntContour, IntShape, IntShapes};
+
+/// Unit of a Gerber file, set by `%MOMM*%` or `%MOIN*%`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GerberUnit {
+    #[default]
+    Millimeters,
+    Inches,
+}
+
+/// Coordinate format of a Gerber file.
+/// - `integer_digits`: Digits before the decimal point, only used on export.
+/// - `decimal_digits`: Digits after the decimal point, an integer unit is `10^-decimal_digits` of `unit`.
+/// - `unit`: Millimeters or inches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GerberFormat {
+    pub integer_digits: u8,
+    pub decimal_digits: u8,
+    pub unit: GerberUnit,
+}
+
+impl Default for GerberFormat {
+    fn default() -> Self {
+        Self {
+            integer_digits: 4,
+            decimal_digits: 6,
+            unit: GerberUnit::Millimeters,
+        }
+    }
+}
+
+/// Shapes of a Gerber file with its coordinate format.
+#[derive(Debug, Clone, Default)]
+pub struct GerberImage {
+    pub format: GerberFormat,
+    pub shapes: IntShapes,
+}
+
+/// Reads regions and rectangular flashes, clear objects are subtracted from the objects before them.
+/// - `text`: Gerber text.
+/// - Returns: Counterclockwise shapes in the units of the file, or an error if the text is malformed,
+///   it has arcs, draws or flashes of other apertures, or a region is not rectilinear.
+///   `polygon` and `ring` of a ring error are the index of the region or the flash and the contour index in it.
+pub fn read(text: &str) -> Result<GerberImage, IoError> {
+    let mut reader = Reader {
+        format: GerberFormat::default(),
+        trailing_zeros: false,
+        incremental: false,
+        apertures: BTreeMap::new(),
+        aperture: None,
+        point: [0, 0],
+        is_circular: false,
+        region: None,
+        regions: 0,
+        dark: true,
+        group: Vec::new(),
+        shapes: Vec::new(),
+    };
+
+    let bytes = text.as_bytes();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let ch = bytes[pos];
+        if ch.is_ascii_whitespace() {
+            pos += 1;
+        } else if ch == b'%' {
+            let end = find(bytes, pos + 1, b'%').ok_or_else(|| IoError::syntax(pos, "'%' is expected"))?;
+            let mut start = pos + 1;
+            while let Some(block_end) = find(&bytes[..end], start, b'*') {
+                let block = text[start..block_end].trim();
+                reader.extended(block, start)?;
+                start = block_end + 1;
+            }
+            pos = end + 1;
+        } else {
+            let end = find(bytes, pos, b'*').ok_or_else(|| IoError::syntax(pos, "'*' is expected"))?;
+            if reader.word(text[pos..end].trim(), pos)? {
+                break;
+            }
+            pos = end + 1;
+        }
+    }
+
+    if reader.region.is_some() {
+        return Err(IoError::syntax(text.len(), "G37 is expected"));
+    }
+    reader.flush()?;
+
+    Ok(GerberImage {
+        format: reader.format,
+        shapes: reader.shapes,
+    })
+}
+
+/// Writes shapes as dark regions, holes are written as clear regions after their shape.
+/// Shapes are sorted by area, so an island inside a hole is drawn after the hole is cleared.
+/// - `shapes`: Shapes in the units of `format`.
+/// - `format`: Coordinate format and unit of the file.
+pub fn write(shapes: &[IntShape], format: &GerberFormat) -> String {
+    let mut out = String::new();
+    let digits = (format.integer_digits, format.decimal_digits);
+    _ = writeln!(out, "%FSLAX{}{}Y{}{}*%", digits.0, digits.1, digits.0, digits.1);
+    out.push_str(match format.unit {
+        GerberUnit::Millimeters => "%MOMM*%\n",
+        GerberUnit::Inches => "%MOIN*%\n",
+    });
+
+    let mut order: Vec<(i64, usize)> = shapes
+        .iter()
+        .enumerate()
+        .filter(|(_, shape)| !shape.is_empty())
+        .map(|(i, shape)| (shape[0].area_two().abs(), i))
+        .collect();
+    order.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+    let mut dark = None;
+    for &(_, index) in order.iter() {
+        for (i, ring) in closed_rings(&shapes[index], ContourDirection::CounterClockwise).enumerate() {
+            let is_outer = i == 0;
+            if dark != Some(is_outer) {
+                out.push_str(if is_outer { "%LPD*%\n" } else { "%LPC*%\n" });
+                dark = Some(is_outer);
+            }
+            out.push_str("G36*\n");
+            for (j, p) in ring.iter().enumerate() {
+                _ = writeln!(out, "X{}Y{}D0{}*", p.x, p.y, if j == 0 { 2 } else { 1 });
+                if j == 0 {
+                    out.push_str("G01*\n");
+                }
+            }
+            out.push_str("G37*\n");
+        }
+    }
+    out.push_str("M02*\n");
+
+    out
+}
+
+#[inline]
+fn find(bytes: &[u8], from: usize, ch: u8) -> Option<usize> {
+    bytes[from..].iter().position(|&b| b == ch).map(|i| from + i)
+}
+
+enum Aperture {
+    Rect { width: i64, height: i64 },
+    Other(String),
+}
+
+struct Reader {
+    format: GerberFormat,
+    trailing_zeros: bool,
+    incremental: bool,
+    apertures: BTreeMap<u32, Aperture>,
+    aperture: Option<u32>,
+    point: [i64; 2],
+    is_circular: bool,
+    // contours of the current region, the last one is open
+    region: Option<Vec<Vec<[i64; 2]>>>,
+    regions: usize,
+    dark: bool,
+    // objects of the current polarity which are not merged yet
+    group: Vec<IntContour>,
+    shapes: IntShapes,
+}
+
+impl Reader {
+    fn extended(&mut self, block: &str, pos: usize) -> Result<(), IoError> {
+        if let Some(spec) = block.strip_prefix("FS") {
+            let spec = spec.as_bytes();
+            let mut i = 0;
+            while i < spec.len() && spec[i] != b'X' {
+                match spec[i] {
+                    b'T' => self.trailing_zeros = true,
+                    b'L' => self.trailing_zeros = false,
+                    b'I' => self.incremental = true,
+                    b'A' => self.incremental = false,
+                    _ => {}
+                }
+                i += 1;
+            }
+            if spec.len() < i + 3 || !spec[i + 1].is_ascii_digit() || !spec[i + 2].is_ascii_digit() {
+                return Err(IoError::syntax(pos, "coordinate format is expected"));
+            }
+            self.format.integer_digits = spec[i + 1] - b'0';
+            self.format.decimal_digits = spec[i + 2] - b'0';
+        } else if let Some(unit) = block.strip_prefix("MO") {
+            self.format.unit = match unit {
+                "MM" => GerberUnit::Millimeters,
+                "IN" => GerberUnit::Inches,
+                _ => return Err(IoError::syntax(pos, "MM or IN is expected")),
+            };
+        } else if let Some(polarity) = block.strip_prefix("LP") {
+            let dark = match polarity {
+                "D" => true,
+                "C" => false,
+                _ => return Err(IoError::syntax(pos, "D or C is expected")),
+            };
+            if dark != self.dark {
+                self.flush()?;
+                self.dark = dark;
+            }
+        } else if let Some(definition) = block.strip_prefix("ADD") {
+            self.aperture_definition(definition, pos)?;
+        } else if block.starts_with("SR") && block.len() > 2 {
+            return Err(IoError::UnsupportedGeometry("step and repeat".into()));
+        }
+        // attributes, macros and deprecated parameters do not change the image
+        Ok(())
+    }
+
+    fn aperture_definition(&mut self, definition: &str, pos: usize) -> Result<(), IoError> {
+        let split = definition
+            .find(|ch: char| !ch.is_ascii_digit())
+            .ok_or_else(|| IoError::syntax(pos, "aperture template is expected"))?;
+        let code: u32 = definition[..split]
+            .parse()
+            .map_err(|_| IoError::syntax(pos, "aperture number is expected"))?;
+        let (template, params) = match definition[split..].split_once(',') {
+            Some((template, params)) => (template, params),
+            None => (&definition[split..], ""),
+        };
+
+        let aperture = if template == "R" {
+            let sizes = params
+                .split('X')
+                .map(|v| v.trim().parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| IoError::syntax(pos, "aperture size is expected"))?;
+            if sizes.len() != 2 {
+                return Err(IoError::UnsupportedGeometry(format!(
+                    "rectangle aperture D{} with a hole",
+                    code
+                )));
+            }
+            let scale = self.scale();
+            Aperture::Rect {
+                width: round(sizes[0] * scale),
+                height: round(sizes[1] * scale),
+            }
+        } else {
+            Aperture::Other(template.into())
+        };
+        self.apertures.insert(code, aperture);
+
+        Ok(())
+    }
+
+    /// Applies a word command.
+    /// - Returns: `true` at the end of the file.
+    fn word(&mut self, word: &str, pos: usize) -> Result<bool, IoError> {
+        if word.starts_with("G04") || word.is_empty() {
+            return Ok(false);
+        }
+
+        let bytes = word.as_bytes();
+        let mut x = None;
+        let mut y = None;
+        let mut i = 0;
+        while i < bytes.len() {
+            let letter = bytes[i];
+            let start = i + 1;
+            i = start;
+            while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'-' || bytes[i] == b'+') {
+                i += 1;
+            }
+            let value = &word[start..i];
+            let code = || -> Result<u32, IoError> {
+                value
+                    .parse()
+                    .map_err(|_| IoError::syntax(pos + start, "a number is expected"))
+            };
+            match letter {
+                b'X' => x = Some(self.coordinate(value, pos + start)?),
+                b'Y' => y = Some(self.coordinate(value, pos + start)?),
+                // arc centers, arcs are rejected by D01
+                b'I' | b'J' => {}
+                b'G' => match code()? {
+                    1 => self.is_circular = false,
+                    2 | 3 => self.is_circular = true,
+                    36 => self.region = Some(vec![Vec::new()]),
+                    37 => self.end_region()?,
+                    70 => self.format.unit = GerberUnit::Inches,
+                    71 => self.format.unit = GerberUnit::Millimeters,
+                    90 => self.incremental = false,
+                    91 => self.incremental = true,
+                    _ => {}
+                },
+                b'D' => {
+                    let code = code()?;
+                    if code >= 10 {
+                        self.aperture = Some(code);
+                    } else {
+                        let point = self.move_to(x.take(), y.take());
+                        self.operation(code, point, pos)?;
+                    }
+                }
+                b'M' => {
+                    if matches!(code()?, 0..=2) {
+                        return Ok(true);
+                    }
+                }
+                _ => return Err(IoError::syntax(pos + start - 1, "unknown command")),
+            }
+        }
+
+        // a coordinate without an operation uses the previous one, D01 is the only one left in the standard
+        if x.is_some() || y.is_some() {
+            let point = self.move_to(x, y);
+            self.operation(1, point, pos)?;
+        }
+
+        Ok(false)
+    }
+
+    #[inline]
+    fn move_to(&mut self, x: Option<i64>, y: Option<i64>) -> [i64; 2] {
+        let mut point = self.point;
+        if let Some(x) = x {
+            point[0] = if self.incremental { point[0] + x } else { x };
+        }
+        if let Some(y) = y {
+            point[1] = if self.incremental { point[1] + y } else { y };
+        }
+        point
+    }
+
+    fn operation(&mut self, code: u32, point: [i64; 2], pos: usize) -> Result<(), IoError> {
+        match (code, &mut self.region) {
+            (1, Some(region)) => {
+                if self.is_circular {
+                    return Err(IoError::UnsupportedGeometry("arc".into()));
+                }
+                let contour = region.last_mut().unwrap();
+                if contour.is_empty() {
+                    contour.push(self.point);
+                }
+                contour.push(point);
+            }
+            (2, Some(region)) => {
+                if !region.last().unwrap().is_empty() {
+                    region.push(Vec::new());
+                }
+            }
+            (1, None) => return Err(IoError::UnsupportedGeometry("draw".into())),
+            (2, None) => {}
+            (3, None) => self.flash(point)?,
+            _ => return Err(IoError::syntax(pos, "operation is not allowed here")),
+        }
+        self.point = point;
+        Ok(())
+    }
+
+    fn flash(&mut self, point: [i64; 2]) -> Result<(), IoError> {
+        let code = self.aperture.unwrap_or(0);
+        let (width, height) = match self.apertures.get(&code) {
+            Some(Aperture::Rect { width, height }) => (*width, *height),
+            Some(Aperture::Other(template)) => {
+                return Err(IoError::UnsupportedGeometry(format!(
+                    "flash of {} aperture D{}",
+                    template, code
+                )));
+            }
+            None => {
+                return Err(IoError::UnsupportedGeometry(format!(
+                    "flash of undefined aperture D{}",
+                    code
+                )));
+            }
+        };
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        // an odd size is rounded down on the left and the bottom sides
+        let (x0, y0) = (point[0] - width / 2, point[1] - height / 2);
+        let rect = [[x0, y0], [x0 + width, y0], [x0 + width, y0 + height], [x0, y0 + height]];
+        let contour = self.to_contour(&rect, 0)?;
+        self.group.push(contour);
+        self.regions += 1;
+
+        Ok(())
+    }
+
+    fn end_region(&mut self) -> Result<(), IoError> {
+        let region = self.region.take().unwrap_or_default();
+        for (ring, points) in region.iter().enumerate().filter(|(_, points)| !points.is_empty()) {
+            let mut contour = self.to_contour(points, ring)?;
+            // every contour is filled on its own, whatever its direction
+            if contour.area_two() > 0 {
+                contour[1..].reverse();
+            }
+            self.group.push(contour);
+        }
+        self.regions += 1;
+        Ok(())
+    }
+
+    fn to_contour(&self, points: &[[i64; 2]], ring: usize) -> Result<IntContour, IoError> {
+        let polygon = self.regions;
+        let mut contour: IntContour = Vec::with_capacity(points.len());
+        for (point, p) in points.iter().enumerate() {
+            let p = match (i32::try_from(p[0]), i32::try_from(p[1])) {
+                (Ok(x), Ok(y)) => IntPoint::new(x, y),
+                _ => return Err(IoError::OutOfRange { polygon, ring, point }),
+            };
+            if let Some(&last) = contour.last() {
+                if last == p {
+                    continue;
+                }
+                if last.x != p.x && last.y != p.y {
+                    return Err(IoError::NotRectilinear { polygon, ring, point });
+                }
+            }
+            contour.push(p);
+        }
+
+        if contour.len() > 1 && contour[0] == contour[contour.len() - 1] {
+            contour.pop();
+        }
+
+        if contour.len() < 4 {
+            return Err(IoError::NotValidRing { polygon, ring });
+        }
+
+        let (first, last) = (contour[0], contour[contour.len() - 1]);
+        if first.x != last.x && first.y != last.y {
+            return Err(IoError::NotRectilinear {
+                polygon,
+                ring,
+                point: 0,
+            });
+        }
+
+        Ok(contour)
+    }
+
+    /// Merges the objects of the current polarity into the image.
+    fn flush(&mut self) -> Result<(), IoError> {
+        if self.group.is_empty() {
+            return Ok(());
+        }
+
+        let image: Vec<IntContour> = self.shapes.drain(..).flatten().collect();
+        let overlay_rule = if self.dark {
+            OverlayRule::Union
+        } else {
+            OverlayRule::Difference
+        };
+
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay
+            .init_with_ortho_contours(&image, &self.group)
+            .map_err(|_| IoError::UnsupportedGeometry("region".into()))?;
+        self.shapes = overlay.overlay(overlay_rule, FillRule::NonZero);
+        self.group.clear();
+
+        Ok(())
+    }
+
+    fn coordinate(&self, value: &str, pos: usize) -> Result<i64, IoError> {
+        let (negative, digits) = match value.as_bytes().first() {
+            Some(b'-') => (true, &value[1..]),
+            Some(b'+') => (false, &value[1..]),
+            _ => (false, value),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(IoError::syntax(pos, "a coordinate is expected"));
+        }
+
+        let mut number: i64 = digits
+            .parse()
+            .map_err(|_| IoError::syntax(pos, "coordinate is too big"))?;
+        if self.trailing_zeros {
+            let total = (self.format.integer_digits + self.format.decimal_digits) as usize;
+            for _ in digits.len()..total {
+                number *= 10;
+            }
+        }
+
+        Ok(if negative { -number } else { number })
+    }
+
+    #[inline]
+    fn scale(&self) -> f64 {
+        let mut scale = 1.0;
+        for _ in 0..self.format.decimal_digits {
+            scale *= 10.0;
+        }
+        scale
+    }
+}
+
+#[inline]
+fn round(value: f64) -> i64 {
+    // no_std has no `f64::round`
+    if value < 0.0 {
+        (value - 0.5) as i64
+    } else {
+        (value + 0.5) as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::io::IoError;
+    use crate::io::gerber::{GerberFormat, GerberUnit, read, write};
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::test_util::rect;
+    use alloc::vec;
+    use i_shape::int::area::Area;
+    use i_shape::int::shape::IntShapes;
+
+    fn area(shapes: &IntShapes) -> i64 {
+        shapes.iter().map(|shape| shape.area()).sum()
+    }
+
+    #[test]
+    fn test_0() {
+        let text = "\
+G04 copper pour with a clearance*
+%FSLAX25Y25*%
+%MOIN*%
+%ADD10R,0.01X0.02*%
+%ADD11C,0.01*%
+%LPD*%
+G36*
+X0Y0D02*
+G01*
+X1000000D01*
+Y500000D01*
+X0D01*
+Y0D01*
+G37*
+%LPC*%
+G36*
+X100000Y100000D02*
+X200000Y100000D01*
+X200000Y200000D01*
+X100000Y200000D01*
+X100000Y100000D01*
+G37*
+%LPD*%
+D10*
+X150000Y150000D03*
+M02*
+";
+        let image = read(text).expect("OK");
+        assert_eq!(
+            image.format,
+            GerberFormat {
+                integer_digits: 2,
+                decimal_digits: 5,
+                unit: GerberUnit::Inches,
+            }
+        );
+
+        // the flash is 1000 x 2000 inside the cleared square
+        assert_eq!(image.shapes.len(), 2);
+        assert_eq!(area(&image.shapes), -(1000000 * 500000 - 100000 * 100000 + 1000 * 2000));
+        assert!(image.shapes.iter().any(|shape| shape.len() == 2));
+    }
+
+    #[test]
+    fn test_1() {
+        // a hole with an island inside, and a cut-in region
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay
+            .init_with_ortho_contours(&[rect(0, 0, 100, 100)], &[rect(20, 20, 80, 80)])
+            .expect("OK");
+        let mut shapes = overlay.overlay(OverlayRule::Difference, FillRule::NonZero);
+        shapes.insert(0, vec![rect(40, 40, 60, 60)]);
+
+        let text = write(&shapes, &GerberFormat::default());
+        assert!(text.starts_with("%FSLAX46Y46*%\n%MOMM*%\n%LPD*%\nG36*\n"));
+
+        let image = read(&text).expect("OK");
+        assert_eq!(image.format, GerberFormat::default());
+        overlay
+            .init_with_ortho_contours(&image.shapes.concat(), &shapes.concat())
+            .expect("OK");
+        assert!(overlay.overlay(OverlayRule::Xor, FillRule::NonZero).is_empty());
+
+        let cut_in = "%FSLAX46Y46*%%MOMM*%G36*X0Y0D02*G01*X30Y0D01*Y30D01*X10D01*Y20D01*X20D01*Y10D01*X10D01*Y30D01*X0D01*Y0D01*G37*M02*";
+        let image = read(cut_in).expect("OK");
+        assert_eq!(image.shapes.len(), 1);
+        assert_eq!(image.shapes[0].len(), 2);
+        assert_eq!(area(&image.shapes), -800);
+    }
+
+    #[test]
+    fn test_2() {
+        let header = "%FSLAX46Y46*%%MOMM*%%ADD10C,0.5*%";
+        let region = |body: &str| alloc::format!("{}G36*X0Y0D02*{}G37*M02*", header, body);
+
+        let arc = region("G03X10Y10I5J0D01*G01*X0D01*Y0D01*");
+        assert!(matches!(read(&arc), Err(IoError::UnsupportedGeometry(_))));
+
+        let diagonal = region("G01*X10Y0D01*X0Y10D01*X0Y0D01*");
+        assert!(matches!(
+            read(&diagonal),
+            Err(IoError::NotRectilinear {
+                polygon: 0,
+                ring: 0,
+                point: 2
+            })
+        ));
+
+        let flash = alloc::format!("{}D10*X0Y0D03*M02*", header);
+        assert!(matches!(read(&flash), Err(IoError::UnsupportedGeometry(_))));
+
+        assert!(matches!(read("%FSLAX46Y46*%G36*X0Y0D02*"), Err(IoError::Syntax { .. })));
+    }
+}
@@ -0,0 +1,86 @@
+//! Import and export of ortho shapes, enabled by the `io` feature.
+//!
+//! WKT, WKB and GeoJSON support only `Polygon` and `MultiPolygon` geometries. Coordinates are
+//! multiplied by `scale` and rounded on import, and divided by it on export.
+//! Layout formats, [`gdsii`] and [`oasis`], keep database units and group contours by layer,
+//! [`gerber`] keeps the units of its format specification.
+
+pub mod gdsii;
+pub mod geojson;
+pub mod gerber;
+pub mod oasis;
+pub mod wkb;
+pub mod wkt;
+mod flatten;
+mod ring;
+
+use crate::core::direction::ContourDirection;
+use alloc::string::String;
+use core::fmt;
+
+/// Configuration options for reading and writing shapes.
+#[derive(Debug, Clone, Copy)]
+pub struct IoOptions {
+    /// Factor from file coordinates to integer coordinates.
+    pub scale: f64,
+
+    /// Direction of outer contours, holes always go in the opposite direction.
+    /// Imported shapes are reoriented, exported rings follow it as well.
+    pub direction: ContourDirection,
+}
+
+impl Default for IoOptions {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            direction: ContourDirection::CounterClockwise,
+        }
+    }
+}
+
+/// An error of reading shapes. `polygon`, `ring` and `point` are indices in the input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IoError {
+    /// The input is malformed, `position` is a char offset in a text or a byte offset in a binary.
+    Syntax { position: usize, message: String },
+    /// The geometry is not a `Polygon` or a `MultiPolygon`.
+    UnsupportedGeometry(String),
+    /// A ring has a segment which is neither vertical nor horizontal.
+    NotRectilinear { polygon: usize, ring: usize, point: usize },
+    /// A ring has less than four distinct corners.
+    NotValidRing { polygon: usize, ring: usize },
+    /// A scaled coordinate does not fit in `i32`.
+    OutOfRange { polygon: usize, ring: usize, point: usize },
+}
+
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IoError::Syntax { position, message } => write!(f, "syntax error at {}: {}", position, message),
+            IoError::UnsupportedGeometry(name) => write!(f, "unsupported geometry: {}", name),
+            IoError::NotRectilinear { polygon, ring, point } => write!(
+                f,
+                "ring {} of polygon {} is not rectilinear at point {}",
+                ring, polygon, point
+            ),
+            IoError::NotValidRing { polygon, ring } => {
+                write!(f, "ring {} of polygon {} has less than 4 corners", ring, polygon)
+            }
+            IoError::OutOfRange { polygon, ring, point } => write!(
+                f,
+                "point {} of ring {} of polygon {} is out of the i32 range",
+                point, ring, polygon
+            ),
+        }
+    }
+}
+
+impl IoError {
+    #[inline]
+    pub(crate) fn syntax(position: usize, message: &str) -> Self {
+        IoError::Syntax {
+            position,
+            message: message.into(),
+        }
+    }
+}
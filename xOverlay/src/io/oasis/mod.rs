@@ -0,0 +1,312 @@
+//! OASIS stream format, `RECTANGLE` and `POLYGON` records grouped by layer and datatype.
+//!
+//! Repetitions and modal variables are expanded on import and placements are flattened like in
+//! [`gdsii`](crate::io::gdsii). Compressed blocks, paths, trapezoids and circles are not supported.
+//! The writer emits rectangles, e.g. the result of
+//! [`OrthoOverlay::fracture`](crate::ortho::overlay::OrthoOverlay), into a single cell.
+
+mod reader;
+mod stream;
+mod writer;
+
+use crate::io::IoError;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use i_float::int::rect::IntRect;
+use i_shape::int::shape::IntContour;
+
+/// A layer and a datatype of a geometry record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OasisLayer {
+    pub layer: u32,
+    pub datatype: u32,
+}
+
+impl OasisLayer {
+    #[inline]
+    pub fn new(layer: u32, datatype: u32) -> Self {
+        Self { layer, datatype }
+    }
+}
+
+/// A flattened layout: contours of every top cell grouped by layer.
+/// - `unit`: Database units per micron.
+#[derive(Debug, Clone, Default)]
+pub struct OasisLibrary {
+    pub unit: f64,
+    pub layers: BTreeMap<OasisLayer, Vec<IntContour>>,
+}
+
+impl OasisLibrary {
+    /// Collects the contours of several layers into one set, e.g. a subject or a clip of an overlay.
+    pub fn contours(&self, layers: &[OasisLayer]) -> Vec<IntContour> {
+        let mut contours = Vec::new();
+        for layer in layers.iter() {
+            if let Some(list) = self.layers.get(layer) {
+                contours.extend_from_slice(list);
+            }
+        }
+        contours
+    }
+}
+
+/// Configuration options for writing a layout.
+#[derive(Debug, Clone)]
+pub struct OasisOptions {
+    /// Name of the single cell which holds all the rectangles.
+    pub cell: String,
+
+    /// Database units per micron.
+    pub unit: f64,
+}
+
+impl Default for OasisOptions {
+    fn default() -> Self {
+        Self {
+            cell: "TOP".into(),
+            unit: 1000.0,
+        }
+    }
+}
+
+/// Reads a layout and flattens it into contours.
+/// - `bytes`: OASIS stream.
+/// - `top`: The cell to flatten, or `None` to flatten every cell which is not placed in another one.
+/// - Returns: The contours by layer, or an error if the stream is broken, it has unsupported records,
+///   a placement is rotated by an angle which is not a multiple of 90 degrees, placements form a cycle
+///   or a polygon is not rectilinear. Ring errors refer to the polygon index in its cell.
+pub fn read(bytes: &[u8], top: Option<&str>) -> Result<OasisLibrary, IoError> {
+    reader::read(bytes, top)
+}
+
+/// Writes rectangles of a single cell, modal variables are used to skip repeated values.
+/// - `layers`: Rectangles by layer.
+/// - `options`: The cell name and the unit.
+pub fn write(layers: &BTreeMap<OasisLayer, Vec<IntRect>>, options: &OasisOptions) -> Vec<u8> {
+    writer::write(layers, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::io::IoError;
+    use crate::io::oasis::stream::*;
+    use crate::io::oasis::{OasisLayer, OasisOptions, read, write};
+    use crate::ortho::fracture::FractureOptions;
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::test_util::rect;
+    use alloc::collections::BTreeMap;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_float::int::point::IntPoint;
+    use i_shape::int::shape::{IntContour, IntShapes};
+
+    fn union(contours: &[IntContour]) -> IntShapes {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.init_with_ortho_contours(contours, &[]).expect("OK");
+        overlay.overlay(OverlayRule::Subject, FillRule::NonZero)
+    }
+
+    fn bounds(contour: &IntContour) -> [i32; 4] {
+        let xs = contour.iter().map(|p| p.x);
+        let ys = contour.iter().map(|p| p.y);
+        [
+            xs.clone().min().unwrap(),
+            ys.clone().min().unwrap(),
+            xs.max().unwrap(),
+            ys.max().unwrap(),
+        ]
+    }
+
+    fn begin() -> StreamWriter {
+        let mut writer = StreamWriter { bytes: Vec::new() };
+        writer.bytes.extend_from_slice(MAGIC);
+        writer.uint(START);
+        writer.string(b"1.0");
+        writer.real(1000.0);
+        writer.uint(0);
+        (0..12).for_each(|_| writer.uint(0));
+        writer
+    }
+
+    fn end(mut writer: StreamWriter) -> Vec<u8> {
+        writer.uint(END);
+        writer.uint(0);
+        writer.uint(0);
+        writer.bytes
+    }
+
+    #[test]
+    fn test_0() {
+        // the result goes through a fracture and back
+        let subj = [rect(0, 0, 100, 100), rect(200, 0, 300, 50)];
+        let clip = [rect(20, 20, 40, 40), rect(90, -10, 250, 10)];
+
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.init_with_ortho_contours(&subj, &clip).expect("OK");
+        let shapes = overlay.overlay(OverlayRule::Difference, FillRule::NonZero);
+
+        overlay.init_with_ortho_contours(&subj, &clip).expect("OK");
+        let rects = overlay.fracture(OverlayRule::Difference, FillRule::NonZero, &FractureOptions::default());
+
+        let mut layers = BTreeMap::new();
+        layers.insert(OasisLayer::new(3, 0), rects.clone());
+        layers.insert(OasisLayer::new(7, 2), vec![rects[0].clone()]);
+
+        let bytes = write(&layers, &OasisOptions::default());
+        let library = read(&bytes, None).expect("OK");
+
+        assert_eq!(library.unit, 1000.0);
+        assert_eq!(library.layers.len(), 2);
+        assert_eq!(library.layers[&OasisLayer::new(3, 0)].len(), rects.len());
+        let restored = &library.layers[&OasisLayer::new(3, 0)];
+        overlay
+            .init_with_ortho_contours(restored, &shapes.concat())
+            .expect("OK");
+        assert!(overlay.overlay(OverlayRule::Xor, FillRule::NonZero).is_empty());
+        assert_eq!(library.layers[&OasisLayer::new(7, 2)].len(), 1);
+
+        let all = library.contours(&[OasisLayer::new(3, 0), OasisLayer::new(7, 2), OasisLayer::new(9, 9)]);
+        assert_eq!(all.len(), rects.len() + 1);
+    }
+
+    #[test]
+    fn test_1() {
+        let mut writer = begin();
+        writer.uint(CELLNAME_IMPLICIT);
+        writer.string(b"A");
+
+        // cell 0 is A
+        writer.uint(CELL_REF);
+        writer.uint(0);
+        // a square 10x10 on layer 1 at (0, 0), repeated 2x2 with a step of 20
+        writer.uint(RECTANGLE);
+        writer.bytes.push(0x80 | 0x40 | 0x10 | 0x08 | 0x04 | 0x02 | 0x01);
+        writer.uint(1);
+        writer.uint(0);
+        writer.uint(10);
+        writer.sint(0);
+        writer.sint(0);
+        writer.uint(1);
+        writer.uint(0);
+        writer.uint(0);
+        writer.uint(20);
+        writer.uint(20);
+        // the same square at x = 100 with the modal layer and size, a property on it
+        writer.uint(RECTANGLE);
+        writer.bytes.push(0x80 | 0x10);
+        writer.sint(100);
+        writer.uint(PROPERTY);
+        writer.bytes.push(0x10 | 0x04);
+        writer.string(b"S_NAME");
+        writer.uint(10);
+        writer.string(b"value");
+        // an L-shape with a manhattan point list, 100 to the right of the previous rectangle
+        writer.uint(XY_RELATIVE);
+        writer.uint(POLYGON);
+        writer.bytes.push(0x20 | 0x10);
+        writer.uint(0);
+        writer.uint(4);
+        writer.sint(20);
+        writer.sint(10);
+        writer.sint(-10);
+        writer.sint(10);
+        writer.sint(100);
+        // a text is skipped
+        writer.uint(TEXT);
+        writer.bytes.push(0x40 | 0x01 | 0x02 | 0x10);
+        writer.string(b"label");
+        writer.uint(5);
+        writer.uint(0);
+        writer.sint(7);
+
+        writer.uint(CELL);
+        writer.string(b"TOP");
+        // A rotated by 90 degrees and reflected at (1000, 0), repeated along y twice with a step of 500
+        writer.uint(PLACEMENT);
+        writer.bytes.push(0x80 | 0x40 | 0x20 | 0x08 | 0x02 | 0x01);
+        writer.uint(0);
+        writer.sint(1000);
+        writer.uint(3);
+        writer.uint(0);
+        writer.uint(500);
+
+        let bytes = end(writer);
+        let library = read(&bytes, None).expect("OK");
+        let contours = &library.layers[&OasisLayer::new(1, 0)];
+        assert_eq!(contours.len(), 12);
+
+        let cell = read(&bytes, Some("A")).expect("OK");
+        let local = &cell.layers[&OasisLayer::new(1, 0)];
+        assert_eq!(
+            local.iter().map(bounds).collect::<Vec<_>>(),
+            vec![
+                [0, 0, 10, 10],
+                [20, 0, 30, 10],
+                [0, 20, 10, 30],
+                [20, 20, 30, 30],
+                [100, 0, 110, 10],
+                [200, 0, 220, 20],
+            ]
+        );
+        assert_eq!(
+            union(&local[5..]),
+            union(&[vec![
+                IntPoint::new(200, 0),
+                IntPoint::new(220, 0),
+                IntPoint::new(220, 10),
+                IntPoint::new(210, 10),
+                IntPoint::new(210, 20),
+                IntPoint::new(200, 20),
+            ]])
+        );
+
+        // (x, y) -> (x, -y) -> (y, x)
+        assert_eq!(bounds(&contours[1]), [1000, 20, 1010, 30]);
+        assert_eq!(bounds(&contours[11]), [1000, 700, 1020, 720]);
+    }
+
+    #[test]
+    fn test_2() {
+        let cell = |writer: &mut StreamWriter| {
+            writer.uint(CELL);
+            writer.string(b"TOP");
+        };
+
+        assert!(matches!(read(b"%SEMI-OASIS\n", None), Err(IoError::Syntax { .. })));
+
+        // a circle
+        let mut writer = begin();
+        cell(&mut writer);
+        writer.uint(27);
+        assert!(matches!(read(&end(writer), None), Err(IoError::UnsupportedGeometry(_))));
+
+        // a rectangle without the modal layer
+        let mut writer = begin();
+        cell(&mut writer);
+        writer.uint(RECTANGLE);
+        writer.bytes.push(0x80 | 0x40);
+        writer.uint(10);
+        assert!(matches!(read(&end(writer), None), Err(IoError::Syntax { .. })));
+
+        // a placement of itself
+        let mut writer = begin();
+        cell(&mut writer);
+        writer.uint(PLACEMENT);
+        writer.bytes.push(0x80);
+        writer.string(b"TOP");
+        assert!(matches!(
+            read(&end(writer), Some("TOP")),
+            Err(IoError::UnsupportedGeometry(_))
+        ));
+
+        // a broken stream
+        let mut writer = begin();
+        cell(&mut writer);
+        writer.uint(RECTANGLE);
+        assert!(matches!(read(&writer.bytes, None), Err(IoError::Syntax { .. })));
+    }
+}
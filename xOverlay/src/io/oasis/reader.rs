@@ -0,0 +1,575 @@
+use crate::io::IoError;
+use crate::io::flatten::{Cell, Reference, Transform, flatten};
+use crate::io::oasis::stream::*;
+use crate::io::oasis::{OasisLayer, OasisLibrary};
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+
+// the biggest repetition which is expanded
+const MAX_REPETITION: u64 = 1 << 24;
+
+#[derive(Clone)]
+enum CellKey {
+    Name(String),
+    Number(u64),
+}
+
+/// Modal variables, they are reset at the beginning of every cell.
+#[derive(Default)]
+struct Modal {
+    layer: Option<u32>,
+    datatype: Option<u32>,
+    width: Option<i64>,
+    height: Option<i64>,
+    geometry: [i64; 2],
+    placement: [i64; 2],
+    text: [i64; 2],
+    points: Option<Vec<[i64; 2]>>,
+    repetition: Option<Vec<[i64; 2]>>,
+    placement_cell: Option<CellKey>,
+    relative: bool,
+}
+
+struct ParsedCell {
+    key: CellKey,
+    cell: Cell<OasisLayer>,
+    // cells of `cell.references`, names are resolved at the end of the stream
+    references: Vec<CellKey>,
+}
+
+pub(super) fn read(bytes: &[u8], top: Option<&str>) -> Result<OasisLibrary, IoError> {
+    let mut stream = StreamReader::new(bytes);
+    if stream.bytes(MAGIC.len()).ok() != Some(MAGIC) {
+        return Err(IoError::syntax(0, "OASIS magic bytes are expected"));
+    }
+
+    let mut parser = Parser {
+        stream,
+        modal: Modal::default(),
+        unit: 1.0,
+        names: BTreeMap::new(),
+        next_name: 0,
+        cells: Vec::new(),
+    };
+    parser.parse()?;
+
+    let names = core::mem::take(&mut parser.names);
+    let name_of = |key: &CellKey| match key {
+        CellKey::Name(name) => name.clone(),
+        CellKey::Number(number) => names.get(number).cloned().unwrap_or_else(|| format!("#{}", number)),
+    };
+
+    let mut cells = BTreeMap::new();
+    let mut order = Vec::with_capacity(parser.cells.len());
+    for mut parsed in parser.cells.into_iter() {
+        for (r, key) in parsed.cell.references.iter_mut().zip(parsed.references.iter()) {
+            r.name = name_of(key);
+        }
+        let name = name_of(&parsed.key);
+        order.push(name.clone());
+        cells.insert(name, parsed.cell);
+    }
+
+    Ok(OasisLibrary {
+        unit: parser.unit,
+        layers: flatten(&cells, &order, top)?,
+    })
+}
+
+struct Parser<'a> {
+    stream: StreamReader<'a>,
+    modal: Modal,
+    unit: f64,
+    names: BTreeMap<u64, String>,
+    next_name: u64,
+    cells: Vec<ParsedCell>,
+}
+
+impl Parser<'_> {
+    fn parse(&mut self) -> Result<(), IoError> {
+        loop {
+            if self.stream.is_end() {
+                return Err(IoError::syntax(self.stream.pos, "END record is expected"));
+            }
+            let position = self.stream.pos;
+            let id = self.stream.uint()?;
+            match id {
+                PAD | PROPERTY_REPEAT => {}
+                START => {
+                    self.stream.string()?;
+                    self.unit = self.stream.real()?;
+                    if self.stream.uint()? == 0 {
+                        // offsets of the name tables
+                        for _ in 0..12 {
+                            self.stream.uint()?;
+                        }
+                    }
+                }
+                END => return Ok(()),
+                CELLNAME_IMPLICIT => {
+                    let name = self.stream.string()?;
+                    self.names.insert(self.next_name, name);
+                    self.next_name += 1;
+                }
+                CELLNAME => {
+                    let name = self.stream.string()?;
+                    let number = self.stream.uint()?;
+                    self.names.insert(number, name);
+                }
+                // TEXTSTRING, PROPNAME and PROPSTRING
+                5 | 7 | 9 => {
+                    self.stream.string()?;
+                }
+                6 | 8 | 10 => {
+                    self.stream.string()?;
+                    self.stream.uint()?;
+                }
+                // LAYERNAME
+                11 | 12 => {
+                    self.stream.string()?;
+                    self.interval()?;
+                    self.interval()?;
+                }
+                CELL_REF => {
+                    let number = self.stream.uint()?;
+                    self.begin_cell(CellKey::Number(number));
+                }
+                CELL => {
+                    let name = self.stream.string()?;
+                    self.begin_cell(CellKey::Name(name));
+                }
+                XY_ABSOLUTE => self.modal.relative = false,
+                XY_RELATIVE => self.modal.relative = true,
+                PLACEMENT | PLACEMENT_MAG => self.placement(id == PLACEMENT_MAG)?,
+                TEXT => self.text()?,
+                RECTANGLE => self.rectangle()?,
+                POLYGON => self.polygon()?,
+                22 => return Err(IoError::UnsupportedGeometry("PATH".into())),
+                23..=26 => return Err(IoError::UnsupportedGeometry("TRAPEZOID".into())),
+                27 => return Err(IoError::UnsupportedGeometry("CIRCLE".into())),
+                PROPERTY => self.property()?,
+                // XNAME
+                30 => {
+                    self.stream.uint()?;
+                    self.stream.string()?;
+                }
+                31 => {
+                    self.stream.uint()?;
+                    self.stream.string()?;
+                    self.stream.uint()?;
+                }
+                // XELEMENT
+                32 => {
+                    self.stream.uint()?;
+                    self.stream.string()?;
+                }
+                33 => return Err(IoError::UnsupportedGeometry("XGEOMETRY".into())),
+                34 => return Err(IoError::UnsupportedGeometry("CBLOCK".into())),
+                _ => return Err(IoError::syntax(position, "unknown record")),
+            }
+        }
+    }
+
+    fn begin_cell(&mut self, key: CellKey) {
+        self.modal = Modal::default();
+        self.cells.push(ParsedCell {
+            key,
+            cell: Cell::default(),
+            references: Vec::new(),
+        });
+    }
+
+    fn current_cell(&mut self, position: usize) -> Result<&mut ParsedCell, IoError> {
+        self.cells
+            .last_mut()
+            .ok_or_else(|| IoError::syntax(position, "element is out of a cell"))
+    }
+
+    fn interval(&mut self) -> Result<(), IoError> {
+        match self.stream.uint()? {
+            0 => {}
+            1..=3 => {
+                self.stream.uint()?;
+            }
+            _ => {
+                self.stream.uint()?;
+                self.stream.uint()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads x and y, relative values are added to the modal position.
+    fn position(
+        &mut self,
+        info: u8,
+        x_bit: u8,
+        y_bit: u8,
+        modal: fn(&mut Modal) -> &mut [i64; 2],
+    ) -> Result<[i64; 2], IoError> {
+        let relative = self.modal.relative;
+        let x = if info & x_bit != 0 {
+            Some(self.stream.sint()?)
+        } else {
+            None
+        };
+        let y = if info & y_bit != 0 {
+            Some(self.stream.sint()?)
+        } else {
+            None
+        };
+        let p = modal(&mut self.modal);
+        if let Some(x) = x {
+            p[0] = if relative { p[0] + x } else { x };
+        }
+        if let Some(y) = y {
+            p[1] = if relative { p[1] + y } else { y };
+        }
+        Ok(*p)
+    }
+
+    /// Reads a repetition if the flag is set, the result always includes the zero offset.
+    fn repetition(&mut self, is_set: bool) -> Result<Vec<[i64; 2]>, IoError> {
+        if !is_set {
+            return Ok(vec![[0, 0]]);
+        }
+        let position = self.stream.pos;
+        let kind = self.stream.uint()?;
+        if kind == 0 {
+            return self
+                .modal
+                .repetition
+                .clone()
+                .ok_or_else(|| IoError::syntax(position, "modal repetition is not set"));
+        }
+
+        let s = &mut self.stream;
+        let count = |s: &mut StreamReader, extra: u64| -> Result<u64, IoError> {
+            let n = s.uint()?.saturating_add(2);
+            if n.saturating_mul(extra) > MAX_REPETITION {
+                return Err(IoError::syntax(position, "repetition is too big"));
+            }
+            Ok(n)
+        };
+
+        let mut offsets = Vec::new();
+        match kind {
+            1 => {
+                let nx = count(s, 1)?;
+                let ny = count(s, nx)?;
+                let (dx, dy) = (s.uint()? as i64, s.uint()? as i64);
+                for j in 0..ny as i64 {
+                    for i in 0..nx as i64 {
+                        offsets.push([i * dx, j * dy]);
+                    }
+                }
+            }
+            2 | 3 => {
+                let n = count(s, 1)?;
+                let d = s.uint()? as i64;
+                for i in 0..n as i64 {
+                    offsets.push(if kind == 2 { [i * d, 0] } else { [0, i * d] });
+                }
+            }
+            4..=7 => {
+                let n = count(s, 1)?;
+                let grid = if kind == 5 || kind == 7 { s.uint()? as i64 } else { 1 };
+                let mut d = 0;
+                offsets.push([0, 0]);
+                for _ in 1..n {
+                    d += s.uint()? as i64 * grid;
+                    offsets.push(if kind < 6 { [d, 0] } else { [0, d] });
+                }
+            }
+            8 => {
+                let n = count(s, 1)?;
+                let m = count(s, n)?;
+                let (dn, dm) = (s.delta_g()?, s.delta_g()?);
+                for j in 0..m as i64 {
+                    for i in 0..n as i64 {
+                        offsets.push([i * dn[0] + j * dm[0], i * dn[1] + j * dm[1]]);
+                    }
+                }
+            }
+            9 => {
+                let n = count(s, 1)?;
+                let d = s.delta_g()?;
+                for i in 0..n as i64 {
+                    offsets.push([i * d[0], i * d[1]]);
+                }
+            }
+            10 | 11 => {
+                let n = count(s, 1)?;
+                let grid = if kind == 11 { s.uint()? as i64 } else { 1 };
+                let mut p = [0, 0];
+                offsets.push(p);
+                for _ in 1..n {
+                    let d = s.delta_g()?;
+                    p = [p[0] + d[0] * grid, p[1] + d[1] * grid];
+                    offsets.push(p);
+                }
+            }
+            _ => return Err(IoError::syntax(position, "unknown repetition type")),
+        }
+
+        self.modal.repetition = Some(offsets.clone());
+        Ok(offsets)
+    }
+
+    /// Reads a point list of a polygon, the first point is the origin and the closing point is omitted.
+    fn point_list(&mut self) -> Result<Vec<[i64; 2]>, IoError> {
+        let position = self.stream.pos;
+        let kind = self.stream.uint()?;
+        let count = self.stream.uint()?;
+        if count > MAX_REPETITION {
+            return Err(IoError::syntax(position, "point list is too big"));
+        }
+
+        let mut points = Vec::with_capacity(count as usize + 2);
+        let mut p = [0, 0];
+        points.push(p);
+        match kind {
+            0 | 1 => {
+                // alternating horizontal and vertical edges, the last vertex is implicit
+                let mut is_horizontal = kind == 0;
+                for _ in 0..count {
+                    let d = self.stream.sint()?;
+                    if is_horizontal {
+                        p[0] += d;
+                    } else {
+                        p[1] += d;
+                    }
+                    points.push(p);
+                    is_horizontal = !is_horizontal;
+                }
+                points.push(if is_horizontal { [0, p[1]] } else { [p[0], 0] });
+            }
+            2..=5 => {
+                let mut d = [0, 0];
+                for _ in 0..count {
+                    let delta = match kind {
+                        2 => self.stream.delta_2()?,
+                        3 => self.stream.delta_3()?,
+                        _ => self.stream.delta_g()?,
+                    };
+                    if kind == 5 {
+                        d = [d[0] + delta[0], d[1] + delta[1]];
+                    } else {
+                        d = delta;
+                    }
+                    p = [p[0] + d[0], p[1] + d[1]];
+                    points.push(p);
+                }
+            }
+            _ => return Err(IoError::syntax(position, "unknown point list type")),
+        }
+
+        Ok(points)
+    }
+
+    fn layer(&mut self, info: u8, position: usize) -> Result<OasisLayer, IoError> {
+        if info & 0x01 != 0 {
+            self.modal.layer = Some(self.stream.uint()? as u32);
+        }
+        if info & 0x02 != 0 {
+            self.modal.datatype = Some(self.stream.uint()? as u32);
+        }
+        match (self.modal.layer, self.modal.datatype) {
+            (Some(layer), Some(datatype)) => Ok(OasisLayer { layer, datatype }),
+            _ => Err(IoError::syntax(position, "modal layer is not set")),
+        }
+    }
+
+    fn add_polygons(
+        &mut self,
+        layer: OasisLayer,
+        points: &[[i64; 2]],
+        origin: [i64; 2],
+        offsets: &[[i64; 2]],
+        position: usize,
+    ) -> Result<(), IoError> {
+        let cell = self.current_cell(position)?;
+        for offset in offsets.iter() {
+            let (x, y) = (origin[0] + offset[0], origin[1] + offset[1]);
+            let mut contour = Vec::with_capacity(points.len());
+            for (index, p) in points.iter().enumerate() {
+                match (i32::try_from(x + p[0]), i32::try_from(y + p[1])) {
+                    (Ok(x), Ok(y)) => contour.push(IntPoint::new(x, y)),
+                    _ => {
+                        return Err(IoError::OutOfRange {
+                            polygon: cell.cell.polygons.len(),
+                            ring: 0,
+                            point: index,
+                        });
+                    }
+                }
+            }
+            cell.cell.polygons.push((layer, contour));
+        }
+        Ok(())
+    }
+
+    fn rectangle(&mut self) -> Result<(), IoError> {
+        let position = self.stream.pos;
+        let info = self.stream.byte()?;
+        let layer = self.layer(info, position)?;
+        let is_square = info & 0x80 != 0;
+        if info & 0x40 != 0 {
+            self.modal.width = Some(self.stream.uint()? as i64);
+        }
+        if info & 0x20 != 0 {
+            if is_square {
+                return Err(IoError::syntax(position, "square has a height"));
+            }
+            self.modal.height = Some(self.stream.uint()? as i64);
+        }
+        let origin = self.position(info, 0x10, 0x08, |m| &mut m.geometry)?;
+        let offsets = self.repetition(info & 0x04 != 0)?;
+
+        let width = self
+            .modal
+            .width
+            .ok_or_else(|| IoError::syntax(position, "modal width is not set"))?;
+        let height = if is_square {
+            self.modal.height = Some(width);
+            width
+        } else {
+            self.modal
+                .height
+                .ok_or_else(|| IoError::syntax(position, "modal height is not set"))?
+        };
+
+        let points = [[0, 0], [width, 0], [width, height], [0, height]];
+        self.add_polygons(layer, &points, origin, &offsets, position)
+    }
+
+    fn polygon(&mut self) -> Result<(), IoError> {
+        let position = self.stream.pos;
+        let info = self.stream.byte()?;
+        let layer = self.layer(info, position)?;
+        if info & 0x20 != 0 {
+            self.modal.points = Some(self.point_list()?);
+        }
+        let origin = self.position(info, 0x10, 0x08, |m| &mut m.geometry)?;
+        let offsets = self.repetition(info & 0x04 != 0)?;
+
+        let points = self
+            .modal
+            .points
+            .take()
+            .ok_or_else(|| IoError::syntax(position, "modal point list is not set"))?;
+        let result = self.add_polygons(layer, &points, origin, &offsets, position);
+        self.modal.points = Some(points);
+        result
+    }
+
+    fn placement(&mut self, is_mag: bool) -> Result<(), IoError> {
+        let position = self.stream.pos;
+        let info = self.stream.byte()?;
+        if info & 0x80 != 0 {
+            let key = if info & 0x40 != 0 {
+                CellKey::Number(self.stream.uint()?)
+            } else {
+                CellKey::Name(self.stream.string()?)
+            };
+            self.modal.placement_cell = Some(key);
+        }
+        let key = self
+            .modal
+            .placement_cell
+            .clone()
+            .ok_or_else(|| IoError::syntax(position, "modal placement cell is not set"))?;
+
+        let (mag, angle) = if is_mag {
+            let mag = if info & 0x04 != 0 { self.stream.real()? } else { 1.0 };
+            let angle = if info & 0x02 != 0 { self.stream.real()? } else { 0.0 };
+            (mag, angle)
+        } else {
+            (1.0, 90.0 * ((info >> 1) & 3) as f64)
+        };
+
+        let origin = self.position(info, 0x20, 0x10, |m| &mut m.placement)?;
+        let offsets = self.repetition(info & 0x08 != 0)?;
+
+        let origin = [origin[0] as f64, origin[1] as f64];
+        let transform = if let Some(transform) = Transform::new(origin, info & 0x01 != 0, mag, angle) {
+            transform
+        } else {
+            return Err(IoError::UnsupportedGeometry(format!(
+                "placement is rotated by {} degrees",
+                angle
+            )));
+        };
+
+        let cell = self.current_cell(position)?;
+        cell.cell.references.push(Reference {
+            name: String::new(),
+            transform,
+            offsets: offsets.iter().map(|d| [d[0] as f64, d[1] as f64]).collect(),
+        });
+        cell.references.push(key);
+
+        Ok(())
+    }
+
+    fn text(&mut self) -> Result<(), IoError> {
+        let info = self.stream.byte()?;
+        if info & 0x40 != 0 {
+            if info & 0x20 != 0 {
+                self.stream.uint()?;
+            } else {
+                self.stream.string()?;
+            }
+        }
+        if info & 0x01 != 0 {
+            self.stream.uint()?;
+        }
+        if info & 0x02 != 0 {
+            self.stream.uint()?;
+        }
+        self.position(info, 0x10, 0x08, |m| &mut m.text)?;
+        self.repetition(info & 0x04 != 0)?;
+        Ok(())
+    }
+
+    fn property(&mut self) -> Result<(), IoError> {
+        let info = self.stream.byte()?;
+        if info & 0x04 != 0 {
+            if info & 0x02 != 0 {
+                self.stream.uint()?;
+            } else {
+                self.stream.string()?;
+            }
+        }
+        if info & 0x08 != 0 {
+            // the values of the previous property are reused
+            return Ok(());
+        }
+
+        let mut count = (info >> 4) as u64;
+        if count == 15 {
+            count = self.stream.uint()?;
+        }
+
+        for _ in 0..count {
+            let position = self.stream.pos;
+            match self.stream.uint()? {
+                kind @ 0..=7 => {
+                    self.stream.real_body(kind)?;
+                }
+                8 | 9 | 13..=15 => {
+                    self.stream.uint()?;
+                }
+                10..=12 => {
+                    self.stream.string()?;
+                }
+                _ => return Err(IoError::syntax(position, "unknown property value type")),
+            }
+        }
+        Ok(())
+    }
+}
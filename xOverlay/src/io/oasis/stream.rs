@@ -0,0 +1,196 @@
+use crate::io::IoError;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub(super) const MAGIC: &[u8] = b"%SEMI-OASIS\r\n";
+
+pub(super) const PAD: u64 = 0;
+pub(super) const START: u64 = 1;
+pub(super) const END: u64 = 2;
+pub(super) const CELLNAME_IMPLICIT: u64 = 3;
+pub(super) const CELLNAME: u64 = 4;
+pub(super) const CELL_REF: u64 = 13;
+pub(super) const CELL: u64 = 14;
+pub(super) const XY_ABSOLUTE: u64 = 15;
+pub(super) const XY_RELATIVE: u64 = 16;
+pub(super) const PLACEMENT: u64 = 17;
+pub(super) const PLACEMENT_MAG: u64 = 18;
+pub(super) const TEXT: u64 = 19;
+pub(super) const RECTANGLE: u64 = 20;
+pub(super) const POLYGON: u64 = 21;
+pub(super) const PROPERTY: u64 = 28;
+pub(super) const PROPERTY_REPEAT: u64 = 29;
+
+/// OASIS primitives: unsigned and signed varints, reals, strings and deltas.
+pub(super) struct StreamReader<'a> {
+    bytes: &'a [u8],
+    pub(super) pos: usize,
+}
+
+impl<'a> StreamReader<'a> {
+    #[inline]
+    pub(super) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    #[inline]
+    pub(super) fn is_end(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    pub(super) fn byte(&mut self) -> Result<u8, IoError> {
+        let byte = *self
+            .bytes
+            .get(self.pos)
+            .ok_or_else(|| IoError::syntax(self.pos, "unexpected end of the stream"))?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    pub(super) fn bytes(&mut self, count: usize) -> Result<&'a [u8], IoError> {
+        if self.bytes.len() - self.pos < count {
+            return Err(IoError::syntax(self.pos, "unexpected end of the stream"));
+        }
+        let bytes = &self.bytes[self.pos..self.pos + count];
+        self.pos += count;
+        Ok(bytes)
+    }
+
+    pub(super) fn uint(&mut self) -> Result<u64, IoError> {
+        let start = self.pos;
+        let mut value = 0u64;
+        let mut shift = 0;
+        loop {
+            let byte = self.byte()?;
+            if shift > 63 {
+                return Err(IoError::syntax(start, "integer is too big"));
+            }
+            value |= ((byte & 0x7F) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    #[inline]
+    pub(super) fn sint(&mut self) -> Result<i64, IoError> {
+        let value = self.uint()?;
+        let magnitude = (value >> 1) as i64;
+        Ok(if value & 1 == 1 { -magnitude } else { magnitude })
+    }
+
+    pub(super) fn real(&mut self) -> Result<f64, IoError> {
+        let kind = self.uint()?;
+        self.real_body(kind)
+    }
+
+    pub(super) fn real_body(&mut self, kind: u64) -> Result<f64, IoError> {
+        let start = self.pos;
+        let value = match kind {
+            0 => self.uint()? as f64,
+            1 => -(self.uint()? as f64),
+            2 => 1.0 / self.uint()? as f64,
+            3 => -1.0 / self.uint()? as f64,
+            4 => self.uint()? as f64 / self.uint()? as f64,
+            5 => -(self.uint()? as f64) / self.uint()? as f64,
+            6 => {
+                let mut buf = [0; 4];
+                buf.copy_from_slice(self.bytes(4)?);
+                f32::from_le_bytes(buf) as f64
+            }
+            7 => {
+                let mut buf = [0; 8];
+                buf.copy_from_slice(self.bytes(8)?);
+                f64::from_le_bytes(buf)
+            }
+            _ => return Err(IoError::syntax(start, "unknown real type")),
+        };
+        Ok(value)
+    }
+
+    pub(super) fn string(&mut self) -> Result<String, IoError> {
+        let len = self.uint()? as usize;
+        let bytes = self.bytes(len)?;
+        Ok(String::from_utf8_lossy(bytes).into())
+    }
+
+    /// A 2-delta is a vertical or a horizontal displacement.
+    pub(super) fn delta_2(&mut self) -> Result<[i64; 2], IoError> {
+        let value = self.uint()?;
+        let m = (value >> 2) as i64;
+        Ok(match value & 3 {
+            0 => [m, 0],
+            1 => [0, m],
+            2 => [-m, 0],
+            _ => [0, -m],
+        })
+    }
+
+    /// A 3-delta is a displacement in one of the 8 directions.
+    pub(super) fn delta_3(&mut self) -> Result<[i64; 2], IoError> {
+        let value = self.uint()?;
+        Ok(octangular(value & 7, (value >> 3) as i64))
+    }
+
+    /// A g-delta is either an octangular displacement or an arbitrary pair.
+    pub(super) fn delta_g(&mut self) -> Result<[i64; 2], IoError> {
+        let value = self.uint()?;
+        if value & 1 == 0 {
+            return Ok(octangular((value >> 1) & 7, (value >> 4) as i64));
+        }
+        let x = (value >> 2) as i64;
+        let x = if value & 2 != 0 { -x } else { x };
+        let y = self.sint()?;
+        Ok([x, y])
+    }
+}
+
+#[inline]
+fn octangular(direction: u64, m: i64) -> [i64; 2] {
+    match direction {
+        0 => [m, 0],
+        1 => [0, m],
+        2 => [-m, 0],
+        3 => [0, -m],
+        4 => [m, m],
+        5 => [-m, m],
+        6 => [-m, -m],
+        _ => [m, -m],
+    }
+}
+
+pub(super) struct StreamWriter {
+    pub(super) bytes: Vec<u8>,
+}
+
+impl StreamWriter {
+    pub(super) fn uint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.bytes.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.bytes.push(value as u8);
+    }
+
+    #[inline]
+    pub(super) fn sint(&mut self, value: i64) {
+        let sign = if value < 0 { 1 } else { 0 };
+        self.uint((value.unsigned_abs() << 1) | sign);
+    }
+
+    pub(super) fn real(&mut self, value: f64) {
+        if value >= 0.0 && value == (value as u64) as f64 {
+            self.uint(0);
+            self.uint(value as u64);
+        } else {
+            self.uint(7);
+            self.bytes.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    pub(super) fn string(&mut self, value: &[u8]) {
+        self.uint(value.len() as u64);
+        self.bytes.extend_from_slice(value);
+    }
+}
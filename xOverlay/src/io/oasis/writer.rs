@@ -0,0 +1,112 @@
+use crate::io::oasis::stream::*;
+use crate::io::oasis::{OasisLayer, OasisOptions};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use i_float::int::rect::IntRect;
+
+// the END record is padded to this size
+const END_SIZE: usize = 256;
+
+pub(super) fn write(layers: &BTreeMap<OasisLayer, Vec<IntRect>>, options: &OasisOptions) -> Vec<u8> {
+    let mut writer = StreamWriter { bytes: Vec::new() };
+    writer.bytes.extend_from_slice(MAGIC);
+
+    writer.uint(START);
+    writer.string(b"1.0");
+    writer.real(options.unit);
+    // the name tables are empty, their offsets are kept in START
+    writer.uint(0);
+    for _ in 0..12 {
+        writer.uint(0);
+    }
+
+    writer.uint(CELL);
+    writer.string(options.cell.as_bytes());
+
+    let mut modal = RectModal::default();
+    for (layer, rects) in layers.iter() {
+        for rect in rects.iter() {
+            modal.write(&mut writer, *layer, rect);
+        }
+    }
+
+    writer.uint(END);
+    // a record id, a padding string and a validation scheme
+    let padding = END_SIZE - 4;
+    writer.uint(padding as u64);
+    writer.bytes.resize(writer.bytes.len() + padding, 0);
+    writer.uint(0);
+
+    writer.bytes
+}
+
+/// Modal variables of the writer, a field is written only when its value changes.
+#[derive(Default)]
+struct RectModal {
+    layer: Option<OasisLayer>,
+    width: Option<i64>,
+    height: Option<i64>,
+    x: Option<i64>,
+    y: Option<i64>,
+}
+
+impl RectModal {
+    fn write(&mut self, writer: &mut StreamWriter, layer: OasisLayer, rect: &IntRect) {
+        let width = rect.max_x as i64 - rect.min_x as i64;
+        let height = rect.max_y as i64 - rect.min_y as i64;
+        let (x, y) = (rect.min_x as i64, rect.min_y as i64);
+
+        let mut info = 0u8;
+        let layer_changed = self.layer.map(|l| l.layer) != Some(layer.layer);
+        let datatype_changed = self.layer.map(|l| l.datatype) != Some(layer.datatype);
+        if layer_changed {
+            info |= 0x01;
+        }
+        if datatype_changed {
+            info |= 0x02;
+        }
+        let is_square = width == height;
+        if is_square {
+            info |= 0x80;
+        }
+        if self.width != Some(width) {
+            info |= 0x40;
+        }
+        if !is_square && self.height != Some(height) {
+            info |= 0x20;
+        }
+        if self.x != Some(x) {
+            info |= 0x10;
+        }
+        if self.y != Some(y) {
+            info |= 0x08;
+        }
+
+        writer.uint(RECTANGLE);
+        writer.bytes.push(info);
+        if layer_changed {
+            writer.uint(layer.layer as u64);
+        }
+        if datatype_changed {
+            writer.uint(layer.datatype as u64);
+        }
+        if info & 0x40 != 0 {
+            writer.uint(width as u64);
+        }
+        if info & 0x20 != 0 {
+            writer.uint(height as u64);
+        }
+        if info & 0x10 != 0 {
+            writer.sint(x);
+        }
+        if info & 0x08 != 0 {
+            writer.sint(y);
+        }
+
+        self.layer = Some(layer);
+        self.width = Some(width);
+        self.height = Some(height);
+        self.x = Some(x);
+        self.y = Some(y);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::core::direction::ContourDirection;
+use crate::io::{IoError, IoOptions};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use i_float::int::point::IntPoint;
+use i_shape::int::area::Area;
+use i_shape::int::shape::{IntContour, IntShape, IntShapes};
+
+/// Polygons as they are read from a file: rings of points, the first ring is the outer one.
+pub(super) type RawPolygon = Vec<Vec<[f64; 2]>>;
+
+pub(super) fn to_shapes(polygons: Vec<RawPolygon>, options: &IoOptions) -> Result<IntShapes, IoError> {
+    let mut shapes = Vec::with_capacity(polygons.len());
+    for (polygon_index, polygon) in polygons.into_iter().enumerate() {
+        let mut shape = Vec::with_capacity(polygon.len());
+        for (ring_index, ring) in polygon.iter().enumerate() {
+            let mut contour = to_contour(ring, options.scale, polygon_index, ring_index)?;
+            // outer contour goes in the given direction and holes go in the opposite one
+            if (ring_index == 0) != is_direct(&contour, options.direction) {
+                contour[1..].reverse();
+            }
+            shape.push(contour);
+        }
+        if !shape.is_empty() {
+            shapes.push(shape);
+        }
+    }
+    Ok(shapes)
+}
+
+fn to_contour(ring: &[[f64; 2]], scale: f64, polygon: usize, ring_index: usize) -> Result<IntContour, IoError> {
+    let mut contour: IntContour = Vec::with_capacity(ring.len());
+    for (point, p) in ring.iter().enumerate() {
+        let x = to_int(p[0] * scale);
+        let y = to_int(p[1] * scale);
+        let (x, y) = match (x, y) {
+            (Some(x), Some(y)) => (x, y),
+            _ => {
+                return Err(IoError::OutOfRange {
+                    polygon,
+                    ring: ring_index,
+                    point,
+                });
+            }
+        };
+        let p = IntPoint::new(x, y);
+        if let Some(&last) = contour.last() {
+            if last == p {
+                continue;
+            }
+            if last.x != p.x && last.y != p.y {
+                return Err(IoError::NotRectilinear {
+                    polygon,
+                    ring: ring_index,
+                    point,
+                });
+            }
+        }
+        contour.push(p);
+    }
+
+    // a ring is closed in the file
+    if contour.len() > 1 && contour[0] == contour[contour.len() - 1] {
+        contour.pop();
+    }
+
+    if contour.len() < 4 {
+        return Err(IoError::NotValidRing {
+            polygon,
+            ring: ring_index,
+        });
+    }
+
+    let (first, last) = (contour[0], contour[contour.len() - 1]);
+    if first.x != last.x && first.y != last.y {
+        return Err(IoError::NotRectilinear {
+            polygon,
+            ring: ring_index,
+            point: 0,
+        });
+    }
+
+    Ok(contour)
+}
+
+#[inline]
+fn to_int(value: f64) -> Option<i32> {
+    // no_std has no `f64::round`
+    let rounded = if value < 0.0 { value - 0.5 } else { value + 0.5 };
+    if value.is_finite() && i32::MIN as f64 <= rounded && rounded < i32::MAX as f64 + 1.0 {
+        Some(rounded as i32)
+    } else {
+        None
+    }
+}
+
+#[inline]
+fn is_direct(contour: &[IntPoint], direction: ContourDirection) -> bool {
+    // counterclockwise contours have a negative area
+    let area = contour.area_two();
+    match direction {
+        ContourDirection::CounterClockwise => area <= 0,
+        ContourDirection::Clockwise => area >= 0,
+    }
+}
+
+/// Rings of a shape in the given direction, every ring is closed.
+pub(super) fn closed_rings(shape: &IntShape, direction: ContourDirection) -> impl Iterator<Item = Vec<IntPoint>> + '_ {
+    shape.iter().enumerate().map(move |(i, contour)| {
+        let mut ring = Vec::with_capacity(contour.len() + 1);
+        if (i == 0) == is_direct(contour, direction) {
+            ring.extend_from_slice(contour);
+        } else {
+            ring.extend(contour.first());
+            ring.extend(contour[1..].iter().rev());
+        }
+        if let Some(&first) = ring.first() {
+            ring.push(first);
+        }
+        ring
+    })
+}
+
+#[inline]
+pub(super) fn write_coord(out: &mut String, value: i32, scale: f64) {
+    if scale == 1.0 {
+        _ = write!(out, "{}", value);
+    } else {
+        _ = write!(out, "{}", value as f64 / scale);
+    }
+}
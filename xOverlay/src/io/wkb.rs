@@ -0,0 +1,202 @@
+//! Well-known binary, `Polygon` and `MultiPolygon` in ISO and extended (PostGIS) flavors.
+
+use crate::io::ring::{RawPolygon, closed_rings, to_shapes};
+use crate::io::{IoError, IoOptions};
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use i_shape::int::shape::{IntShape, IntShapes};
+
+const POLYGON: u32 = 3;
+const MULTI_POLYGON: u32 = 6;
+
+const EWKB_Z: u32 = 0x8000_0000;
+const EWKB_M: u32 = 0x4000_0000;
+const EWKB_SRID: u32 = 0x2000_0000;
+
+/// Reads a `Polygon` or a `MultiPolygon`, Z and M values are ignored.
+/// - `bytes`: WKB data in any byte order.
+/// - `options`: Scale and direction of the result.
+/// - Returns: Shapes, or an error if the data is malformed or a ring is not rectilinear.
+pub fn read(bytes: &[u8], options: &IoOptions) -> Result<IntShapes, IoError> {
+    let mut reader = Reader { bytes, pos: 0, little: true };
+    let (geometry, dims) = reader.header()?;
+    let polygons = match geometry {
+        POLYGON => vec![reader.polygon(dims)?],
+        MULTI_POLYGON => {
+            let count = reader.count()?;
+            let mut polygons = Vec::with_capacity(count);
+            for _ in 0..count {
+                let (geometry, dims) = reader.header()?;
+                if geometry != POLYGON {
+                    return Err(IoError::syntax(reader.pos, "a polygon is expected"));
+                }
+                polygons.push(reader.polygon(dims)?);
+            }
+            polygons
+        }
+        _ => return Err(IoError::UnsupportedGeometry(format!("WKB type {}", geometry))),
+    };
+
+    to_shapes(polygons, options)
+}
+
+/// Writes shapes as a little endian 2D `MultiPolygon`, rings are closed.
+/// - `shapes`: Shapes, e.g. a result of `extract_shapes`.
+/// - `options`: Scale and direction of the rings.
+pub fn write(shapes: &[IntShape], options: &IoOptions) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.push(1);
+    out.extend_from_slice(&MULTI_POLYGON.to_le_bytes());
+    out.extend_from_slice(&(shapes.len() as u32).to_le_bytes());
+    for shape in shapes.iter() {
+        out.push(1);
+        out.extend_from_slice(&POLYGON.to_le_bytes());
+        out.extend_from_slice(&(shape.len() as u32).to_le_bytes());
+        for ring in closed_rings(shape, options.direction) {
+            out.extend_from_slice(&(ring.len() as u32).to_le_bytes());
+            for p in ring.iter() {
+                out.extend_from_slice(&(p.x as f64 / options.scale).to_le_bytes());
+                out.extend_from_slice(&(p.y as f64 / options.scale).to_le_bytes());
+            }
+        }
+    }
+    out
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    little: bool,
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], IoError> {
+        let end = self.pos + N;
+        if end > self.bytes.len() {
+            return Err(IoError::syntax(self.pos, "unexpected end of data"));
+        }
+        let mut buf = [0; N];
+        buf.copy_from_slice(&self.bytes[self.pos..end]);
+        self.pos = end;
+        Ok(buf)
+    }
+
+    #[inline]
+    fn u32(&mut self) -> Result<u32, IoError> {
+        let buf = self.take::<4>()?;
+        Ok(if self.little { u32::from_le_bytes(buf) } else { u32::from_be_bytes(buf) })
+    }
+
+    #[inline]
+    fn f64(&mut self) -> Result<f64, IoError> {
+        let buf = self.take::<8>()?;
+        Ok(if self.little { f64::from_le_bytes(buf) } else { f64::from_be_bytes(buf) })
+    }
+
+    #[inline]
+    fn count(&mut self) -> Result<usize, IoError> {
+        let count = self.u32()? as usize;
+        // every item takes at least 4 bytes, it protects from allocating a huge buffer
+        if count > (self.bytes.len() - self.pos) / 4 {
+            return Err(IoError::syntax(self.pos, "count is too large"));
+        }
+        Ok(count)
+    }
+
+    /// Reads the byte order and the geometry type.
+    /// - Returns: The base geometry type and the count of values per point.
+    fn header(&mut self) -> Result<(u32, usize), IoError> {
+        let [order] = self.take::<1>()?;
+        self.little = match order {
+            0 => false,
+            1 => true,
+            _ => return Err(IoError::syntax(self.pos - 1, "byte order must be 0 or 1")),
+        };
+
+        let code = self.u32()?;
+        let mut dims = 2 + (code & EWKB_Z != 0) as usize + (code & EWKB_M != 0) as usize;
+        if code & EWKB_SRID != 0 {
+            self.u32()?;
+        }
+
+        // ISO codes: 1000 is Z, 2000 is M, 3000 is ZM
+        let iso = code & 0x0FFF_FFFF;
+        dims += match iso / 1000 {
+            1 | 2 => 1,
+            3 => 2,
+            _ => 0,
+        };
+
+        Ok((iso % 1000, dims))
+    }
+
+    fn polygon(&mut self, dims: usize) -> Result<RawPolygon, IoError> {
+        let rings_count = self.count()?;
+        let mut polygon = Vec::with_capacity(rings_count);
+        for _ in 0..rings_count {
+            let count = self.count()?;
+            let mut ring = Vec::with_capacity(count);
+            for _ in 0..count {
+                let x = self.f64()?;
+                let y = self.f64()?;
+                for _ in 2..dims {
+                    self.f64()?;
+                }
+                ring.push([x, y]);
+            }
+            polygon.push(ring);
+        }
+        Ok(polygon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::wkb::{read, write};
+    use crate::io::{IoError, IoOptions};
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_float::int::point::IntPoint;
+
+    fn polygon_z_be(ring: &[[f64; 2]]) -> Vec<u8> {
+        let mut out = vec![0];
+        out.extend_from_slice(&1003u32.to_be_bytes());
+        out.extend_from_slice(&1u32.to_be_bytes());
+        out.extend_from_slice(&(ring.len() as u32).to_be_bytes());
+        for p in ring.iter() {
+            out.extend_from_slice(&p[0].to_be_bytes());
+            out.extend_from_slice(&p[1].to_be_bytes());
+            out.extend_from_slice(&7.0f64.to_be_bytes());
+        }
+        out
+    }
+
+    #[test]
+    fn test_0() {
+        let ring = [[0.0, 0.0], [4.0, 0.0], [4.0, 2.0], [0.0, 2.0], [0.0, 0.0]];
+        let shapes = read(&polygon_z_be(&ring), &IoOptions::default()).expect("OK");
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(4, 0),
+            IntPoint::new(4, 2),
+            IntPoint::new(0, 2),
+        ];
+        assert_eq!(shapes, vec![vec![contour]]);
+
+        let bytes = write(&shapes, &IoOptions::default());
+        assert_eq!(bytes.len(), 1 + 4 + 4 + 1 + 4 + 4 + 4 + 5 * 16);
+        assert_eq!(read(&bytes, &IoOptions::default()).expect("OK"), shapes);
+
+        assert!(matches!(
+            read(&bytes[..bytes.len() - 1], &IoOptions::default()),
+            Err(IoError::Syntax { .. })
+        ));
+
+        let ring = [[0.0, 0.0], [4.0, 1.0], [4.0, 2.0], [0.0, 2.0], [0.0, 0.0]];
+        assert_eq!(
+            read(&polygon_z_be(&ring), &IoOptions::default()),
+            Err(IoError::NotRectilinear { polygon: 0, ring: 0, point: 1 })
+        );
+    }
+}
@@ -0,0 +1,273 @@
+//! Well-known text, `POLYGON` and `MULTIPOLYGON`.
+
+use crate::io::ring::{RawPolygon, closed_rings, to_shapes, write_coord};
+use crate::io::{IoError, IoOptions};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use i_shape::int::shape::{IntShape, IntShapes};
+
+/// Reads a `POLYGON` or a `MULTIPOLYGON`, Z and M values are ignored.
+/// - `text`: WKT text.
+/// - `options`: Scale and direction of the result.
+/// - Returns: Shapes, or an error if the text is malformed or a ring is not rectilinear.
+pub fn read(text: &str, options: &IoOptions) -> Result<IntShapes, IoError> {
+    let mut parser = Parser { text: text.as_bytes(), pos: 0 };
+
+    let name = parser.word()?;
+    let polygons = match name.as_str() {
+        "POLYGON" => {
+            parser.skip_dimension()?;
+            if parser.is_empty()? {
+                vec![]
+            } else {
+                vec![parser.polygon()?]
+            }
+        }
+        "MULTIPOLYGON" => {
+            parser.skip_dimension()?;
+            if parser.is_empty()? {
+                vec![]
+            } else {
+                parser.list(|p| p.polygon())?
+            }
+        }
+        _ => return Err(IoError::UnsupportedGeometry(name)),
+    };
+
+    parser.skip_spaces();
+    if parser.pos < parser.text.len() {
+        return Err(IoError::syntax(parser.pos, "unexpected text after the geometry"));
+    }
+
+    to_shapes(polygons, options)
+}
+
+/// Writes shapes as a `MULTIPOLYGON`, rings are closed.
+/// - `shapes`: Shapes, e.g. a result of `extract_shapes`.
+/// - `options`: Scale and direction of the rings.
+pub fn write(shapes: &[IntShape], options: &IoOptions) -> String {
+    let mut out = String::from("MULTIPOLYGON");
+    if shapes.is_empty() {
+        out.push_str(" EMPTY");
+        return out;
+    }
+
+    out.push_str(" (");
+    for (i, shape) in shapes.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        out.push('(');
+        for (j, ring) in closed_rings(shape, options.direction).enumerate() {
+            if j > 0 {
+                out.push_str(", ");
+            }
+            out.push('(');
+            for (k, p) in ring.iter().enumerate() {
+                if k > 0 {
+                    out.push_str(", ");
+                }
+                write_coord(&mut out, p.x, options.scale);
+                out.push(' ');
+                write_coord(&mut out, p.y, options.scale);
+            }
+            out.push(')');
+        }
+        out.push(')');
+    }
+    out.push(')');
+
+    out
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    #[inline]
+    fn skip_spaces(&mut self) {
+        while self.pos < self.text.len() && self.text[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    #[inline]
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_spaces();
+        self.text.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, ch: u8) -> Result<(), IoError> {
+        if self.peek() == Some(ch) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            let message = match ch {
+                b'(' => "'(' is expected",
+                b')' => "')' is expected",
+                _ => "',' is expected",
+            };
+            Err(IoError::syntax(self.pos, message))
+        }
+    }
+
+    fn word(&mut self) -> Result<String, IoError> {
+        self.skip_spaces();
+        let start = self.pos;
+        while self.pos < self.text.len() && self.text[self.pos].is_ascii_alphabetic() {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Err(IoError::syntax(start, "a geometry name is expected"));
+        }
+        let word = core::str::from_utf8(&self.text[start..self.pos]).unwrap_or_default();
+        Ok(word.to_ascii_uppercase())
+    }
+
+    fn skip_dimension(&mut self) -> Result<(), IoError> {
+        if self.peek().is_some_and(|ch| ch.is_ascii_alphabetic()) {
+            let start = self.pos;
+            let word = self.word()?;
+            match word.as_str() {
+                "Z" | "M" | "ZM" => {}
+                // not a dimension, it's read again by `is_empty`
+                _ => self.pos = start,
+            }
+        }
+        Ok(())
+    }
+
+    fn is_empty(&mut self) -> Result<bool, IoError> {
+        if self.peek().is_some_and(|ch| ch.is_ascii_alphabetic()) {
+            let start = self.pos;
+            if self.word()? == "EMPTY" {
+                return Ok(true);
+            }
+            return Err(IoError::syntax(start, "'EMPTY' or '(' is expected"));
+        }
+        Ok(false)
+    }
+
+    fn list<T, F: FnMut(&mut Self) -> Result<T, IoError>>(&mut self, mut item: F) -> Result<Vec<T>, IoError> {
+        self.expect(b'(')?;
+        let mut items = vec![item(self)?];
+        while self.peek() == Some(b',') {
+            self.pos += 1;
+            items.push(item(self)?);
+        }
+        self.expect(b')')?;
+        Ok(items)
+    }
+
+    #[inline]
+    fn polygon(&mut self) -> Result<RawPolygon, IoError> {
+        self.list(|p| p.list(|p| p.point()))
+    }
+
+    fn point(&mut self) -> Result<[f64; 2], IoError> {
+        let x = self.number()?;
+        let y = self.number()?;
+        // skip Z and M
+        while self.peek().is_some_and(|ch| ch != b',' && ch != b')') {
+            self.number()?;
+        }
+        Ok([x, y])
+    }
+
+    fn number(&mut self) -> Result<f64, IoError> {
+        self.skip_spaces();
+        let start = self.pos;
+        while self.pos < self.text.len() {
+            let ch = self.text[self.pos];
+            if ch.is_ascii_digit() || matches!(ch, b'-' | b'+' | b'.' | b'e' | b'E') {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        core::str::from_utf8(&self.text[start..self.pos])
+            .ok()
+            .and_then(|s| s.parse::<f64>().ok())
+            .ok_or_else(|| IoError::syntax(start, "a number is expected"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::direction::ContourDirection;
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::io::wkt::{read, write};
+    use crate::io::{IoError, IoOptions};
+    use crate::ortho::overlay::OrthoOverlay;
+    use alloc::vec;
+    use i_float::int::point::IntPoint;
+    use i_shape::int::area::Area;
+
+    #[test]
+    fn test_0() {
+        // clockwise outer ring and counterclockwise hole
+        let text = "POLYGON ((0 0, 0 10, 10 10, 10 0, 0 0), (2 2, 8 2, 8 8, 2 8, 2 2))";
+        let shapes = read(text, &IoOptions::default()).expect("OK");
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 2);
+        assert_eq!(shapes[0][0].area(), -100);
+        assert_eq!(shapes[0][1].area(), 36);
+
+        let options = IoOptions {
+            scale: 10.0,
+            direction: ContourDirection::Clockwise,
+        };
+        let shapes = read("multipolygon z (((0 0 1, 0.5 0 1, 0.5 0.5 1, 0 0.5 1, 0 0 1)))", &options).expect("OK");
+        assert_eq!(
+            shapes,
+            vec![vec![vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(0, 5),
+                IntPoint::new(5, 5),
+                IntPoint::new(5, 0),
+            ]]]
+        );
+
+        assert!(read("POLYGON EMPTY", &IoOptions::default()).expect("OK").is_empty());
+        assert_eq!(
+            read("POLYGON ((0 0, 10 0, 10 10, 0 0))", &IoOptions::default()),
+            Err(IoError::NotRectilinear { polygon: 0, ring: 0, point: 3 })
+        );
+        assert!(matches!(
+            read("POINT (0 0)", &IoOptions::default()),
+            Err(IoError::UnsupportedGeometry(_))
+        ));
+        assert!(matches!(
+            read("POLYGON ((0 0, 10 0, 10 10", &IoOptions::default()),
+            Err(IoError::Syntax { .. })
+        ));
+    }
+
+    #[test]
+    fn test_1() {
+        let text = "MULTIPOLYGON (((0 0, 10 0, 10 10, 0 10, 0 0)), ((5 5, 15 5, 15 15, 5 15, 5 5)))";
+        let options = IoOptions {
+            scale: 2.0,
+            ..Default::default()
+        };
+        let input = read(text, &options).expect("OK");
+        let subj: vec::Vec<_> = input.into_iter().flatten().collect();
+
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
+        let shapes = overlay.overlay(OverlayRule::Subject, FillRule::NonZero);
+
+        let text = write(&shapes, &options);
+        assert_eq!(
+            text,
+            "MULTIPOLYGON (((0 10, 0 0, 10 0, 10 5, 15 5, 15 15, 5 15, 5 10, 0 10)))"
+        );
+        assert_eq!(read(&text, &options).expect("OK"), shapes);
+        assert_eq!(write(&[], &options), "MULTIPOLYGON EMPTY");
+    }
+}
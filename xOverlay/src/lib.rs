@@ -1,16 +1,32 @@
 //! # xOverlay
 //!
 //! The `xOverlay` provides Boolean Operations for 45 degrees geometry.
+//!
+//! ## Features
+//! - `multithreading` (default): Processes columns in parallel with `rayon`, requires `std`.
+//! - `serde`: Serialization of the public types.
+//! - `io`: Import and export of ortho shapes.
+//! - `stats`: Statistics of the last run with the durations of its phases, requires `std`
+//!   for `std::time::Instant`.
+//! - `provenance`: Tracks the input contours of every output edge.
+//!
+//! Without `multithreading` and `stats` the crate is `no_std` and needs only `alloc`.
 
 #![cfg_attr(not(test), no_std)]
 extern crate alloc;
+#[cfg(all(feature = "stats", not(test)))]
+extern crate std;
 
 pub mod core;
 pub(crate) mod graph;
 pub mod ortho;
+#[cfg(feature = "io")]
+pub mod io;
 mod sub;
 mod geom;
 pub(crate) mod bind;
+#[cfg(test)]
+mod test_util;
 
 pub use i_float;
 pub use i_shape;
\ No newline at end of file
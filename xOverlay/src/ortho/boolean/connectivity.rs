@@ -0,0 +1,303 @@
+use crate::bind::locate::ShapeLocator;
+use crate::core::direction::ContourDirection;
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay_rule::OverlayRule;
+use crate::geom::v_segment::VSegment;
+use crate::graph::boolean::winding_count::ShapeCountBoolean;
+use crate::ortho::connectivity::{ConnectedComponent, LayerContour, LayerShape, Net, ViaLayer};
+use crate::ortho::error::OrthoError;
+use crate::ortho::overlay::OrthoOverlay;
+use crate::sub::disjoint_set::DisjointSet;
+use alloc::vec;
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+use i_shape::int::path::ContourExtension;
+use i_shape::int::shape::IntContour;
+
+impl OrthoOverlay<ShapeCountBoolean> {
+    /// Groups contours which overlap or touch each other along an edge, and merges every group into a single shape.
+    ///
+    /// Every contour is treated as a solid region regardless of its direction, contours which
+    /// share only a corner point are not connected.
+    /// - `contours`: The contours to group.
+    /// - Returns: Connected components with the indices of their contours. Contours with zero area do not belong to any component.
+    pub fn connected_components(&mut self, contours: &[IntContour]) -> Result<Vec<ConnectedComponent>, OrthoError> {
+        let mut probes = Vec::with_capacity(contours.len());
+        let mut probe_contours = Vec::with_capacity(contours.len());
+        for (i, contour) in contours.iter().enumerate() {
+            if let Some(probe) = contour.probe() {
+                probes.push(probe);
+                probe_contours.push(i);
+            }
+        }
+
+        if probes.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.init_with_solid_contours(contours)?;
+        self.build_custom_graph(FillRule::NonZero, OverlayRule::Subject)?;
+        let shapes = if let Some(graph) = &mut self.graph {
+            graph.extract_shapes(OverlayRule::Subject)
+        } else {
+            return Ok(Vec::new());
+        };
+
+        let clockwise = self.options.output_direction == ContourDirection::Clockwise;
+        let parents = shapes.locate(&probes, clockwise);
+
+        let mut components: Vec<_> = shapes
+            .into_iter()
+            .map(|shape| ConnectedComponent { contours: Vec::new(), shape })
+            .collect();
+
+        for (&contour_index, &shape_index) in probe_contours.iter().zip(parents.iter()) {
+            components[shape_index].contours.push(contour_index);
+        }
+
+        Ok(components)
+    }
+
+    /// Finds electrically connected nets across conductor layers which are joined by via layers.
+    ///
+    /// Contours of a conductor layer are connected if they overlap or touch along an edge, see [`Self::connected_components`].
+    /// A via connects every conductor shape it overlaps or touches on its bottom and top layers.
+    /// - `layers`: Contours of the conductor layers.
+    /// - `vias`: Via layers, which reference the conductor layers by index.
+    /// - Returns: The nets with their conductor contours, via contours and merged conductor shapes.
+    pub fn connected_nets(&mut self, layers: &[&[IntContour]], vias: &[ViaLayer]) -> Result<Vec<Net>, OrthoError> {
+        // every conductor component and every via contour is a node
+        let mut layer_components = Vec::with_capacity(layers.len());
+        let mut layer_offsets = Vec::with_capacity(layers.len());
+        let mut nodes_count = 0;
+        for &contours in layers.iter() {
+            let components = self.connected_components(contours)?;
+            layer_offsets.push(nodes_count);
+            nodes_count += components.len();
+            layer_components.push(components);
+        }
+
+        let mut via_offsets = Vec::with_capacity(vias.len());
+        for via in vias.iter() {
+            via_offsets.push(nodes_count);
+            nodes_count += via.contours.len();
+        }
+
+        let mut set = DisjointSet::new(nodes_count);
+
+        let mut combined = Vec::new();
+        let mut combined_nodes = Vec::new();
+        for (layer, &contours) in layers.iter().enumerate() {
+            let mut contour_nodes = vec![usize::MAX; contours.len()];
+            for (i, component) in layer_components[layer].iter().enumerate() {
+                for &ci in component.contours.iter() {
+                    contour_nodes[ci] = layer_offsets[layer] + i;
+                }
+            }
+
+            combined.clear();
+            combined_nodes.clear();
+            combined.extend_from_slice(contours);
+            combined_nodes.extend_from_slice(&contour_nodes);
+
+            for (via, &offset) in vias.iter().zip(via_offsets.iter()) {
+                if via.bottom == layer || via.top == layer {
+                    combined.extend_from_slice(via.contours);
+                    combined_nodes.extend(offset..offset + via.contours.len());
+                }
+            }
+
+            if combined.len() == contours.len() {
+                continue;
+            }
+
+            for component in self.connected_components(&combined)? {
+                let mut nodes = component.contours.iter().map(|&i| combined_nodes[i]);
+                if let Some(first) = nodes.next() {
+                    for node in nodes {
+                        set.union(first, node);
+                    }
+                }
+            }
+        }
+
+        let mut net_for_root = vec![usize::MAX; nodes_count];
+        let mut nets: Vec<Net> = Vec::new();
+
+        for (layer, components) in layer_components.into_iter().enumerate() {
+            for (i, component) in components.into_iter().enumerate() {
+                let net = net_for_root.net_mut(&mut nets, set.root(layer_offsets[layer] + i));
+                net.contours
+                    .extend(component.contours.iter().map(|&index| LayerContour { layer, index }));
+                net.shapes.push(LayerShape { layer, shape: component.shape });
+            }
+        }
+
+        for (via_layer, (via, &offset)) in vias.iter().zip(via_offsets.iter()).enumerate() {
+            for index in 0..via.contours.len() {
+                if via.contours[index].probe().is_none() {
+                    continue;
+                }
+                let net = net_for_root.net_mut(&mut nets, set.root(offset + index));
+                net.vias.push(LayerContour { layer: via_layer, index });
+            }
+        }
+
+        for net in nets.iter_mut() {
+            net.contours.sort_unstable();
+        }
+
+        Ok(nets)
+    }
+}
+
+trait NetMap {
+    fn net_mut<'a>(&mut self, nets: &'a mut Vec<Net>, root: usize) -> &'a mut Net;
+}
+
+impl NetMap for [usize] {
+    #[inline]
+    fn net_mut<'a>(&mut self, nets: &'a mut Vec<Net>, root: usize) -> &'a mut Net {
+        if self[root] == usize::MAX {
+            self[root] = nets.len();
+            nets.push(Net::default());
+        }
+        &mut nets[self[root]]
+    }
+}
+
+trait Probe {
+    fn probe(&self) -> Option<VSegment>;
+}
+
+impl Probe for IntContour {
+    /// A short diagonal from the left-bottom corner into the contour.
+    #[inline]
+    fn probe(&self) -> Option<VSegment> {
+        if self.len() < 4 || self.unsafe_area() == 0 {
+            return None;
+        }
+        let a = *self.iter().min()?;
+
+        Some(VSegment {
+            a,
+            b: IntPoint::new(a.x + 1, a.y + 1),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::connectivity::{LayerContour, ViaLayer};
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::test_util::rect;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_shape::int::area::Area;
+    use i_shape::int::shape::IntContour;
+
+    #[test]
+    fn test_0() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+
+        let contours = [
+            rect(0, 0, 10, 10),
+            rect(20, 0, 30, 10),
+            rect(5, 5, 15, 15),
+            // clockwise, touches the second one
+            rect(30, 0, 40, 10).into_iter().rev().collect(),
+            rect(100, 100, 110, 110),
+            // touches the fourth one by a corner only
+            rect(40, 10, 50, 20),
+        ];
+
+        let mut components = overlay.connected_components(&contours).expect("OK");
+        components.sort_by_key(|c| c.contours[0]);
+
+        let groups: Vec<_> = components.iter().map(|c| c.contours.clone()).collect();
+        assert_eq!(groups, vec![vec![0, 2], vec![1, 3], vec![4], vec![5]]);
+
+        let areas: Vec<_> = components.iter().map(|c| -c.shape.area()).collect();
+        assert_eq!(areas, vec![175, 200, 100, 100]);
+    }
+
+    #[test]
+    fn test_1() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+
+        // a frame with an island in its hole and a rect inside the island
+        let contours = [
+            rect(0, 0, 30, 30),
+            rect(10, 10, 20, 20).into_iter().rev().collect(),
+            rect(12, 12, 18, 18),
+            rect(14, 14, 16, 16),
+            rect(1, 1, 3, 3),
+        ];
+
+        let mut components = overlay.connected_components(&contours).expect("OK");
+        components.sort_by_key(|c| c.contours[0]);
+
+        // contour 1 is solid as well, so it fills the hole
+        let groups: Vec<_> = components.iter().map(|c| c.contours.clone()).collect();
+        assert_eq!(groups, vec![vec![0, 1, 2, 3, 4]]);
+    }
+
+    #[test]
+    fn test_2() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.options.min_count_per_column_power = 2;
+
+        // frames around islands
+        let mut contours = Vec::new();
+        for i in 0..4 {
+            let x = 40 * i;
+            contours.push(rect(x, 0, x + 30, 5));
+            contours.push(rect(x, 25, x + 30, 30));
+            contours.push(rect(x, 0, x + 5, 30));
+            contours.push(rect(x + 25, 0, x + 30, 30));
+            contours.push(rect(x + 10, 10, x + 20, 20));
+        }
+
+        let mut components = overlay.connected_components(&contours).expect("OK");
+        components.sort_by_key(|c| c.contours[0]);
+
+        let groups: Vec<_> = components.iter().map(|c| c.contours.clone()).collect();
+        let mut template = Vec::new();
+        for i in 0..4 {
+            template.push(vec![5 * i, 5 * i + 1, 5 * i + 2, 5 * i + 3]);
+            template.push(vec![5 * i + 4]);
+        }
+        assert_eq!(groups, template);
+    }
+
+    #[test]
+    fn test_3() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+
+        let metal_1 = [rect(0, 0, 10, 2), rect(20, 0, 30, 2)];
+        let metal_2 = [rect(8, 0, 22, 2), rect(50, 50, 60, 60)];
+        let vias = [rect(8, 0, 10, 2), rect(20, 0, 22, 2), rect(200, 0, 202, 2)];
+
+        let layers: [&[IntContour]; 2] = [&metal_1, &metal_2];
+        let via_layers = [ViaLayer { contours: &vias, bottom: 0, top: 1 }];
+
+        let nets = overlay.connected_nets(&layers, &via_layers).expect("OK");
+
+        assert_eq!(nets.len(), 3);
+        let net = nets.iter().find(|n| n.contours.len() == 3).unwrap();
+        assert_eq!(
+            net.contours,
+            vec![
+                LayerContour { layer: 0, index: 0 },
+                LayerContour { layer: 0, index: 1 },
+                LayerContour { layer: 1, index: 0 },
+            ]
+        );
+        assert_eq!(net.vias.len(), 2);
+        assert_eq!(net.shapes.len(), 3);
+
+        assert!(nets.iter().any(|n| n.contours == vec![LayerContour { layer: 1, index: 1 }] && n.vias.is_empty()));
+        assert!(nets.iter().any(|n| n.contours.is_empty() && n.vias.len() == 1));
+    }
+}
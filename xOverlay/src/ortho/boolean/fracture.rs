@@ -0,0 +1,165 @@
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay_rule::OverlayRule;
+use crate::core::sweep::Transpose;
+use crate::graph::boolean::winding_count::ShapeCountBoolean;
+use crate::ortho::error::OrthoError;
+use crate::ortho::fracture::FractureOptions;
+use crate::ortho::overlay::OrthoOverlay;
+use alloc::vec;
+use alloc::vec::Vec;
+use i_float::int::rect::IntRect;
+
+impl OrthoOverlay<ShapeCountBoolean> {
+    /// Fractures the result of the overlay into non-overlapping rectangles, which is the form expected by mask writers.
+    ///
+    /// Every shape is cut into strips along `options.slicing`, neighbouring strips with the same range are joined,
+    /// and figures larger than the maximum size are split into equal parts.
+    /// - `overlay_rule`: The boolean operation rule to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas.
+    /// - `options`: Fracturing limits and the slicing direction.
+    /// - Returns: A flat list of rectangles, which together cover exactly the result of the overlay.
+    ///
+    /// **A cancelled or overflowed run returns no rectangles**, use [`Self::try_fracture`] to tell it from an empty result.
+    pub fn fracture(&mut self, overlay_rule: OverlayRule, fill_rule: FillRule, options: &FractureOptions) -> Vec<IntRect> {
+        self.try_fracture(overlay_rule, fill_rule, options).unwrap_or_default()
+    }
+
+    /// Fractures the result of the overlay like [`Self::fracture`] and stops as soon as `solver.cancel` is cancelled.
+    /// - Returns: The rectangles, or the error of [`Self::try_overlay`].
+    pub fn try_fracture(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        options: &FractureOptions,
+    ) -> Result<Vec<IntRect>, OrthoError> {
+        self.build_custom_graph(fill_rule, overlay_rule)?;
+        let graph = if let Some(graph) = &mut self.graph {
+            graph
+        } else {
+            return Ok(vec![]);
+        };
+        if !self.transposed {
+            return Ok(graph.fracture(overlay_rule, options));
+        }
+
+        let options = FractureOptions {
+            slicing: options.slicing.invert(),
+            max_width: options.max_height,
+            max_height: options.max_width,
+            sliver_size: options.sliver_size,
+        };
+        let mut rects = graph.fracture(overlay_rule, &options);
+        for rect in rects.iter_mut() {
+            rect.transpose();
+        }
+
+        Ok(rects)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::fracture::{FractureOptions, Slicing};
+    use crate::ortho::overlay::OrthoOverlay;
+    use alloc::vec;
+    use i_float::int::point::IntPoint;
+    use i_float::int::rect::IntRect;
+
+    fn area(rects: &[IntRect]) -> i64 {
+        rects.iter().map(|r| r.width() as i64 * r.height() as i64).sum()
+    }
+
+    #[test]
+    fn test_0() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+
+        // L shape
+        let subj = [vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 2),
+            IntPoint::new(2, 2),
+            IntPoint::new(2, 10),
+            IntPoint::new(0, 10),
+        ]];
+
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
+        let rects = overlay.fracture(OverlayRule::Subject, FillRule::NonZero, &FractureOptions::default());
+
+        assert_eq!(rects.len(), 2);
+        assert_eq!(area(&rects), 36);
+        assert!(rects.iter().all(|r| r.width() == 10 || r.height() == 8));
+
+        let options = FractureOptions { slicing: Slicing::Vertical, ..Default::default() };
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
+        let rects = overlay.fracture(OverlayRule::Subject, FillRule::NonZero, &options);
+
+        assert_eq!(rects.len(), 2);
+        assert_eq!(area(&rects), 36);
+        assert!(rects.iter().all(|r| r.height() == 10 || r.width() == 8));
+    }
+
+    #[test]
+    fn test_1() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+
+        // square with a hole
+        let subj = [
+            vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(9, 0),
+                IntPoint::new(9, 9),
+                IntPoint::new(0, 9),
+            ],
+            vec![
+                IntPoint::new(3, 3),
+                IntPoint::new(3, 6),
+                IntPoint::new(6, 6),
+                IntPoint::new(6, 3),
+            ],
+        ];
+
+        let options = FractureOptions { max_width: 4, max_height: 4, ..Default::default() };
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
+        let rects = overlay.fracture(OverlayRule::Subject, FillRule::NonZero, &options);
+
+        assert_eq!(area(&rects), 72);
+        assert!(rects.iter().all(|r| 0 < r.width() && r.width() <= 4 && 0 < r.height() && r.height() <= 4));
+        assert!(rects.iter().all(|r| r.max_x <= 3 || r.min_x >= 6 || r.max_y <= 3 || r.min_y >= 6));
+
+        // equal parts, no remainder slivers
+        assert!(rects.iter().all(|r| r.width() >= 3 && r.height() >= 3));
+    }
+
+    #[test]
+    fn test_2() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+
+        // two squares shifted by 1, horizontal slicing gives slivers
+        let subj = [vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 1),
+            IntPoint::new(20, 1),
+            IntPoint::new(20, 11),
+            IntPoint::new(10, 11),
+            IntPoint::new(10, 10),
+            IntPoint::new(0, 10),
+        ]];
+
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
+        let rects = overlay.fracture(OverlayRule::Subject, FillRule::NonZero, &FractureOptions::default());
+        assert!(rects.iter().any(|r| r.height() < 2));
+
+        let options = FractureOptions { sliver_size: 2, ..Default::default() };
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
+        let rects = overlay.fracture(OverlayRule::Subject, FillRule::NonZero, &options);
+
+        assert_eq!(area(&rects), 200);
+        assert_eq!(rects.len(), 2);
+        assert!(rects.iter().all(|r| r.width() == 10 && r.height() == 10));
+    }
+}
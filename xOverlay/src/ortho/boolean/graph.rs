@@ -1,38 +1,127 @@
 use crate::core::fill_rule::FillRule;
 use crate::core::overlay_rule::OverlayRule;
+use crate::core::progress::{Phase, PhaseMonitor};
 use crate::graph::OverlayGraph;
 use crate::graph::boolean::winding_count::ShapeCountBoolean;
+#[cfg(feature = "multithreading")]
 use crate::graph::link::OverlayLink;
 use crate::ortho::column::Column;
+use crate::ortho::error::OrthoError;
 use crate::ortho::overlay::OrthoOverlay;
-use rayon::iter::IntoParallelRefMutIterator;
-use rayon::iter::ParallelIterator;
+#[cfg(feature = "provenance")]
+use crate::sub::source::SourceGroups;
+#[cfg(feature = "multithreading")]
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator};
 
 impl OrthoOverlay<ShapeCountBoolean> {
-    pub(crate) fn build_custom_graph(&mut self, fill_rule: FillRule, overlay_rule: OverlayRule) {
+    /// Builds the graph of the prepared columns.
+    /// - Returns: `OrthoError::Cancelled` if the run is cancelled, or `OrthoError::WindingOverflow`
+    ///   if a count overflows, the columns are cleared then.
+    pub(crate) fn build_custom_graph(
+        &mut self,
+        fill_rule: FillRule,
+        overlay_rule: OverlayRule,
+    ) -> Result<(), OrthoError> {
         let mut graph = self.graph.take().unwrap_or_default();
-        let multithreading = self.solver.multithreading && self.columns.len() > 4;
+        let result = self.build_graph_into(&mut graph, fill_rule, overlay_rule);
+        self.graph = Some(graph);
+        result.map_err(|error| self.abort_run(error))
+    }
+
+    /// Drops the columns of a cancelled run, so the overlay must be initialized again.
+    #[inline]
+    pub(super) fn cancel_run(&mut self) -> OrthoError {
+        self.abort_run(OrthoError::Cancelled)
+    }
+
+    /// Drops the columns of a failed run, so the overlay must be initialized again.
+    #[inline]
+    pub(super) fn abort_run(&mut self, error: OrthoError) -> OrthoError {
+        self.columns.clear();
+        error
+    }
+
+    fn build_graph_into(
+        &mut self,
+        graph: &mut OverlayGraph,
+        fill_rule: FillRule,
+        overlay_rule: OverlayRule,
+    ) -> Result<(), OrthoError> {
+        let fill =
+            PhaseMonitor::start(&self.solver, Phase::Fill, self.columns.len()).ok_or(OrthoError::Cancelled)?;
+        let multithreading = self.is_multithreading();
+
+        #[cfg(feature = "multithreading")]
         if multithreading {
-            self.parallel_build_graph(&mut graph, fill_rule, overlay_rule);
+            self.parallel_prepare_links(fill_rule, overlay_rule, &fill)?;
         } else {
-            self.serial_build_graph(&mut graph, fill_rule, overlay_rule);
+            self.serial_prepare_links(fill_rule, overlay_rule, &fill)?;
         }
-        graph.build(self.options, multithreading);
-        self.graph = Some(graph)
+
+        #[cfg(not(feature = "multithreading"))]
+        self.serial_prepare_links(fill_rule, overlay_rule, &fill)?;
+
+        if !fill.finish() {
+            return Err(OrthoError::Cancelled);
+        }
+        self.record_columns();
+        self.record_phase(&fill);
+
+        let monitor = PhaseMonitor::start(&self.solver, Phase::Graph, 1).ok_or(OrthoError::Cancelled)?;
+        self.validate_links_range_and_allocate_space(graph);
+
+        #[cfg(feature = "multithreading")]
+        if multithreading {
+            self.parallel_copy_links(graph, overlay_rule);
+        } else {
+            self.serial_copy_links(graph, overlay_rule);
+        }
+
+        #[cfg(not(feature = "multithreading"))]
+        self.serial_copy_links(graph, overlay_rule);
+
+        self.collect_sources(graph);
+        graph.solver = self.solver.clone();
+
+        let sort_parallel =
+            multithreading && self.solver.is_parallel(graph.links.len(), self.solver.min_parallel_links);
+        let options = self.options;
+
+        #[cfg(feature = "multithreading")]
+        if sort_parallel {
+            self.solver.install(|| graph.build(options, true));
+        } else {
+            graph.build(options, false);
+        }
+
+        #[cfg(not(feature = "multithreading"))]
+        graph.build(options, sort_parallel);
+
+        if !monitor.finish() {
+            return Err(OrthoError::Cancelled);
+        }
+        self.record_graph(graph);
+        self.record_phase(&monitor);
+
+        Ok(())
     }
 
-    fn serial_build_graph(
+    fn serial_prepare_links(
         &mut self,
-        graph: &mut OverlayGraph,
         fill_rule: FillRule,
         overlay_rule: OverlayRule,
-    ) {
+        fill: &PhaseMonitor,
+    ) -> Result<(), OrthoError> {
         for column in self.columns.iter_mut() {
-            column.prepare_links(fill_rule, overlay_rule);
+            column.prepare_links(fill_rule, overlay_rule)?;
+            if !fill.tick() {
+                break;
+            }
         }
+        Ok(())
+    }
 
-        self.validate_links_range_and_allocate_space(graph);
-
+    fn serial_copy_links(&self, graph: &mut OverlayGraph, overlay_rule: OverlayRule) {
         for column in self.columns.iter() {
             let sub_links = &mut graph.links[column.links_start..column.links_end()];
             column.copy_links_into(overlay_rule, sub_links);
@@ -40,28 +129,45 @@ impl OrthoOverlay<ShapeCountBoolean> {
         }
     }
 
-    fn parallel_build_graph(
+    #[cfg(feature = "multithreading")]
+    fn parallel_prepare_links(
         &mut self,
-        graph: &mut OverlayGraph,
         fill_rule: FillRule,
         overlay_rule: OverlayRule,
-    ) {
-        self.columns
-            .par_iter_mut()
-            .for_each(|column| column.prepare_links(fill_rule, overlay_rule));
-
-        self.validate_links_range_and_allocate_space(graph);
+        fill: &PhaseMonitor,
+    ) -> Result<(), OrthoError> {
+        let min_len = self.solver.min_task_len(self.columns.len());
+        let columns = &mut self.columns;
+        self.solver.install(|| {
+            columns.par_iter_mut().with_min_len(min_len).try_for_each(|column| {
+                if fill.is_cancelled() {
+                    return Ok(());
+                }
+                column.prepare_links(fill_rule, overlay_rule)?;
+                fill.tick();
+                Ok(())
+            })
+        })
+    }
 
-        let max_columns_count = (self.columns.len() / 256).max(2);
+    #[cfg(feature = "multithreading")]
+    fn parallel_copy_links(&self, graph: &mut OverlayGraph, overlay_rule: OverlayRule) {
+        // without a limit, links are copied by at most 256 tasks
+        let max_columns_count = if self.solver.max_parallelism == 0 {
+            self.columns.len() / 256
+        } else {
+            self.solver.min_task_len(self.columns.len())
+        }
+        .max(2);
 
-        Self::parallel_copy_and_sort_links(
-            &self.columns,
-            max_columns_count,
-            overlay_rule,
-            &mut graph.links,
-        );
+        let columns = &self.columns;
+        let links = &mut graph.links;
+        self.solver.install(|| {
+            Self::parallel_copy_and_sort_links(columns, max_columns_count, overlay_rule, links)
+        });
     }
 
+    #[cfg(feature = "multithreading")]
     fn parallel_copy_and_sort_links(
         columns: &[Column<ShapeCountBoolean>],
         max_columns_count: usize,
@@ -113,6 +219,98 @@ impl OrthoOverlay<ShapeCountBoolean> {
         );
     }
 
+    /// Columns are processed in parallel only with the `multithreading` feature and if there are
+    /// at least `solver.min_parallel_columns` of them.
+    #[inline]
+    pub(crate) fn is_multithreading(&self) -> bool {
+        self.solver.is_parallel(self.columns.len(), self.solver.min_parallel_columns)
+    }
+
+    /// Splits and fills all columns.
+    /// - Returns: `OrthoError::Cancelled` if the run is cancelled, or `OrthoError::WindingOverflow`
+    ///   if a count overflows, the columns are cleared then.
+    #[inline]
+    pub(super) fn prepare_all_fills(&mut self, fill_rule: FillRule) -> Result<(), OrthoError> {
+        self.for_each_column(|column| column.prepare_fills(fill_rule))
+    }
+
+    /// Runs `f` for every column as the fill phase.
+    /// - Returns: `OrthoError::Cancelled` if the run is cancelled, or the first error of `f`,
+    ///   the columns are cleared then.
+    pub(super) fn for_each_column<F>(&mut self, f: F) -> Result<(), OrthoError>
+    where
+        F: Fn(&mut Column<ShapeCountBoolean>) -> Result<(), OrthoError> + Send + Sync,
+    {
+        let fill = if let Some(fill) = PhaseMonitor::start(&self.solver, Phase::Fill, self.columns.len()) {
+            fill
+        } else {
+            return Err(self.cancel_run());
+        };
+
+        if let Err(error) = self.for_each_column_with(&fill, f) {
+            return Err(self.abort_run(error));
+        }
+        if !fill.finish() {
+            return Err(self.cancel_run());
+        }
+        self.record_columns();
+        self.record_phase(&fill);
+
+        Ok(())
+    }
+
+    fn for_each_column_with<F>(&mut self, fill: &PhaseMonitor, f: F) -> Result<(), OrthoError>
+    where
+        F: Fn(&mut Column<ShapeCountBoolean>) -> Result<(), OrthoError> + Send + Sync,
+    {
+        #[cfg(feature = "multithreading")]
+        if self.is_multithreading() {
+            let min_len = self.solver.min_task_len(self.columns.len());
+            let columns = &mut self.columns;
+            return self.solver.install(|| {
+                columns.par_iter_mut().with_min_len(min_len).try_for_each(|column| {
+                    if fill.is_cancelled() {
+                        return Ok(());
+                    }
+                    f(column)?;
+                    fill.tick();
+                    Ok(())
+                })
+            });
+        }
+
+        for column in self.columns.iter_mut() {
+            f(column)?;
+            if !fill.tick() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "provenance")]
+    fn collect_sources(&self, graph: &mut OverlayGraph) {
+        graph.sources.clear();
+        if !self.options.track_sources {
+            return;
+        }
+
+        // group sources are local for a column
+        for column in self.columns.iter() {
+            if column.sources.is_empty() {
+                continue;
+            }
+            let offset = graph.sources.append(&column.sources);
+            for link in graph.links[column.links_start..column.links_end()].iter_mut() {
+                link.source = SourceGroups::shift(link.source, offset);
+            }
+        }
+    }
+
+    #[cfg(not(feature = "provenance"))]
+    #[inline(always)]
+    fn collect_sources(&self, _graph: &mut OverlayGraph) {}
+
     #[inline]
     fn validate_links_range_and_allocate_space(&mut self, graph: &mut OverlayGraph) {
         let mut total_count = 0;
@@ -128,9 +326,15 @@ impl OrthoOverlay<ShapeCountBoolean> {
 
 impl Column<ShapeCountBoolean> {
     #[inline]
-    fn prepare_links(&mut self, fill_rule: FillRule, overlay_rule: OverlayRule) {
-        self.split();
-        self.fill_boolean(fill_rule);
+    fn prepare_links(&mut self, fill_rule: FillRule, overlay_rule: OverlayRule) -> Result<(), OrthoError> {
+        self.prepare_fills(fill_rule)?;
         self.links_count = self.count_links(overlay_rule);
+        Ok(())
+    }
+
+    #[inline]
+    fn prepare_fills(&mut self, fill_rule: FillRule) -> Result<(), OrthoError> {
+        self.split()?;
+        self.fill_boolean(fill_rule)
     }
 }
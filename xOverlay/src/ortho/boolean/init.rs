@@ -1,12 +1,18 @@
 use i_float::int::point::IntPoint;
+use i_shape::int::path::ContourExtension;
+use i_shape::int::path::IntPath;
 use i_shape::int::shape::IntContour;
 use crate::core::layout::Layout;
+use crate::core::progress::{Phase, PhaseMonitor};
 use crate::core::shape_type::ShapeType;
+use crate::core::sweep;
 use crate::core::winding::WindingCount;
 use crate::graph::boolean::winding_count::ShapeCountBoolean;
 use crate::ortho::error::OrthoError;
 use crate::ortho::mapper::Mapper;
 use crate::ortho::orientation::Orientation;
+#[cfg(feature = "provenance")]
+use crate::ortho::provenance::InputContour;
 use crate::ortho::overlay::OrthoOverlay;
 use crate::ortho::segment::OrthoSegment;
 use crate::sub::seg_iter::{DropCollinear, SegmentIterable};
@@ -14,35 +20,187 @@ use crate::sub::seg_iter::{DropCollinear, SegmentIterable};
 const MIN_COUNT_PER_COLUMN_POWER: u32 = 6;
 
 impl OrthoOverlay<ShapeCountBoolean> {
+    /// Adds subject and clip contours, they are swept along `solver.sweep_axis`.
     pub fn init_with_ortho_contours(
         &mut self,
         subj: &[IntContour],
         clip: &[IntContour],
     ) -> Result<(), OrthoError> {
-        let layout = if let Some(layout) =
-            Layout::with_subj_and_clip(subj, clip, self.options.min_count_per_column_power)
-        {
-            layout
+        let transposed = self.solver.sweep_axis.is_rows(subj, clip);
+        self.init_with_sweep(subj, clip, transposed)
+    }
+
+    /// Adds subject and clip contours, they are transposed to be swept by rows if `transposed` is set.
+    pub(crate) fn init_with_sweep(
+        &mut self,
+        subj: &[IntContour],
+        clip: &[IntContour],
+        transposed: bool,
+    ) -> Result<(), OrthoError> {
+        let result = if transposed {
+            self.init_with_columns(&sweep::transposed(subj), &sweep::transposed(clip))
         } else {
+            self.init_with_columns(subj, clip)
+        };
+        self.transposed = transposed;
+
+        result
+    }
+
+    fn init_with_columns(&mut self, subj: &[IntContour], clip: &[IntContour]) -> Result<(), OrthoError> {
+        if !self.init_columns(subj, clip)? {
+            return Ok(());
+        }
+
+        self.add_ortho_contours(subj, ShapeType::Subject)?;
+        self.add_ortho_contours(clip, ShapeType::Clip)?;
+
+        Ok(())
+    }
+
+    /// Adds every contour as a solid subject shape, the contour direction is ignored.
+    /// Contours with zero area are skipped.
+    pub(crate) fn init_with_solid_contours(&mut self, contours: &[IntContour]) -> Result<(), OrthoError> {
+        if !self.init_columns(contours, &[])? {
             return Ok(());
+        }
+
+        let (direct, invert) = ShapeCountBoolean::with_shape_type(ShapeType::Subject);
+
+        for (index, contour) in contours.iter().enumerate() {
+            if contour.len() < 4 {
+                continue;
+            }
+            let source = self.contour_source(ShapeType::Subject, index)?;
+            let area = contour.unsafe_area();
+            if area < 0 {
+                self.add_ortho_contour(contour, direct, invert, source)?;
+            } else if area > 0 {
+                self.add_ortho_contour(contour, invert, direct, source)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds open polylines as subject segments and clip contours. Every polyline segment has the same
+    /// direct count, so overlapped polylines never cancel each other.
+    /// - Returns: `OrthoError::NotValidPath` if a string segment is not orthogonal, the overlay is not changed then.
+    pub(crate) fn init_with_strings(&mut self, strings: &[IntPath], clip: &[IntContour]) -> Result<(), OrthoError> {
+        for string in strings.iter() {
+            for w in string.windows(2) {
+                if w[0] != w[1] {
+                    Orientation::new([w[0], w[1]])?;
+                }
+            }
+        }
+
+        if !self.init_columns(strings, clip)? {
+            return Ok(());
+        }
+
+        // a failed init must not leave a part of the input
+        self.add_strings(strings, clip).map_err(|error| self.abort_run(error))
+    }
+
+    fn add_strings(&mut self, strings: &[IntPath], clip: &[IntContour]) -> Result<(), OrthoError> {
+        let (direct, _) = ShapeCountBoolean::with_shape_type(ShapeType::Subject);
+        for (index, string) in strings.iter().enumerate() {
+            let source = self.contour_source(ShapeType::Subject, index)?;
+            for w in string.windows(2) {
+                if w[0] != w[1] {
+                    self.add_segment([w[0], w[1]], direct, direct, source)?;
+                }
+            }
+            if let (Some(&first), Some(&last)) = (string.first(), string.last()) {
+                self.add_end_point(first);
+                self.add_end_point(last);
+            }
+        }
+
+        self.add_ortho_contours(clip, ShapeType::Clip)?;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn add_end_point(&mut self, point: IntPoint) {
+        let index = self.layout.index(point.x);
+        self.columns[index].end_points.push(point);
+    }
+
+    /// - Returns: `false` if there is nothing to overlay, or `OrthoError::Cancelled` if the run is cancelled.
+    fn init_columns(&mut self, subj: &[IntContour], clip: &[IntContour]) -> Result<bool, OrthoError> {
+        let layout_monitor = if let Some(monitor) = PhaseMonitor::start(&self.solver, Phase::Layout, 1) {
+            monitor
+        } else {
+            return Err(self.cancel_run());
+        };
+
+        let layout = Layout::with_options(subj, clip, &self.options);
+        if !layout_monitor.finish() {
+            return Err(self.cancel_run());
+        }
+        let layout = if let Some(layout) = layout {
+            layout
+        } else {
+            self.reset_stats();
+            self.transposed = false;
+            return Ok(false);
+        };
+
+        let monitor = if let Some(monitor) = PhaseMonitor::start(&self.solver, Phase::Mapping, 1) {
+            monitor
+        } else {
+            return Err(self.cancel_run());
         };
 
-        self.layout = layout;
-        let mut mapper = Mapper::new(self.layout.clone());
+        let mut mapper = Mapper::new(layout);
 
         mapper.add_ortho_contours(subj);
         mapper.add_ortho_contours(clip);
 
+        if !monitor.finish() {
+            return Err(self.cancel_run());
+        }
+
+        self.init_columns_with_mapper(mapper);
+        self.record_phase(&layout_monitor);
+        self.record_phase(&monitor);
+
+        Ok(true)
+    }
+
+    pub(crate) fn init_columns_with_mapper(&mut self, mapper: Mapper) {
+        self.reset_stats();
+        self.transposed = false;
+        self.layout = mapper.layout;
         self.columns.resize(self.layout.count(), Default::default());
         for (i, (column, counter)) in self.columns.iter_mut().zip(mapper.columns).enumerate() {
             let (min, max) = self.layout.borders(i);
             column.init_with_counter(min, max, counter);
+            #[cfg(feature = "provenance")]
+            {
+                column.sources.enabled = self.options.track_sources;
+            }
         }
+    }
 
-        self.add_ortho_contours(subj, ShapeType::Subject)?;
-        self.add_ortho_contours(clip, ShapeType::Clip)?;
+    /// The source id of an input contour, it is zero if sources are not tracked.
+    #[cfg(feature = "provenance")]
+    #[inline]
+    pub(crate) fn contour_source(&self, shape_type: ShapeType, index: usize) -> Result<u32, OrthoError> {
+        if self.options.track_sources {
+            InputContour::new(shape_type, index).source()
+        } else {
+            Ok(0)
+        }
+    }
 
-        Ok(())
+    #[cfg(not(feature = "provenance"))]
+    #[inline(always)]
+    pub(crate) fn contour_source(&self, _shape_type: ShapeType, _index: usize) -> Result<u32, OrthoError> {
+        Ok(0)
     }
 
     fn add_ortho_contours(
@@ -52,19 +210,21 @@ impl OrthoOverlay<ShapeCountBoolean> {
     ) -> Result<(), OrthoError> {
         let (direct, invert) = ShapeCountBoolean::with_shape_type(shape_type);
 
-        for contour in contours.iter() {
-            self.add_ortho_contour(contour, direct, invert)?;
+        for (index, contour) in contours.iter().enumerate() {
+            let source = self.contour_source(shape_type, index)?;
+            self.add_ortho_contour(contour, direct, invert, source)?;
         }
 
         Ok(())
     }
 
     #[inline]
-    fn add_ortho_contour(
+    pub(crate) fn add_ortho_contour(
         &mut self,
         contour: &[IntPoint],
         direct: ShapeCountBoolean,
         invert: ShapeCountBoolean,
+        source: u32,
     ) -> Result<(), OrthoError> {
         let iter = if let Some(result) = contour.segment_iter::<DropCollinear>() {
             result
@@ -73,18 +233,20 @@ impl OrthoOverlay<ShapeCountBoolean> {
         };
 
         for s in iter {
-            _ = self.add_segment(s, direct, invert);
+            _ = self.add_segment(s, direct, invert, source);
         }
 
         Ok(())
     }
 
     #[inline]
+    #[cfg_attr(not(feature = "provenance"), allow(unused_variables))]
     fn add_segment(
         &mut self,
         segment: [IntPoint; 2],
         direct: ShapeCountBoolean,
         invert: ShapeCountBoolean,
+        source: u32,
     ) -> Result<(), OrthoError> {
         match Orientation::new(segment)? {
             Orientation::Vertical => {
@@ -97,6 +259,8 @@ impl OrthoOverlay<ShapeCountBoolean> {
                             min,
                             max,
                             count,
+                            #[cfg(feature = "provenance")]
+                            source,
                         }
                     );
                 }
@@ -117,6 +281,8 @@ impl OrthoOverlay<ShapeCountBoolean> {
                                 min: x0,
                                 max: xi,
                                 count,
+                                #[cfg(feature = "provenance")]
+                                source,
                             }
                         );
                     }
@@ -134,6 +300,8 @@ impl OrthoOverlay<ShapeCountBoolean> {
                                 min: x0,
                                 max: max_x,
                                 count,
+                                #[cfg(feature = "provenance")]
+                                source,
                             });
                 }
 
@@ -203,6 +371,8 @@ mod tests {
                 min,
                 max,
                 count,
+                #[cfg(feature = "provenance")]
+                source: 0,
             }
         }
 
@@ -220,21 +390,21 @@ mod tests {
         ]
             .to_vec()];
 
-        overlay.init_with_ortho_contours(&subj, &[]);
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
 
         assert_eq!(overlay.columns.len(), 1);
         let column = &overlay.columns[0];
 
         let must_be_hz_set: HashSet<_> = [
             OrthoSegment::with_shape(0, 10, 0, Subject),
-            OrthoSegment::with_shape(0, 10, 10, Subject),
+            OrthoSegment::with_shape(10, 0, 10, Subject),
         ]
             .iter()
             .copied()
             .collect();
 
         let must_be_vr_set: HashSet<_> = [
-            OrthoSegment::with_shape(0, 10, 0, Subject),
+            OrthoSegment::with_shape(10, 0, 0, Subject),
             OrthoSegment::with_shape(0, 10, 10, Subject),
         ]
             .iter()
@@ -265,22 +435,21 @@ mod tests {
         ]
             .to_vec()];
 
-        overlay.init_with_ortho_contours(&subj, &[]);
-        let (direct, invert) = ShapeCountBoolean::with_shape_type(Subject);
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
 
         assert_eq!(overlay.columns.len(), 1);
         let column = &overlay.columns[0];
 
         let must_be_hz_set: HashSet<_> = [
             OrthoSegment::with_shape(0, 10, 0, Subject),
-            OrthoSegment::with_shape(0, 10, 10, Subject),
+            OrthoSegment::with_shape(10, 0, 10, Subject),
         ]
             .iter()
             .copied()
             .collect();
 
         let must_be_vr_set: HashSet<_> = [
-            OrthoSegment::with_shape(0, 10, 0, Subject),
+            OrthoSegment::with_shape(10, 0, 0, Subject),
             OrthoSegment::with_shape(0, 10, 10, Subject),
         ]
             .iter()
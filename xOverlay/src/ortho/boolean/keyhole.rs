@@ -0,0 +1,154 @@
+use crate::bind::keyhole::Keyhole;
+use crate::core::canonical::Canonical;
+use crate::core::direction::ContourDirection;
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay_rule::OverlayRule;
+use crate::core::sweep::Transpose;
+use crate::graph::boolean::winding_count::ShapeCountBoolean;
+use crate::ortho::error::OrthoError;
+use crate::ortho::overlay::OrthoOverlay;
+use alloc::vec;
+use alloc::vec::Vec;
+use i_shape::int::shape::IntContour;
+
+impl OrthoOverlay<ShapeCountBoolean> {
+    /// Performs the overlay and returns every shape as a single contour, holes are joined to the
+    /// outer boundary through zero-width orthogonal bridges.
+    /// - `overlay_rule`: The boolean operation rule to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas.
+    /// - Returns: A simple contour per shape, with the direction of an outer boundary.
+    ///
+    /// **A cancelled or overflowed run returns no contours**, use [`Self::try_overlay_keyhole`] to tell it from an empty result.
+    pub fn overlay_keyhole(&mut self, overlay_rule: OverlayRule, fill_rule: FillRule) -> Vec<IntContour> {
+        self.try_overlay_keyhole(overlay_rule, fill_rule).unwrap_or_default()
+    }
+
+    /// Performs the overlay like [`Self::overlay_keyhole`] and stops as soon as `solver.cancel` is cancelled.
+    /// - Returns: The contours, or the error of [`Self::try_overlay`].
+    pub fn try_overlay_keyhole(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+    ) -> Result<Vec<IntContour>, OrthoError> {
+        self.build_custom_graph(fill_rule, overlay_rule)?;
+        let graph = if let Some(graph) = &mut self.graph {
+            graph
+        } else {
+            return Ok(vec![]);
+        };
+        if !self.transposed {
+            return Ok(graph.extract_keyhole_contours(overlay_rule));
+        }
+
+        // bridges are built by columns, so the shapes are transposed back first
+        let mut shapes = graph.extract_shapes(overlay_rule);
+        shapes.transpose();
+        shapes.canonicalize();
+
+        let clockwise = self.options.output_direction == ContourDirection::Clockwise;
+        Ok(shapes.iter().map(|shape| shape.keyhole(clockwise)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::direction::ContourDirection;
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::test_util::rect;
+    use alloc::vec::Vec;
+    use i_float::int::point::IntPoint;
+    use i_shape::int::area::Area;
+    use i_shape::int::shape::{IntContour, IntShapes};
+
+    fn area(shapes: &IntShapes) -> i64 {
+        shapes.iter().map(|shape| shape.area()).sum()
+    }
+
+    fn check(subj: &[IntContour], clip: &[IntContour], direction: ContourDirection) {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.options.output_direction = direction;
+        overlay.init_with_ortho_contours(subj, clip).expect("OK");
+        let shapes = overlay.overlay(OverlayRule::Difference, FillRule::NonZero);
+
+        overlay.init_with_ortho_contours(subj, clip).expect("OK");
+        let contours = overlay.overlay_keyhole(OverlayRule::Difference, FillRule::NonZero);
+
+        assert_eq!(contours.len(), shapes.len());
+        let keyhole_area: i64 = contours.iter().map(|c| c.area()).sum();
+        assert_eq!(keyhole_area, area(&shapes));
+
+        for contour in contours.iter() {
+            assert!(contour.len() >= 4);
+            for (i, &p) in contour.iter().enumerate() {
+                let q = contour[(i + 1) % contour.len()];
+                assert_ne!(p, q);
+                assert!(p.x == q.x || p.y == q.y);
+            }
+        }
+
+        // bridges have zero width, so the contours fill exactly the same area
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.options.output_direction = direction;
+        overlay.init_with_ortho_contours(&contours, &[]).expect("OK");
+        let restored = overlay.overlay(OverlayRule::Subject, FillRule::NonZero);
+        assert_eq!(area(&restored), area(&shapes));
+        assert_eq!(restored.len(), shapes.len());
+        let holes = |shapes: &IntShapes| shapes.iter().map(|s| s.len()).sum::<usize>();
+        assert_eq!(holes(&restored), holes(&shapes));
+    }
+
+    #[test]
+    fn test_0() {
+        let subj = [rect(0, 0, 10, 10)];
+        let clip = [rect(2, 2, 8, 8)];
+
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.init_with_ortho_contours(&subj, &clip).expect("OK");
+        let contours = overlay.overlay_keyhole(OverlayRule::Difference, FillRule::NonZero);
+
+        assert_eq!(contours.len(), 1);
+        assert_eq!(contours[0].area(), -64);
+        assert_eq!(contours[0].len(), 11);
+        assert!(contours[0].contains(&IntPoint::new(2, 0)));
+
+        check(&subj, &clip, ContourDirection::CounterClockwise);
+        check(&subj, &clip, ContourDirection::Clockwise);
+    }
+
+    #[test]
+    fn test_1() {
+        // holes above each other, side by side and on the same line
+        let subj = [rect(0, 0, 100, 100), rect(200, 0, 210, 10)];
+        let clip = [
+            rect(10, 10, 20, 20),
+            rect(10, 30, 20, 40),
+            rect(5, 50, 30, 60),
+            rect(40, 10, 50, 20),
+            rect(60, 10, 70, 90),
+            rect(75, 10, 80, 20),
+        ];
+
+        check(&subj, &clip, ContourDirection::CounterClockwise);
+        check(&subj, &clip, ContourDirection::Clockwise);
+    }
+
+    #[test]
+    fn test_2() {
+        // a grid of holes
+        let subj = [rect(0, 0, 200, 200)];
+        let mut clip = Vec::new();
+        for i in 0..9 {
+            for j in 0..9 {
+                let x = 20 * i + 10 + (j % 3);
+                let y = 20 * j + 10 + (i % 2);
+                clip.push(rect(x, y, x + 5, y + 5));
+            }
+        }
+
+        check(&subj, &clip, ContourDirection::CounterClockwise);
+        check(&subj, &clip, ContourDirection::Clockwise);
+    }
+}
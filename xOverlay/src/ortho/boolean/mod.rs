@@ -1,3 +1,15 @@
 pub mod init;
+mod connectivity;
 pub(crate) mod graph;
-mod overlay;
\ No newline at end of file
+mod fracture;
+mod keyhole;
+mod overlay;
+#[cfg(feature = "provenance")]
+mod provenance;
+mod raster;
+mod slice;
+mod stats;
+mod stream;
+mod string;
+mod svg;
+mod tile;
\ No newline at end of file
@@ -1,17 +1,50 @@
 use crate::core::fill_rule::FillRule;
+use crate::core::canonical::Canonical;
 use crate::core::overlay_rule::OverlayRule;
+use crate::core::progress::{Phase, PhaseMonitor};
+use crate::core::sweep::Transpose;
 use crate::graph::boolean::winding_count::ShapeCountBoolean;
+use crate::ortho::error::OrthoError;
 use crate::ortho::overlay::OrthoOverlay;
 use alloc::vec;
 use i_shape::int::shape::IntShapes;
 
 impl OrthoOverlay<ShapeCountBoolean> {
+    /// Performs the overlay, see [`Self::try_overlay`].
+    ///
+    /// **A cancelled or overflowed run returns no shapes**, the same as an empty result.
+    /// Use [`Self::try_overlay`] if `solver.cancel` is set or the input may stack more than 32767 contours.
     pub fn overlay(&mut self, overlay_rule: OverlayRule, fill_rule: FillRule) -> IntShapes {
-        self.build_custom_graph(fill_rule, overlay_rule);
-        if let Some(graph) = &mut self.graph {
+        self.try_overlay(overlay_rule, fill_rule).unwrap_or_default()
+    }
+
+    /// Performs the overlay and stops as soon as `solver.cancel` is cancelled.
+    /// - `overlay_rule`: The boolean operation rule to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas.
+    /// - Returns: The shapes, `OrthoError::Cancelled`, or `OrthoError::WindingOverflow` if more
+    ///   contours are stacked than an `i16` count holds. A failed overlay is reusable, but it
+    ///   must be initialized again.
+    pub fn try_overlay(&mut self, overlay_rule: OverlayRule, fill_rule: FillRule) -> Result<IntShapes, OrthoError> {
+        self.build_custom_graph(fill_rule, overlay_rule)?;
+
+        let monitor = PhaseMonitor::start(&self.solver, Phase::Extraction, 1).ok_or(OrthoError::Cancelled)?;
+        let mut shapes = if let Some(graph) = &mut self.graph {
             graph.extract_shapes(overlay_rule)
         } else {
             vec![]
+        };
+        if self.transposed {
+            // the canonical form of columns does not depend on the axis
+            shapes.transpose();
+            shapes.canonicalize();
+        }
+
+        if monitor.finish() {
+            self.record_shapes(&shapes);
+            self.record_phase(&monitor);
+            Ok(shapes)
+        } else {
+            Err(OrthoError::Cancelled)
         }
     }
 }
@@ -22,7 +55,10 @@ mod tests {
 
     use crate::core::fill_rule::FillRule;
     use crate::core::overlay_rule::OverlayRule;
+    use crate::core::shape_type::ShapeType;
+    use crate::core::winding::WindingCount;
     use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::error::OrthoError;
     use crate::ortho::overlay::OrthoOverlay;
     use alloc::vec;
     use alloc::vec::Vec;
@@ -429,6 +465,73 @@ mod tests {
         assert_eq!(shape[0].area(), -27);
     }
 
+    #[test]
+    #[ignore = "slow, the overflow itself is covered by test_15"]
+    fn test_13() {
+        // identical rects are merged into one segment per side, the merged count overflows i16
+        let rect = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(0, 10),
+        ];
+
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.init_with_ortho_contours(&vec![rect.clone(); 40_000], &[]).expect("OK");
+        let result = overlay.try_overlay(OverlayRule::Subject, FillRule::NonZero);
+        assert!(matches!(result, Err(OrthoError::WindingOverflow)));
+
+        // the failed overlay must be initialized again
+        assert!(overlay.overlay(OverlayRule::Subject, FillRule::NonZero).is_empty());
+
+        overlay.init_with_ortho_contours(&[rect.clone(), rect], &[]).expect("OK");
+        assert_eq!(overlay.overlay(OverlayRule::Subject, FillRule::NonZero).len(), 1);
+    }
+
+    #[test]
+    #[ignore = "slow, the overflow itself is covered by test_15"]
+    fn test_14() {
+        // nested rects are accumulated by the fill, the count of the center overflows i16
+        let rect = |r: i32| {
+            vec![
+                IntPoint::new(-r, -r),
+                IntPoint::new(r, -r),
+                IntPoint::new(r, r),
+                IntPoint::new(-r, r),
+            ]
+        };
+
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        let subj: Vec<IntContour> = (1..=40_000).map(rect).collect();
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
+        let result = overlay.try_overlay(OverlayRule::Subject, FillRule::Positive);
+        assert!(matches!(result, Err(OrthoError::WindingOverflow)));
+
+        let n = i16::MAX as i32;
+        overlay.init_with_ortho_contours(&[rect(n)], &[]).expect("OK");
+        let outer = overlay.overlay(OverlayRule::Subject, FillRule::NonZero);
+        assert_eq!(outer.len(), 1);
+
+        let subj: Vec<IntContour> = (1..=n).map(rect).collect();
+        overlay.init_with_ortho_contours(&subj, &[rect(1)]).expect("OK");
+        assert_eq!(overlay.overlay(OverlayRule::Subject, FillRule::Positive), outer);
+
+        // the subject count of the center is odd
+        assert_eq!(overlay.overlay(OverlayRule::Intersect, FillRule::EvenOdd).len(), 1);
+    }
+
+    #[test]
+    fn test_15() {
+        let max = ShapeCountBoolean::new(i16::MAX, 0);
+        let (direct, invert) = ShapeCountBoolean::with_shape_type(ShapeType::Subject);
+        assert_eq!(max.add(invert), Some(ShapeCountBoolean::new(i16::MAX - 1, 0)));
+        assert_eq!(max.add(direct), None);
+
+        let max = ShapeCountBoolean::new(0, i16::MAX);
+        let (direct, _) = ShapeCountBoolean::with_shape_type(ShapeType::Clip);
+        assert_eq!(max.add(direct), None);
+    }
+
     #[test]
     fn test_random_0() {
         let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
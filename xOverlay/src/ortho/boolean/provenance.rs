@@ -0,0 +1,178 @@
+use crate::core::canonical::Canonical;
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay_rule::OverlayRule;
+use crate::core::sweep::Transpose;
+use crate::graph::boolean::winding_count::ShapeCountBoolean;
+use crate::ortho::error::OrthoError;
+use crate::ortho::overlay::OrthoOverlay;
+use crate::ortho::provenance::SourcedShape;
+use alloc::vec;
+use alloc::vec::Vec;
+
+impl OrthoOverlay<ShapeCountBoolean> {
+    /// Performs the overlay like [`Self::overlay`] and maps every output edge back to the input contours which produce it.
+    ///
+    /// Requires `options.track_sources` to be set before the contours are added.
+    /// - `overlay_rule`: The boolean operation rule to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas.
+    /// - Returns: Shapes in the same layout as [`Self::overlay`], with the sources of every edge,
+    ///   `OrthoError::SourcesNotTracked` if the contours were added without tracking, or the error
+    ///   of [`Self::try_overlay`].
+    pub fn overlay_with_sources(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+    ) -> Result<Vec<SourcedShape>, OrthoError> {
+        // untracked columns know only one source of merged segments
+        if !self.options.track_sources || self.columns.iter().any(|column| !column.sources.enabled) {
+            return Err(OrthoError::SourcesNotTracked);
+        }
+        self.build_custom_graph(fill_rule, overlay_rule)?;
+
+        let mut shapes = if let Some(graph) = &mut self.graph {
+            graph.extract_shapes_with_sources(overlay_rule)
+        } else {
+            vec![]
+        };
+        if self.transposed {
+            for shape in shapes.iter_mut() {
+                shape.transpose();
+            }
+            shapes.canonicalize();
+        }
+
+        Ok(shapes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::core::shape_type::ShapeType;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::error::OrthoError;
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::ortho::provenance::{InputContour, SourcedContour};
+    use crate::test_util::rect;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_float::int::point::IntPoint;
+
+    fn edge_sources(contour: &SourcedContour, a: IntPoint, b: IntPoint) -> Vec<InputContour> {
+        let n = contour.points.len();
+        let i = contour
+            .points
+            .iter()
+            .position(|&p| p == a)
+            .expect("edge start");
+        assert_eq!(contour.points[(i + 1) % n], b);
+        contour.edges[i].clone()
+    }
+
+    #[test]
+    fn test_0() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.options.track_sources = true;
+
+        let subj = [rect(0, 0, 10, 10), rect(10, 0, 20, 10)];
+        let clip = [rect(0, 0, 20, 5)];
+
+        overlay.init_with_ortho_contours(&subj, &clip).expect("OK");
+        let result = overlay.overlay_with_sources(OverlayRule::Union, FillRule::NonZero).expect("OK");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 1);
+        let contour = &result[0][0];
+        assert_eq!(contour.points.len(), contour.edges.len());
+        assert_eq!(contour.points.len(), 4);
+
+        let s0 = InputContour::new(ShapeType::Subject, 0);
+        let s1 = InputContour::new(ShapeType::Subject, 1);
+        let c0 = InputContour::new(ShapeType::Clip, 0);
+
+        // the bottom edge is shared by all three contours
+        let bottom = edge_sources(contour, IntPoint::new(0, 0), IntPoint::new(20, 0));
+        assert_eq!(bottom, vec![s0, s1, c0]);
+
+        let right = edge_sources(contour, IntPoint::new(20, 0), IntPoint::new(20, 10));
+        assert_eq!(right, vec![s1, c0]);
+
+        let top = edge_sources(contour, IntPoint::new(20, 10), IntPoint::new(0, 10));
+        assert_eq!(top, vec![s0, s1]);
+
+        let left = edge_sources(contour, IntPoint::new(0, 10), IntPoint::new(0, 0));
+        assert_eq!(left, vec![s0, c0]);
+    }
+
+    #[test]
+    fn test_1() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.options.track_sources = true;
+        overlay.options.min_count_per_column_power = 2;
+
+        // a frame, the hole is produced by the clip
+        let mut subj = Vec::new();
+        for i in 0..8 {
+            subj.push(rect(10 * i, 0, 10 * i + 10, 80));
+        }
+        let clip = [rect(20, 20, 60, 60)];
+
+        overlay.init_with_ortho_contours(&subj, &clip).expect("OK");
+        let result = overlay.overlay_with_sources(OverlayRule::Difference, FillRule::NonZero).expect("OK");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 2);
+
+        let outer = &result[0][0];
+        let bottom = edge_sources(outer, IntPoint::new(0, 0), IntPoint::new(80, 0));
+        let template: Vec<_> = (0..8).map(|i| InputContour::new(ShapeType::Subject, i)).collect();
+        assert_eq!(bottom, template);
+
+        // vertical edges of the hole overlap the borders of subject contours
+        let s = |index| InputContour::new(ShapeType::Subject, index);
+        let c0 = InputContour::new(ShapeType::Clip, 0);
+        let hole = &result[0][1];
+        assert_eq!(edge_sources(hole, IntPoint::new(20, 20), IntPoint::new(20, 60)), vec![s(1), s(2), c0]);
+        assert_eq!(edge_sources(hole, IntPoint::new(20, 60), IntPoint::new(60, 60)), vec![c0]);
+        assert_eq!(edge_sources(hole, IntPoint::new(60, 60), IntPoint::new(60, 20)), vec![s(5), s(6), c0]);
+        assert_eq!(edge_sources(hole, IntPoint::new(60, 20), IntPoint::new(20, 20)), vec![c0]);
+    }
+
+    #[test]
+    fn test_2() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+
+        let subj = [rect(0, 0, 10, 10), rect(0, 0, 10, 10)];
+
+        // without tracking the sources are not known
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
+        assert!(matches!(
+            overlay.overlay_with_sources(OverlayRule::Subject, FillRule::NonZero),
+            Err(OrthoError::SourcesNotTracked)
+        ));
+
+        // tracking is set after the init
+        overlay.options.track_sources = true;
+        assert!(matches!(
+            overlay.overlay_with_sources(OverlayRule::Subject, FillRule::NonZero),
+            Err(OrthoError::SourcesNotTracked)
+        ));
+
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
+        let result = overlay.overlay_with_sources(OverlayRule::Subject, FillRule::NonZero).expect("OK");
+        let s = |index| InputContour::new(ShapeType::Subject, index);
+        for edge in result[0][0].edges.iter() {
+            assert_eq!(edge, &vec![s(0), s(1)]);
+        }
+    }
+
+    #[test]
+    fn test_3() {
+        assert_eq!(InputContour::new(ShapeType::Clip, (1 << 30) - 1).source().ok(), Some(u32::MAX >> 1));
+        assert!(matches!(
+            InputContour::new(ShapeType::Subject, 1 << 30).source(),
+            Err(OrthoError::OutOfRange)
+        ));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay_rule::OverlayRule;
+use crate::graph::boolean::winding_count::ShapeCountBoolean;
+use crate::ortho::error::OrthoError;
+use crate::ortho::overlay::OrthoOverlay;
+use crate::ortho::raster::{Raster, RasterBuffer, RasterLayout};
+
+impl OrthoOverlay<ShapeCountBoolean> {
+    /// Renders the result of the overlay into a pixel grid with exact per-pixel coverage.
+    ///
+    /// The result is the same region which [`OrthoOverlay::overlay`] would return, but it is
+    /// accumulated column by column from the filled horizontal segments, so no contours are built.
+    /// - `overlay_rule`: The boolean operation rule to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas.
+    /// - `layout`: The pixel grid to render into.
+    ///
+    /// **A cancelled or overflowed run returns a blank raster**, use [`Self::try_rasterize`] to tell it from an empty result.
+    pub fn rasterize(&mut self, overlay_rule: OverlayRule, fill_rule: FillRule, layout: RasterLayout) -> Raster {
+        self.try_rasterize(overlay_rule, fill_rule, layout)
+            .unwrap_or_else(|_| RasterBuffer::new(layout).into_raster())
+    }
+
+    /// Renders the result of the overlay like [`Self::rasterize`] and stops as soon as `solver.cancel` is cancelled.
+    /// - Returns: The raster, or the error of [`Self::try_overlay`].
+    pub fn try_rasterize(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        layout: RasterLayout,
+    ) -> Result<Raster, OrthoError> {
+        self.prepare_all_fills(fill_rule)?;
+
+        let layout = if self.transposed { layout.transposed() } else { layout };
+        let mut buffer = RasterBuffer::new(layout);
+        for column in self.columns.iter() {
+            column.rasterize_into(overlay_rule, &mut buffer);
+        }
+
+        let raster = buffer.into_raster();
+        Ok(if self.transposed { raster.transposed() } else { raster })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::error::OrthoError;
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::ortho::raster::RasterLayout;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_float::int::point::IntPoint;
+
+    #[test]
+    fn test_0() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+
+        let subj = [vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(0, 10),
+        ]];
+
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
+        let layout = RasterLayout::new(IntPoint::new(0, 0), 4, 4, 3, 3).expect("OK");
+        let raster = overlay.rasterize(OverlayRule::Subject, FillRule::NonZero, layout);
+
+        assert_eq!(raster.areas(), &[16, 16, 8, 16, 16, 8, 8, 8, 4]);
+        assert_eq!(raster.coverage(2, 2), 0.25);
+        assert_eq!(raster.to_bytes()[2], 128);
+        assert_eq!(raster.to_bits(), vec![true, true, true, true, true, true, true, true, false]);
+    }
+
+    #[test]
+    fn test_1() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.options.min_count_per_column_power = 2;
+
+        let subj = [vec![
+            IntPoint::new(-2, -2),
+            IntPoint::new(9, -2),
+            IntPoint::new(9, 9),
+            IntPoint::new(-2, 9),
+        ]];
+
+        let clip = [
+            vec![
+                IntPoint::new(1, 1),
+                IntPoint::new(4, 1),
+                IntPoint::new(4, 4),
+                IntPoint::new(1, 4),
+            ],
+            vec![
+                IntPoint::new(5, 2),
+                IntPoint::new(12, 2),
+                IntPoint::new(12, 3),
+                IntPoint::new(5, 3),
+            ],
+        ];
+
+        let layout = RasterLayout::new(IntPoint::new(-1, 0), 3, 2, 4, 4).expect("OK");
+
+        for rule in [OverlayRule::Difference, OverlayRule::Xor, OverlayRule::Intersect] {
+            overlay.init_with_ortho_contours(&subj, &clip).expect("OK");
+            let raster = overlay.rasterize(rule, FillRule::NonZero, layout);
+
+            let template = brute_force(&subj, &clip, rule, layout);
+            assert_eq!(raster.areas(), template.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_2() {
+        let origin = IntPoint::new(0, 0);
+        assert!(matches!(RasterLayout::new(origin, 0, 4, 3, 3), Err(OrthoError::OutOfRange)));
+        assert!(matches!(RasterLayout::new(origin, 4, 0, 3, 3), Err(OrthoError::OutOfRange)));
+
+        let layout = RasterLayout::new(origin, 4, 2, 0, 3).expect("OK");
+        assert_eq!((layout.pixel_width(), layout.pixel_height(), layout.columns(), layout.rows()), (4, 2, 0, 3));
+        assert_eq!(layout.pixel_area(), 8);
+    }
+
+    fn brute_force(subj: &[Vec<IntPoint>], clip: &[Vec<IntPoint>], rule: OverlayRule, layout: RasterLayout) -> Vec<u64> {
+        let is_inside = |contours: &[Vec<IntPoint>], x: i32, y: i32| {
+            contours.iter().any(|c| {
+                let (min_x, max_x) = (c.iter().map(|p| p.x).min().unwrap(), c.iter().map(|p| p.x).max().unwrap());
+                let (min_y, max_y) = (c.iter().map(|p| p.y).min().unwrap(), c.iter().map(|p| p.y).max().unwrap());
+                min_x <= x && x < max_x && min_y <= y && y < max_y
+            })
+        };
+
+        let mut areas = vec![0; layout.columns() * layout.rows()];
+        for row in 0..layout.rows() {
+            for column in 0..layout.columns() {
+                let x0 = layout.origin().x + column as i32 * layout.pixel_width() as i32;
+                let y0 = layout.origin().y + row as i32 * layout.pixel_height() as i32;
+                for x in x0..x0 + layout.pixel_width() as i32 {
+                    for y in y0..y0 + layout.pixel_height() as i32 {
+                        let s = is_inside(subj, x, y);
+                        let c = is_inside(clip, x, y);
+                        let fill = match rule {
+                            OverlayRule::Difference => s && !c,
+                            OverlayRule::Xor => s != c,
+                            _ => s && c,
+                        };
+                        areas[row * layout.columns() + column] += fill as u64;
+                    }
+                }
+            }
+        }
+        areas
+    }
+}
@@ -0,0 +1,210 @@
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay_rule::OverlayRule;
+use crate::graph::boolean::winding_count::ShapeCountBoolean;
+use crate::ortho::error::OrthoError;
+use crate::ortho::orientation::Orientation;
+use crate::ortho::overlay::OrthoOverlay;
+use alloc::vec;
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+use i_shape::int::area::Area;
+use i_shape::int::path::IntPath;
+use i_shape::int::shape::{IntContour, IntShapes};
+use i_shape::int::simple::SimpleShapes;
+
+impl OrthoOverlay<ShapeCountBoolean> {
+    /// Cuts shapes into pieces along orthogonal lines. Unlike a boolean difference, a cut has zero width:
+    /// pieces on both sides of it keep their whole area and share the cut as a common edge.
+    ///
+    /// Internally the coordinates are doubled and every cut removes a unit strip on its positive side,
+    /// the strip collapses back to the cut line when the result is scaled down.
+    /// - `subj`: Contours of the shapes to cut.
+    /// - `lines`: Cut lines, open polylines where every segment is vertical or horizontal.
+    ///   A line may end inside a shape, such a cut does not separate anything.
+    /// - `fill_rule`: Fill rule to determine the shapes.
+    /// - Returns: The pieces with their holes, or an error if a line segment is not orthogonal
+    ///   or a coordinate does not fit in the half of the `i32` range.
+    ///
+    /// Note: The output is always simplified, `preserve_output_collinear` is ignored.
+    pub fn slice_by_lines(
+        &mut self,
+        subj: &[IntContour],
+        lines: &[IntPath],
+        fill_rule: FillRule,
+    ) -> Result<IntShapes, OrthoError> {
+        self.init_with_ortho_contours(subj, &[])?;
+        let shapes = self.try_overlay(OverlayRule::Subject, fill_rule)?;
+        if lines.is_empty() || shapes.is_empty() {
+            return Ok(shapes);
+        }
+
+        let mut contours = Vec::with_capacity(shapes.iter().map(|shape| shape.len()).sum());
+        for contour in shapes.iter().flatten() {
+            contours.push(contour.doubled()?);
+        }
+
+        let mut strips = Vec::with_capacity(lines.len());
+        for line in lines.iter() {
+            for w in line.windows(2) {
+                if w[0] != w[1] {
+                    strips.push(cut_strip(w[0], w[1])?);
+                }
+            }
+        }
+
+        let options = self.options;
+        self.options.min_output_area = 0;
+        self.options.preserve_output_collinear = false;
+
+        // contours are already resolved, so strips are the only overlapped parts
+        let result = self
+            .init_with_ortho_contours(&contours, &strips)
+            .and_then(|_| self.try_overlay(OverlayRule::Difference, FillRule::NonZero));
+
+        self.options = options;
+
+        let mut pieces = result?;
+        for contour in pieces.iter_mut().flatten() {
+            contour.halve();
+        }
+
+        // strips collapse into spikes and zero-area holes
+        let mut pieces = pieces.simplified();
+
+        if self.options.min_output_area > 0 {
+            let min_area = self.options.min_output_area;
+            for shape in pieces.iter_mut() {
+                shape.retain(|contour| contour.area().unsigned_abs() >= min_area);
+            }
+            pieces.retain(|shape| !shape.is_empty());
+        }
+
+        Ok(pieces)
+    }
+}
+
+const MIN_COORD: i32 = i32::MIN >> 1;
+const MAX_COORD: i32 = (i32::MAX >> 1) - 1;
+
+#[inline]
+fn double(p: IntPoint) -> Result<IntPoint, OrthoError> {
+    if MIN_COORD <= p.x && p.x <= MAX_COORD && MIN_COORD <= p.y && p.y <= MAX_COORD {
+        Ok(IntPoint::new(p.x << 1, p.y << 1))
+    } else {
+        Err(OrthoError::OutOfRange)
+    }
+}
+
+/// A unit strip which goes along the doubled segment on its positive side,
+/// it's one unit longer than the segment, so strips of a polyline always cover its corners.
+fn cut_strip(a: IntPoint, b: IntPoint) -> Result<IntContour, OrthoError> {
+    Orientation::new([a, b])?;
+    let (a, b) = (double(a)?, double(b)?);
+    let (x0, x1) = if a.x < b.x { (a.x, b.x + 1) } else { (b.x, a.x + 1) };
+    let (y0, y1) = if a.y < b.y { (a.y, b.y + 1) } else { (b.y, a.y + 1) };
+
+    Ok(vec![
+        IntPoint::new(x0, y0),
+        IntPoint::new(x0, y1),
+        IntPoint::new(x1, y1),
+        IntPoint::new(x1, y0),
+    ])
+}
+
+trait Scale {
+    fn doubled(&self) -> Result<IntContour, OrthoError>;
+    fn halve(&mut self);
+}
+
+impl Scale for IntContour {
+    #[inline]
+    fn doubled(&self) -> Result<IntContour, OrthoError> {
+        self.iter().map(|&p| double(p)).collect()
+    }
+
+    #[inline]
+    fn halve(&mut self) {
+        // an odd coordinate is a border of a strip, it goes back to its cut
+        for p in self.iter_mut() {
+            *p = IntPoint::new(p.x >> 1, p.y >> 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::test_util::rect;
+    use alloc::vec::Vec;
+    use i_float::int::point::IntPoint;
+    use i_shape::int::area::Area;
+
+    fn path(points: &[[i32; 2]]) -> Vec<IntPoint> {
+        points.iter().map(|p| IntPoint::new(p[0], p[1])).collect()
+    }
+
+    #[test]
+    fn test_0() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+
+        let subj = [rect(0, 0, 10, 10)];
+        let lines = [path(&[[-5, 5], [15, 5]]), path(&[[5, -5], [5, 15]])];
+
+        let mut pieces = overlay.slice_by_lines(&subj, &lines, FillRule::NonZero).expect("OK");
+        pieces.sort_by_key(|shape| shape[0].iter().min().copied());
+
+        assert_eq!(pieces.len(), 4);
+        for (shape, p) in pieces.iter().zip([[0, 0], [0, 5], [5, 0], [5, 5]]) {
+            assert_eq!(shape.len(), 1);
+            assert_eq!(shape[0].len(), 4);
+            assert_eq!(shape[0].area(), -25);
+            assert!(shape[0].contains(&IntPoint::new(p[0], p[1])));
+        }
+    }
+
+    #[test]
+    fn test_1() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+
+        // a shape with a hole, cut by neighbor rows and by a line which ends inside the shape
+        let subj = [rect(0, 0, 30, 10), rect(12, 3, 18, 7)];
+        let lines = [
+            path(&[[-1, 1], [31, 1]]),
+            path(&[[-1, 2], [31, 2]]),
+            path(&[[30, 5], [20, 5]]),
+        ];
+
+        let pieces = overlay.slice_by_lines(&subj, &lines, FillRule::EvenOdd).expect("OK");
+        assert_eq!(pieces.len(), 3);
+
+        let mut areas: Vec<_> = pieces.iter().map(|shape| -shape.area()).collect();
+        areas.sort_unstable();
+        assert_eq!(areas, [30, 30, 216]);
+
+        let top = pieces.iter().find(|shape| shape.len() == 2).expect("hole is kept");
+        assert_eq!(top[0].len(), 4);
+        assert_eq!(top[1].area(), 24);
+    }
+
+    #[test]
+    fn test_2() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+
+        // a polyline goes down and turns to the left
+        let subj = [rect(0, 0, 10, 10)];
+        let lines = [path(&[[4, 12], [4, 6], [-2, 6]])];
+
+        let pieces = overlay.slice_by_lines(&subj, &lines, FillRule::NonZero).expect("OK");
+        assert_eq!(pieces.len(), 2);
+
+        let mut areas: Vec<_> = pieces.iter().map(|shape| -shape[0].area()).collect();
+        areas.sort_unstable();
+        assert_eq!(areas, [16, 84]);
+        assert!(pieces.iter().all(|shape| shape.len() == 1));
+
+        let lines = [path(&[[0, 0], [5, 5]])];
+        assert!(overlay.slice_by_lines(&subj, &lines, FillRule::NonZero).is_err());
+    }
+}
@@ -0,0 +1,77 @@
+use crate::core::progress::PhaseMonitor;
+use crate::graph::OverlayGraph;
+use crate::graph::boolean::winding_count::ShapeCountBoolean;
+#[cfg(feature = "stats")]
+use crate::graph::node::OverlayNode;
+use crate::ortho::overlay::OrthoOverlay;
+#[cfg(feature = "stats")]
+use crate::ortho::stats::RunStats;
+use i_shape::int::shape::IntShapes;
+
+#[cfg(feature = "stats")]
+impl OrthoOverlay<ShapeCountBoolean> {
+    /// Statistics of the last run, they are reset by every init.
+    #[inline]
+    pub fn last_run_stats(&self) -> &RunStats {
+        &self.stats
+    }
+
+    #[inline]
+    pub(super) fn reset_stats(&mut self) {
+        self.stats = Default::default();
+    }
+
+    #[inline]
+    pub(super) fn record_phase(&mut self, monitor: &PhaseMonitor) {
+        self.stats.durations.set(monitor.phase(), monitor.elapsed());
+    }
+
+    /// Records the counters of the split columns.
+    pub(super) fn record_columns(&mut self) {
+        let stats = &mut self.stats;
+        stats.columns = self.columns.len();
+        stats.segments = 0;
+        stats.split_segments = 0;
+        stats.merged_segments = 0;
+        for column in self.columns.iter() {
+            stats.segments += column.counters.segments;
+            stats.split_segments += column.counters.split_segments;
+            stats.merged_segments += column.counters.merged_segments;
+        }
+    }
+
+    /// Records the counters of the built graph, its links are kept by the overlay rule.
+    pub(super) fn record_graph(&mut self, graph: &OverlayGraph) {
+        self.stats.links = graph.links.len();
+        let cross_nodes = graph
+            .nodes
+            .iter()
+            .filter(|node| matches!(node, OverlayNode::Cross(_)))
+            .count();
+        self.stats.cross_nodes = cross_nodes;
+        self.stats.bridge_nodes = graph.nodes.len() - cross_nodes;
+    }
+
+    pub(super) fn record_shapes(&mut self, shapes: &IntShapes) {
+        self.stats.contours = shapes.iter().map(|shape| shape.len()).sum();
+        self.stats.holes = self.stats.contours - shapes.len();
+    }
+}
+
+#[cfg(not(feature = "stats"))]
+impl OrthoOverlay<ShapeCountBoolean> {
+    #[inline(always)]
+    pub(super) fn reset_stats(&mut self) {}
+
+    #[inline(always)]
+    pub(super) fn record_phase(&mut self, _monitor: &PhaseMonitor) {}
+
+    #[inline(always)]
+    pub(super) fn record_columns(&mut self) {}
+
+    #[inline(always)]
+    pub(super) fn record_graph(&mut self, _graph: &OverlayGraph) {}
+
+    #[inline(always)]
+    pub(super) fn record_shapes(&mut self, _shapes: &IntShapes) {}
+}
@@ -0,0 +1,254 @@
+use crate::core::layout::Layout;
+use crate::core::shape_type::ShapeType;
+use crate::core::winding::WindingCount;
+use crate::graph::boolean::winding_count::ShapeCountBoolean;
+use crate::ortho::error::OrthoError;
+use crate::ortho::mapper::Mapper;
+use crate::ortho::overlay::OrthoOverlay;
+use crate::ortho::stream::{BoundsHint, ContourSource};
+use i_float::int::point::IntPoint;
+use i_float::int::rect::IntRect;
+
+impl OrthoOverlay<ShapeCountBoolean> {
+    /// Reads the contours from a source in two passes: the first pass finds the bounds (if there is no hint)
+    /// and counts segments per column, the second one adds the segments. Contours are never copied.
+    /// The input is always split by columns, `solver.sweep_axis` is ignored.
+    /// - `source`: Subject and clip contours, the source is read twice.
+    /// - `hint`: Optional bounds of the source.
+    /// - Returns: An error if a contour goes out of the hint bounds, diagonal segments are skipped as in [`Self::init_with_ortho_contours`].
+    pub fn init_with_source<S: ContourSource>(
+        &mut self,
+        source: &mut S,
+        hint: Option<BoundsHint>,
+    ) -> Result<(), OrthoError> {
+        let hint = if let Some(hint) = hint {
+            hint
+        } else {
+            let mut rect: Option<IntRect> = None;
+            let mut points_count = 0;
+            source.for_each_contour(&mut |contour, _| {
+                points_count += contour.len();
+                for p in contour.iter() {
+                    if let Some(rect) = &mut rect {
+                        rect.add_point(p);
+                    } else {
+                        rect = Some(IntRect::with_point(*p));
+                    }
+                }
+            });
+
+            if let Some(rect) = rect {
+                BoundsHint::new(rect, points_count)
+            } else {
+                self.columns.clear();
+                return Ok(());
+            }
+        };
+
+        let mut mapper = Mapper::new(self.layout_with_hint(&hint));
+        let mut is_inside = true;
+        source.for_each_contour(&mut |contour, _| {
+            if is_inside && contour.is_inside(&hint.rect) {
+                mapper.add_ortho_contour(contour);
+            } else {
+                is_inside = false;
+            }
+        });
+
+        if !is_inside {
+            return Err(OrthoError::OutOfRange);
+        }
+
+        self.init_columns_with_mapper(mapper);
+
+        let mut stream = OrthoStream::with_overlay(self, hint.rect.clone());
+        let mut result = Ok(());
+        source.for_each_contour(&mut |contour, shape_type| {
+            if result.is_ok() {
+                result = stream.add_contour(contour, shape_type);
+            }
+        });
+
+        result
+    }
+
+    /// Starts a single pass input, columns are laid out by the hint and contours are added one by one.
+    /// The input is always split by columns, `solver.sweep_axis` is ignored.
+    /// - `hint`: Bounds of the input, every added contour must be inside.
+    /// - Returns: A stream to add contours to.
+    pub fn stream(&mut self, hint: BoundsHint) -> OrthoStream<'_> {
+        let mapper = Mapper::new(self.layout_with_hint(&hint));
+        self.init_columns_with_mapper(mapper);
+        OrthoStream::with_overlay(self, hint.rect)
+    }
+
+    /// Adds subject and clip contours from an iterator in a single pass, they are split by columns as [`Self::stream`] does.
+    /// - `hint`: Bounds of the input, every contour must be inside.
+    /// - `contours`: Contours with their shape types.
+    /// - Returns: An error if a contour goes out of the hint bounds, diagonal segments are skipped as in [`Self::init_with_ortho_contours`].
+    pub fn init_with_iter<I, C>(&mut self, hint: BoundsHint, contours: I) -> Result<(), OrthoError>
+    where
+        I: IntoIterator<Item = (C, ShapeType)>,
+        C: AsRef<[IntPoint]>,
+    {
+        let mut stream = self.stream(hint);
+        for (contour, shape_type) in contours {
+            stream.add_contour(contour.as_ref(), shape_type)?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn layout_with_hint(&self, hint: &BoundsHint) -> Layout {
+        Layout::with_rect(hint.rect.clone(), hint.points_count, self.options.min_count_per_column_power)
+    }
+}
+
+/// Single pass input of an [`OrthoOverlay`], created by [`OrthoOverlay::stream`].
+pub struct OrthoStream<'a> {
+    overlay: &'a mut OrthoOverlay<ShapeCountBoolean>,
+    rect: IntRect,
+    subj_count: usize,
+    clip_count: usize,
+}
+
+impl<'a> OrthoStream<'a> {
+    #[inline]
+    fn with_overlay(overlay: &'a mut OrthoOverlay<ShapeCountBoolean>, rect: IntRect) -> Self {
+        Self {
+            overlay,
+            rect,
+            subj_count: 0,
+            clip_count: 0,
+        }
+    }
+
+    /// Adds a contour, contours of every shape type are indexed in the order they are added.
+    /// - Returns: An error if the contour goes out of the hint bounds, diagonal segments are skipped.
+    pub fn add_contour(&mut self, contour: &[IntPoint], shape_type: ShapeType) -> Result<(), OrthoError> {
+        if !contour.is_inside(&self.rect) {
+            return Err(OrthoError::OutOfRange);
+        }
+
+        let counter = match shape_type {
+            ShapeType::Subject => &mut self.subj_count,
+            ShapeType::Clip => &mut self.clip_count,
+        };
+        let source = self.overlay.contour_source(shape_type, *counter)?;
+        *counter += 1;
+
+        let (direct, invert) = ShapeCountBoolean::with_shape_type(shape_type);
+        self.overlay.add_ortho_contour(contour, direct, invert, source)
+    }
+}
+
+trait InsideRect {
+    fn is_inside(&self, rect: &IntRect) -> bool;
+}
+
+impl InsideRect for [IntPoint] {
+    #[inline]
+    fn is_inside(&self, rect: &IntRect) -> bool {
+        self.iter().all(|&p| rect.contains(p))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::core::shape_type::ShapeType;
+    use crate::core::sweep::SweepAxis;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::ortho::stream::BoundsHint;
+    use crate::test_util::rect;
+    use alloc::vec::Vec;
+    use i_float::int::point::IntPoint;
+    use i_float::int::rect::IntRect;
+    use i_shape::int::shape::IntContour;
+    use rand::{Rng, thread_rng};
+
+    // rects inside the hint bounds
+    fn hinted_rects(n: usize) -> Vec<IntContour> {
+        let mut rng = thread_rng();
+        (0..n)
+            .map(|_| {
+                let x = rng.gen_range(0..20);
+                let y = rng.gen_range(0..20);
+                rect(x, y, x + rng.gen_range(1..10), y + rng.gen_range(1..10))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_0() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.options.min_count_per_column_power = 2;
+        let hint = BoundsHint::new(IntRect::new(0, 30, 0, 30), 64);
+
+        for _ in 0..100 {
+            let subj = hinted_rects(8);
+            let clip = hinted_rects(8);
+
+            overlay.init_with_ortho_contours(&subj, &clip).expect("OK");
+            let expected = overlay.overlay(OverlayRule::Difference, FillRule::NonZero);
+
+            // the source is a callback which walks the contours on every call
+            let mut source = |f: &mut dyn FnMut(&[IntPoint], ShapeType)| {
+                subj.iter().for_each(|c| f(c, ShapeType::Subject));
+                clip.iter().for_each(|c| f(c, ShapeType::Clip));
+            };
+
+            overlay.init_with_source(&mut source, None).expect("OK");
+            assert_eq!(overlay.overlay(OverlayRule::Difference, FillRule::NonZero), expected);
+
+            overlay.init_with_source(&mut source, Some(hint.clone())).expect("OK");
+            let result = overlay.overlay(OverlayRule::Difference, FillRule::NonZero);
+            assert_eq!(result.len(), expected.len());
+
+            let contours = subj
+                .iter()
+                .map(|c| (c, ShapeType::Subject))
+                .chain(clip.iter().map(|c| (c, ShapeType::Clip)));
+            overlay.init_with_iter(hint.clone(), contours).expect("OK");
+            assert_eq!(overlay.overlay(OverlayRule::Difference, FillRule::NonZero), result);
+        }
+    }
+
+    #[test]
+    fn test_1() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        let hint = BoundsHint::new(IntRect::new(0, 10, 0, 10), 8);
+
+        let mut stream = overlay.stream(hint.clone());
+        stream.add_contour(&rect(0, 0, 5, 5), ShapeType::Subject).expect("OK");
+        assert!(stream.add_contour(&rect(5, 5, 11, 10), ShapeType::Subject).is_err());
+
+        let mut source = |f: &mut dyn FnMut(&[IntPoint], ShapeType)| {
+            f(&rect(0, 0, 5, 5), ShapeType::Subject);
+            f(&rect(-1, 0, 5, 5), ShapeType::Clip);
+        };
+        assert!(overlay.init_with_source(&mut source, Some(hint)).is_err());
+        assert!(overlay.init_with_source(&mut source, None).is_ok());
+    }
+
+    #[test]
+    fn test_2() {
+        // streamed input ignores the sweep axis
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.solver.sweep_axis = SweepAxis::Rows;
+        overlay.options.canonical_output = true;
+        let tall = [rect(0, 0, 10, 100)];
+
+        overlay.init_with_ortho_contours(&tall, &[]).expect("OK");
+        assert!(overlay.transposed);
+        let expected = overlay.overlay(OverlayRule::Subject, FillRule::NonZero);
+
+        let hint = BoundsHint::new(IntRect::new(0, 10, 0, 100), 4);
+        let contours = tall.iter().map(|c| (c, ShapeType::Subject));
+        overlay.init_with_iter(hint, contours).expect("OK");
+        assert!(!overlay.transposed);
+        assert_eq!(overlay.overlay(OverlayRule::Subject, FillRule::NonZero), expected);
+    }
+}
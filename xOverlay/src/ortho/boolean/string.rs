@@ -0,0 +1,328 @@
+use crate::core::fill_rule::FillRule;
+use crate::core::layout::Layout;
+use crate::graph::boolean::winding_count::ShapeCountBoolean;
+use crate::ortho::column::Column;
+use crate::ortho::error::OrthoError;
+use crate::ortho::orientation::Orientation;
+use crate::ortho::overlay::OrthoOverlay;
+use crate::ortho::string::{ClippedStrings, Place, StringClipOptions};
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+use i_shape::int::path::IntPath;
+use i_shape::int::shape::IntContour;
+
+impl OrthoOverlay<ShapeCountBoolean> {
+    /// Clips open orthogonal polylines (strings) by shapes.
+    ///
+    /// Strings are split by the clip boundaries in the same columns which are used by the overlay,
+    /// then every piece is classified by the filled sides of the clip.
+    /// - `strings`: Open polylines, every segment must be vertical or horizontal.
+    /// - `clip`: Clip contours.
+    /// - `fill_rule`: Fill rule to determine the clip area.
+    /// - `options`: Which pieces to collect and how boundary pieces are counted.
+    /// - Returns: The inside and the outside pieces, or an error if a string segment is not orthogonal.
+    pub fn clip_strings(
+        &mut self,
+        strings: &[IntPath],
+        clip: &[IntContour],
+        fill_rule: FillRule,
+        options: &StringClipOptions,
+    ) -> Result<ClippedStrings, OrthoError> {
+        let mut result = ClippedStrings::default();
+        if strings.is_empty() || !options.inside && !options.outside {
+            return Ok(result);
+        }
+
+        self.init_with_strings(strings, clip)?;
+        self.for_each_column(|column| column.prepare_string_fills(fill_rule))?;
+
+        let walker = PieceWalker {
+            layout: &self.layout,
+            columns: &self.columns,
+        };
+
+        let mut builder = PathBuilder {
+            options,
+            path: Vec::new(),
+            inside: None,
+        };
+
+        for string in strings.iter() {
+            for w in string.windows(2) {
+                let (a, b) = (w[0], w[1]);
+                if a == b {
+                    continue;
+                }
+                builder.start_segment();
+                walker.walk(a, b, |p0, p1, place| builder.add(p0, p1, place, &mut result));
+            }
+            builder.flush(&mut result);
+        }
+
+        Ok(result)
+    }
+}
+
+struct PieceWalker<'a> {
+    layout: &'a Layout,
+    columns: &'a [Column<ShapeCountBoolean>],
+}
+
+impl PieceWalker<'_> {
+    fn walk<F: FnMut(IntPoint, IntPoint, Place)>(&self, a: IntPoint, b: IntPoint, mut f: F) {
+        let orientation = if let Ok(orientation) = Orientation::new([a, b]) {
+            orientation
+        } else {
+            return;
+        };
+
+        let mut pieces = Vec::new();
+        match orientation {
+            Orientation::Vertical => {
+                let column = &self.columns[self.layout.index(a.x)];
+                let (mut y, max_y) = if a.y < b.y { (a.y, b.y) } else { (b.y, a.y) };
+                while y < max_y {
+                    if let Some((end, place)) = column.vr_piece(a.x, y) {
+                        pieces.push((IntPoint::new(a.x, y), IntPoint::new(a.x, end), place));
+                        y = end;
+                    } else {
+                        debug_assert!(false, "string piece is not found");
+                        return;
+                    }
+                }
+            }
+            Orientation::Horizontal => {
+                let mut index = self.layout.index(a.x.min(b.x));
+                let (mut x, max_x) = if a.x < b.x { (a.x, b.x) } else { (b.x, a.x) };
+                while x < max_x {
+                    if let Some((end, place)) = self.columns[index].hz_piece(a.y, x) {
+                        pieces.push((IntPoint::new(x, a.y), IntPoint::new(end, a.y), place));
+                        x = end;
+                    } else if index + 1 < self.columns.len() {
+                        // the piece starts at the next column
+                        index += 1;
+                    } else {
+                        debug_assert!(false, "string piece is not found");
+                        return;
+                    }
+                }
+            }
+        }
+
+        if a < b {
+            for (p0, p1, place) in pieces {
+                f(p0, p1, place);
+            }
+        } else {
+            for (p0, p1, place) in pieces.into_iter().rev() {
+                f(p1, p0, place);
+            }
+        }
+    }
+}
+
+struct PathBuilder<'a> {
+    options: &'a StringClipOptions,
+    path: IntPath,
+    inside: Option<bool>,
+}
+
+impl PathBuilder<'_> {
+    #[inline]
+    fn start_segment(&mut self) {
+        // the last point is a string vertex, it must be kept
+        if !self.path.is_empty() {
+            let last = self.path[self.path.len() - 1];
+            self.path.push(last);
+        }
+    }
+
+    #[inline]
+    fn add(&mut self, p0: IntPoint, p1: IntPoint, place: Place, result: &mut ClippedStrings) {
+        let inside = self.options.boundary.resolve(place);
+        if inside != self.inside {
+            self.flush(result);
+            self.inside = inside;
+            if inside.is_some() {
+                self.path.push(p0);
+                self.path.push(p1);
+            }
+            return;
+        }
+
+        if let Some(last) = self.path.last_mut() {
+            // extend the current segment
+            *last = p1;
+        }
+    }
+
+    #[inline]
+    fn flush(&mut self, result: &mut ClippedStrings) {
+        if self.path.len() >= 2 {
+            match self.inside {
+                Some(true) if self.options.inside => result.inside.push(self.path.clone()),
+                Some(false) if self.options.outside => result.outside.push(self.path.clone()),
+                _ => {}
+            }
+        }
+        self.path.clear();
+        self.inside = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::error::OrthoError;
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::ortho::string::{BoundaryRule, StringClipOptions};
+    use crate::test_util::rect;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_float::int::point::IntPoint;
+
+    fn path(points: &[[i32; 2]]) -> Vec<IntPoint> {
+        points.iter().map(|p| IntPoint::new(p[0], p[1])).collect()
+    }
+
+    fn options(boundary: BoundaryRule) -> StringClipOptions {
+        StringClipOptions {
+            inside: true,
+            outside: true,
+            boundary,
+        }
+    }
+
+    #[test]
+    fn test_0() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+
+        let clip = [rect(0, 0, 10, 10)];
+        let strings = [
+            path(&[[-5, 5], [15, 5]]),
+            path(&[[5, 15], [5, 5], [20, 5]]),
+        ];
+
+        let result = overlay
+            .clip_strings(&strings, &clip, FillRule::NonZero, &options(BoundaryRule::Inside))
+            .expect("OK");
+
+        assert_eq!(
+            result.inside,
+            vec![
+                path(&[[0, 5], [10, 5]]),
+                path(&[[5, 10], [5, 5], [10, 5]]),
+            ]
+        );
+        assert_eq!(
+            result.outside,
+            vec![
+                path(&[[-5, 5], [0, 5]]),
+                path(&[[10, 5], [15, 5]]),
+                path(&[[5, 15], [5, 10]]),
+                path(&[[10, 5], [20, 5]]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_1() {
+        let clip = [rect(0, 0, 10, 10)];
+        // goes along the bottom and the right sides of the clip
+        let strings = [path(&[[-5, 0], [10, 0], [10, 20]])];
+
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        let result = overlay
+            .clip_strings(&strings, &clip, FillRule::NonZero, &options(BoundaryRule::Inside))
+            .expect("OK");
+        assert_eq!(result.inside, vec![path(&[[0, 0], [10, 0], [10, 10]])]);
+        assert_eq!(result.outside, vec![path(&[[-5, 0], [0, 0]]), path(&[[10, 10], [10, 20]])]);
+
+        let result = overlay
+            .clip_strings(&strings, &clip, FillRule::NonZero, &options(BoundaryRule::Outside))
+            .expect("OK");
+        assert!(result.inside.is_empty());
+        assert_eq!(result.outside, strings.to_vec());
+
+        let result = overlay
+            .clip_strings(&strings, &clip, FillRule::NonZero, &options(BoundaryRule::Skip))
+            .expect("OK");
+        assert!(result.inside.is_empty());
+        assert_eq!(result.outside, vec![path(&[[-5, 0], [0, 0]]), path(&[[10, 10], [10, 20]])]);
+    }
+
+    #[test]
+    fn test_2() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.options.min_count_per_column_power = 2;
+
+        // two overlapped clips and a long string going in the negative direction
+        let clip = [rect(0, 0, 20, 10), rect(10, 0, 30, 10), rect(40, 0, 50, 10)];
+        let strings = [path(&[[60, 5], [-10, 5]])];
+
+        let result = overlay
+            .clip_strings(&strings, &clip, FillRule::NonZero, &StringClipOptions::default())
+            .expect("OK");
+        assert_eq!(result.inside, vec![path(&[[50, 5], [40, 5]]), path(&[[30, 5], [0, 5]])]);
+        assert!(result.outside.is_empty());
+
+        let result = overlay
+            .clip_strings(&strings, &clip, FillRule::EvenOdd, &StringClipOptions::default())
+            .expect("OK");
+        assert_eq!(
+            result.inside,
+            vec![path(&[[50, 5], [40, 5]]), path(&[[30, 5], [20, 5]]), path(&[[10, 5], [0, 5]])]
+        );
+    }
+
+    #[test]
+    fn test_3() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+
+        // strings end in the middle of clip edges
+        let clip = [rect(0, 0, 10, 10)];
+        let strings = [path(&[[-5, 0], [5, 0]]), path(&[[0, -5], [0, 5]])];
+
+        let result = overlay
+            .clip_strings(&strings, &clip, FillRule::NonZero, &options(BoundaryRule::Skip))
+            .expect("OK");
+        assert!(result.inside.is_empty());
+        assert_eq!(result.outside, vec![path(&[[-5, 0], [0, 0]]), path(&[[0, -5], [0, 0]])]);
+    }
+
+    #[test]
+    fn test_4() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+
+        let clip = [rect(0, 0, 10, 10)];
+        let strings = [path(&[[0, 0], [5, 5]])];
+
+        assert!(
+            overlay
+                .clip_strings(&strings, &clip, FillRule::NonZero, &StringClipOptions::default())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_5() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+
+        let subj = [rect(0, 0, 10, 10)];
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
+        let expected = overlay.overlay(OverlayRule::Subject, FillRule::NonZero);
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
+
+        // the last string is not orthogonal, so the earlier ones are not added
+        let clip = [rect(20, 0, 30, 10)];
+        let strings = [path(&[[0, 5], [30, 5]]), path(&[[0, 0], [5, 5]])];
+        assert!(matches!(
+            overlay.clip_strings(&strings, &clip, FillRule::NonZero, &StringClipOptions::default()),
+            Err(OrthoError::NotValidPath)
+        ));
+
+        assert_eq!(overlay.overlay(OverlayRule::Subject, FillRule::NonZero), expected);
+    }
+}
@@ -0,0 +1,345 @@
+use crate::core::fill::{CLIP_BOTTOM, CLIP_TOP, SUBJ_BOTTOM, SUBJ_TOP, SegmentFill};
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay_rule::OverlayRule;
+use crate::graph::boolean::winding_count::ShapeCountBoolean;
+use crate::graph::node::OverlayNode;
+use crate::ortho::error::OrthoError;
+use crate::ortho::overlay::OrthoOverlay;
+use crate::ortho::svg::SvgOptions;
+use alloc::string::String;
+use core::fmt::Write;
+use i_float::int::point::IntPoint;
+use i_float::int::rect::IntRect;
+use i_shape::int::area::Area;
+use i_shape::int::shape::IntContour;
+
+impl OrthoOverlay<ShapeCountBoolean> {
+    /// Runs the overlay and draws every stage of it into an SVG picture, which makes a bug report self-contained.
+    ///
+    /// Layers go from the bottom to the top: the column borders, the subject (blue) and the clip (red) contours,
+    /// split segments coloured by their fill, graph nodes and the result with an arrow on the first edge
+    /// of every contour, black for outer contours and orange for holes.
+    /// Every segment and node has a `<title>` with its coordinates and fill.
+    /// - `subj`: Subject contours.
+    /// - `clip`: Clip contours.
+    /// - `overlay_rule`: The boolean operation rule to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas.
+    /// - `options`: Size of the picture and which stages to draw.
+    /// - Returns: SVG text, or an error if a contour is not orthogonal.
+    pub fn debug_svg(
+        &mut self,
+        subj: &[IntContour],
+        clip: &[IntContour],
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        options: &SvgOptions,
+    ) -> Result<String, OrthoError> {
+        // the picture is drawn in the input coordinates, so it is always swept by columns
+        self.init_with_sweep(subj, clip, false)?;
+        self.build_custom_graph(fill_rule, overlay_rule)?;
+
+        let rect = IntRect::with_iter(subj.iter().chain(clip.iter()).flatten()).unwrap_or(IntRect {
+            min_x: 0,
+            max_x: 0,
+            min_y: 0,
+            max_y: 0,
+        });
+        let mut svg = Svg::new(rect.clone(), options);
+
+        if options.columns {
+            svg.begin_group("columns", "stroke=\"#bbb\" stroke-dasharray=\"4 4\"");
+            for i in 0..self.layout.count() {
+                let (min, max) = self.layout.borders(i);
+                svg.line(IntPoint::new(min, rect.min_y), IntPoint::new(min, rect.max_y), None);
+                if i + 1 == self.layout.count() {
+                    svg.line(IntPoint::new(max, rect.min_y), IntPoint::new(max, rect.max_y), None);
+                }
+            }
+            svg.end_group();
+        }
+
+        svg.begin_group("subj", "fill=\"#3070e0\" fill-opacity=\"0.2\" stroke=\"#3070e0\"");
+        subj.iter().for_each(|contour| svg.polygon(contour));
+        svg.end_group();
+
+        svg.begin_group("clip", "fill=\"#e03030\" fill-opacity=\"0.2\" stroke=\"#e03030\"");
+        clip.iter().for_each(|contour| svg.polygon(contour));
+        svg.end_group();
+
+        if options.segments {
+            svg.begin_group("segments", "stroke-width=\"2\"");
+            for column in self.columns.iter() {
+                for (s, &fill) in column.vr_segments.iter().zip(column.vr_fills.iter()) {
+                    svg.segment(IntPoint::new(s.pos, s.min), IntPoint::new(s.pos, s.max), fill);
+                }
+                for (s, &fill) in column.hz_segments.iter().zip(column.hz_fills.iter()) {
+                    svg.segment(IntPoint::new(s.min, s.pos), IntPoint::new(s.max, s.pos), fill);
+                }
+            }
+            svg.end_group();
+        }
+
+        let graph = if let Some(graph) = &mut self.graph {
+            graph
+        } else {
+            return Ok(svg.finish());
+        };
+
+        if options.nodes {
+            svg.begin_group("nodes", "stroke=\"none\"");
+            for (id, node) in graph.nodes.iter().enumerate() {
+                let (link, count) = match node {
+                    OverlayNode::Bridge(indices) => (indices[0], 2),
+                    OverlayNode::Cross(indices) => (indices[0], indices.len()),
+                };
+                let link = &graph.links[link];
+                let point = if link.a.id == id { link.a.point } else { link.b.point };
+                svg.node(point, count);
+            }
+            svg.end_group();
+        }
+
+        let shapes = graph.extract_shapes(overlay_rule);
+        svg.begin_group("result", "fill=\"#20a040\" fill-opacity=\"0.3\" stroke=\"#000\"");
+        for shape in shapes.iter() {
+            svg.shape(shape);
+        }
+        svg.end_group();
+
+        Ok(svg.finish())
+    }
+}
+
+struct Svg {
+    out: String,
+    rect: IntRect,
+    scale: f64,
+    margin: f64,
+    height: f64,
+}
+
+impl Svg {
+    fn new(rect: IntRect, options: &SvgOptions) -> Self {
+        let margin = options.margin as f64;
+        let extent = (rect.width().max(rect.height()) as f64).max(1.0);
+        let scale = (options.size as f64 - 2.0 * margin).max(1.0) / extent;
+        let width = 2.0 * margin + rect.width() as f64 * scale;
+        let height = 2.0 * margin + rect.height() as f64 * scale;
+
+        let mut out = String::new();
+        _ = writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" viewBox=\"0 0 {:.0} {:.0}\">",
+            width, height, width, height
+        );
+        out.push_str(concat!(
+            "<defs>\n",
+            "<marker id=\"outer\" viewBox=\"0 0 10 10\" refX=\"5\" refY=\"5\" markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\">",
+            "<path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"#000\"/></marker>\n",
+            "<marker id=\"hole\" viewBox=\"0 0 10 10\" refX=\"5\" refY=\"5\" markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\">",
+            "<path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"#f08000\"/></marker>\n",
+            "</defs>\n",
+        ));
+
+        Self {
+            out,
+            rect,
+            scale,
+            margin,
+            height,
+        }
+    }
+
+    #[inline]
+    fn x(&self, x: i32) -> f64 {
+        self.margin + (x as i64 - self.rect.min_x as i64) as f64 * self.scale
+    }
+
+    #[inline]
+    fn y(&self, y: i32) -> f64 {
+        // svg y goes down
+        self.height - self.margin - (y as i64 - self.rect.min_y as i64) as f64 * self.scale
+    }
+
+    fn begin_group(&mut self, id: &str, style: &str) {
+        _ = writeln!(self.out, "<g id=\"{}\" {}>", id, style);
+    }
+
+    fn end_group(&mut self) {
+        self.out.push_str("</g>\n");
+    }
+
+    fn line(&mut self, a: IntPoint, b: IntPoint, attributes: Option<&str>) {
+        let (x0, y0, x1, y1) = (self.x(a.x), self.y(a.y), self.x(b.x), self.y(b.y));
+        _ = write!(
+            self.out,
+            "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\"",
+            x0, y0, x1, y1
+        );
+        if let Some(attributes) = attributes {
+            self.out.push(' ');
+            self.out.push_str(attributes);
+        }
+        self.out.push_str("/>\n");
+    }
+
+    fn path_data(&mut self, contour: &[IntPoint]) {
+        for (i, p) in contour.iter().enumerate() {
+            let command = if i == 0 { 'M' } else { 'L' };
+            _ = write!(self.out, "{} {:.2} {:.2} ", command, self.x(p.x), self.y(p.y));
+        }
+        self.out.push('Z');
+    }
+
+    fn polygon(&mut self, contour: &IntContour) {
+        self.out.push_str("<path d=\"");
+        self.path_data(contour);
+        self.out.push_str("\"/>\n");
+    }
+
+    fn segment(&mut self, a: IntPoint, b: IntPoint, fill: SegmentFill) {
+        let color = fill_color(fill);
+        let (x0, y0, x1, y1) = (self.x(a.x), self.y(a.y), self.x(b.x), self.y(b.y));
+        _ = writeln!(
+            self.out,
+            "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"{}\"><title>({}, {}) - ({}, {}) fill {:04b}</title></line>",
+            x0, y0, x1, y1, color, a.x, a.y, b.x, b.y, fill
+        );
+    }
+
+    fn node(&mut self, p: IntPoint, count: usize) {
+        let (x, y) = (self.x(p.x), self.y(p.y));
+        if count == 2 {
+            _ = writeln!(
+                self.out,
+                "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"3\" fill=\"#20a040\"><title>bridge ({}, {})</title></circle>",
+                x, y, p.x, p.y
+            );
+        } else {
+            _ = writeln!(
+                self.out,
+                "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"8\" height=\"8\" fill=\"#a020c0\"><title>cross ({}, {}) of {} links</title></rect>",
+                x - 4.0,
+                y - 4.0,
+                p.x,
+                p.y,
+                count
+            );
+        }
+    }
+
+    fn shape(&mut self, shape: &[IntContour]) {
+        self.out.push_str("<path fill-rule=\"evenodd\" d=\"");
+        for contour in shape.iter() {
+            self.path_data(contour);
+            self.out.push(' ');
+        }
+        self.out.push_str("\"/>\n");
+
+        for (i, contour) in shape.iter().enumerate() {
+            if contour.len() < 2 {
+                continue;
+            }
+            // an arrow in the middle of the first edge shows the direction
+            let (a, b) = (contour[0], contour[1]);
+            let mid = IntPoint::new(a.x / 2 + b.x / 2, a.y / 2 + b.y / 2);
+            let kind = if i == 0 { "outer" } else { "hole" };
+            let attributes = alloc::format!(
+                "stroke=\"none\" marker-end=\"url(#{})\" class=\"{}\" data-area=\"{}\"",
+                kind,
+                kind,
+                contour.area_two()
+            );
+            self.line(a, mid, Some(&attributes));
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.out.push_str("</svg>\n");
+        self.out
+    }
+}
+
+/// Subject fills are blue, clip fills are red, a side which is filled by both is purple.
+#[inline]
+fn fill_color(fill: SegmentFill) -> &'static str {
+    let subj = fill & (SUBJ_TOP | SUBJ_BOTTOM) != 0;
+    let clip = fill & (CLIP_TOP | CLIP_BOTTOM) != 0;
+    match (subj, clip) {
+        (true, true) => "#a020c0",
+        (true, false) => "#3070e0",
+        (false, true) => "#e03030",
+        (false, false) => "#999",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::ortho::svg::SvgOptions;
+    use crate::test_util::rect;
+
+    #[test]
+    fn test_0() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.options.min_count_per_column_power = 2;
+
+        let subj = [rect(0, 0, 30, 30)];
+        let clip = [rect(10, 10, 20, 20), rect(25, -10, 40, 5)];
+        let svg = overlay
+            .debug_svg(
+                &subj,
+                &clip,
+                OverlayRule::Difference,
+                FillRule::NonZero,
+                &SvgOptions::default(),
+            )
+            .expect("OK");
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        for id in ["columns", "subj", "clip", "segments", "nodes", "result"] {
+            assert!(svg.contains(&alloc::format!("<g id=\"{}\"", id)));
+        }
+
+        // the result is a shape with a hole
+        assert_eq!(svg.matches("class=\"outer\"").count(), 1);
+        assert_eq!(svg.matches("class=\"hole\"").count(), 1);
+        assert!(svg.contains("<title>bridge (0, 0)</title>"));
+        assert!(svg.contains("<title>bridge (10, 10)</title>"));
+        // the clip crosses the subject at (30, 5) and (25, 0)
+        assert!(svg.contains("<title>bridge (30, 5)</title>"));
+        // the bottom side of the hole is filled by the subject on both sides and by the clip above
+        assert!(svg.contains("<title>(10, 10) - (20, 10) fill 0111</title>"));
+        assert_eq!(svg.matches("fill 0001</title>").count(), 2);
+    }
+
+    #[test]
+    fn test_1() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        let options = SvgOptions {
+            columns: false,
+            segments: false,
+            nodes: false,
+            ..Default::default()
+        };
+
+        let svg = overlay
+            .debug_svg(
+                &[rect(0, 0, 10, 20)],
+                &[],
+                OverlayRule::Subject,
+                FillRule::NonZero,
+                &options,
+            )
+            .expect("OK");
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"420\" height=\"800\""));
+        assert!(!svg.contains("<g id=\"nodes\""));
+        assert!(!svg.contains("<g id=\"columns\""));
+        assert!(!svg.contains("<g id=\"segments\""));
+        assert_eq!(svg.matches("class=\"outer\"").count(), 1);
+    }
+}
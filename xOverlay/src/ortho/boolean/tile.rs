@@ -0,0 +1,331 @@
+use crate::core::fill_rule::FillRule;
+use crate::core::options::IntOverlayOptions;
+use crate::core::overlay_rule::OverlayRule;
+use crate::core::solver::Solver;
+use crate::graph::boolean::winding_count::ShapeCountBoolean;
+use crate::ortho::error::OrthoError;
+use crate::ortho::overlay::OrthoOverlay;
+use crate::ortho::tile::{Tile, TileContour, TileGrid};
+use alloc::vec;
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+use i_float::int::rect::IntRect;
+use i_shape::int::shape::{IntContour, IntShapes};
+#[cfg(feature = "multithreading")]
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+
+impl OrthoOverlay<ShapeCountBoolean> {
+    /// Performs the overlay tile by tile on a fixed grid and returns the result of every tile.
+    ///
+    /// Every tile runs its own overlay only with the input contours which touch the tile or its halo,
+    /// so the memory is limited by the densest tile. Tiles are processed in parallel if `solver.multithreading`
+    /// is on and there are at least `solver.min_parallel_tiles` of them.
+    /// - `subj`: Subject contours.
+    /// - `clip`: Clip contours.
+    /// - `overlay_rule`: The boolean operation rule to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas.
+    /// - `grid`: The tile grid.
+    /// - Returns: Not empty tiles sorted by row and column. Shapes are cropped by the tile rectangle,
+    ///   edges on the tile border are tagged, so the tiles can be stitched later.
+    ///   `OrthoError::OutOfRange` if the tile width or height is zero, or the error of a tile overlay.
+    ///
+    /// Note: `min_output_area` is not applied to tiles, a piece of a shape is usually smaller than the shape.
+    pub fn overlay_tiles(
+        &mut self,
+        subj: &[IntContour],
+        clip: &[IntContour],
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        grid: &TileGrid,
+    ) -> Result<Vec<Tile>, OrthoError> {
+        let tiles = self.tile_shapes(subj, clip, overlay_rule, fill_rule, grid)?;
+        Ok(tiles
+            .into_iter()
+            .map(|(job, shapes)| {
+                let rect = grid.rect(job.column, job.row);
+                let shapes = shapes
+                    .into_iter()
+                    .map(|shape| shape.into_iter().map(|points| TileContour::new(points, &rect)).collect())
+                    .collect();
+                Tile {
+                    column: job.column,
+                    row: job.row,
+                    rect,
+                    shapes,
+                }
+            })
+            .collect())
+    }
+
+    /// Performs the overlay tile by tile on a fixed grid and stitches the tiles back into seamless shapes.
+    /// - `subj`: Subject contours.
+    /// - `clip`: Clip contours.
+    /// - `overlay_rule`: The boolean operation rule to apply.
+    /// - `fill_rule`: Fill rule to determine filled areas.
+    /// - `grid`: The tile grid.
+    /// - Returns: The same shapes as [`OrthoOverlay::overlay`] produces, or the error of [`Self::overlay_tiles`].
+    pub fn overlay_tiled(
+        &mut self,
+        subj: &[IntContour],
+        clip: &[IntContour],
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        grid: &TileGrid,
+    ) -> Result<IntShapes, OrthoError> {
+        let tiles = self.tile_shapes(subj, clip, overlay_rule, fill_rule, grid)?;
+        let contours: Vec<IntContour> = tiles
+            .into_iter()
+            .flat_map(|(_, shapes)| shapes.into_iter().flatten())
+            .collect();
+
+        // tiles never overlap, they only share borders
+        self.init_with_ortho_contours(&contours, &[])?;
+        self.try_overlay(OverlayRule::Subject, FillRule::NonZero)
+    }
+
+    fn tile_shapes(
+        &self,
+        subj: &[IntContour],
+        clip: &[IntContour],
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        grid: &TileGrid,
+    ) -> Result<Vec<(TileJob, IntShapes)>, OrthoError> {
+        if grid.tile_width == 0 || grid.tile_height == 0 {
+            return Err(OrthoError::OutOfRange);
+        }
+
+        let jobs = TileJob::collect(subj, clip, grid);
+
+        let mut options = self.options;
+        options.min_output_area = 0;
+        #[cfg(feature = "provenance")]
+        {
+            options.track_sources = false;
+        }
+
+        let run = |job: &TileJob| -> Result<Option<(TileJob, IntShapes)>, OrthoError> {
+            if self.solver.is_cancelled() {
+                return Err(OrthoError::Cancelled);
+            }
+            let shapes = job.run(subj, clip, overlay_rule, fill_rule, grid, options)?;
+            Ok(if shapes.is_empty() { None } else { Some((job.clone(), shapes)) })
+        };
+
+        #[cfg(feature = "multithreading")]
+        let tiles: Result<Vec<_>, OrthoError> = if self.solver.is_parallel(jobs.len(), self.solver.min_parallel_tiles)
+        {
+            let min_len = self.solver.min_task_len(jobs.len());
+            self.solver
+                .install(|| jobs.par_iter().with_min_len(min_len).map(run).collect())
+        } else {
+            jobs.iter().map(run).collect()
+        };
+
+        #[cfg(not(feature = "multithreading"))]
+        let tiles: Result<Vec<_>, OrthoError> = jobs.iter().map(run).collect();
+
+        Ok(tiles?.into_iter().flatten().collect())
+    }
+}
+
+#[derive(Clone)]
+struct TileJob {
+    column: i32,
+    row: i32,
+    subj: Vec<usize>,
+    clip: Vec<usize>,
+}
+
+impl TileJob {
+    fn collect(subj: &[IntContour], clip: &[IntContour], grid: &TileGrid) -> Vec<TileJob> {
+        // (row, column, index), the lowest bit of the index is the clip flag
+        let mut marks = Vec::new();
+        for (bit, contours) in [(0, subj), (1, clip)] {
+            for (index, contour) in contours.iter().enumerate() {
+                let rect = if let Some(rect) = IntRect::with_points(contour) {
+                    rect
+                } else {
+                    continue;
+                };
+                let (c0, r0, c1, r1) = grid.range(&rect);
+                for row in r0..=r1 {
+                    for column in c0..=c1 {
+                        marks.push((row, column, (index << 1) | bit));
+                    }
+                }
+            }
+        }
+
+        marks.sort_unstable();
+
+        let mut jobs: Vec<TileJob> = Vec::new();
+        for (row, column, index) in marks {
+            let is_same = jobs.last().is_some_and(|job| job.row == row && job.column == column);
+            if !is_same {
+                jobs.push(TileJob {
+                    column,
+                    row,
+                    subj: Vec::new(),
+                    clip: Vec::new(),
+                });
+            }
+            let job = jobs.last_mut().unwrap();
+            if index & 1 == 0 {
+                job.subj.push(index >> 1);
+            } else {
+                job.clip.push(index >> 1);
+            }
+        }
+
+        jobs
+    }
+
+    fn run(
+        &self,
+        subj: &[IntContour],
+        clip: &[IntContour],
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        grid: &TileGrid,
+        options: IntOverlayOptions,
+    ) -> Result<IntShapes, OrthoError> {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean> {
+            options,
+            solver: Solver::serial(),
+            ..Default::default()
+        };
+
+        let subj: Vec<IntContour> = self.subj.iter().map(|&i| subj[i].clone()).collect();
+        let clip: Vec<IntContour> = self.clip.iter().map(|&i| clip[i].clone()).collect();
+
+        overlay.init_with_ortho_contours(&subj, &clip)?;
+        // a failed tile must not be stitched as an empty one
+        let shapes = overlay.try_overlay(overlay_rule, fill_rule)?;
+        if shapes.is_empty() {
+            return Ok(shapes);
+        }
+
+        let r = grid.rect(self.column, self.row);
+        let window = vec![vec![
+            IntPoint::new(r.min_x, r.min_y),
+            IntPoint::new(r.max_x, r.min_y),
+            IntPoint::new(r.max_x, r.max_y),
+            IntPoint::new(r.min_x, r.max_y),
+        ]];
+        let contours: Vec<IntContour> = shapes.into_iter().flatten().collect();
+
+        overlay.init_with_ortho_contours(&contours, &window)?;
+        overlay.try_overlay(OverlayRule::Intersect, FillRule::NonZero)
+    }
+}
+
+impl TileContour {
+    fn new(points: IntContour, rect: &IntRect) -> Self {
+        let mut borders = Vec::with_capacity(points.len());
+        for (i, &a) in points.iter().enumerate() {
+            let b = points[(i + 1) % points.len()];
+            let is_border = a.x == b.x && (a.x == rect.min_x || a.x == rect.max_x)
+                || a.y == b.y && (a.y == rect.min_y || a.y == rect.max_y);
+            borders.push(is_border);
+        }
+        Self { points, borders }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::error::OrthoError;
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::ortho::tile::TileGrid;
+    use crate::test_util::{random_rects, rect};
+    use alloc::vec::Vec;
+    use i_float::int::point::IntPoint;
+    use i_shape::int::area::Area;
+    use i_shape::int::shape::IntShapes;
+
+    fn sorted(shapes: &IntShapes) -> Vec<(i64, usize, Option<IntPoint>)> {
+        let mut keys: Vec<_> = shapes
+            .iter()
+            .map(|shape| (shape.area(), shape.len(), shape[0].iter().min().copied()))
+            .collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    #[test]
+    fn test_0() {
+        let subj = [rect(1, 0, 19, 10)];
+        let mut grid = TileGrid::new(10, 20);
+        grid.origin = IntPoint::new(0, -5);
+
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        let tiles = overlay
+            .overlay_tiles(&subj, &[], OverlayRule::Subject, FillRule::NonZero, &grid)
+            .expect("OK");
+
+        assert_eq!(tiles.len(), 2);
+        for (tile, column) in tiles.iter().zip([0, 1]) {
+            assert_eq!((tile.column, tile.row), (column, 0));
+            assert_eq!(tile.shapes.len(), 1);
+            let contour = &tile.shapes[0][0];
+            assert_eq!(contour.points.len(), 4);
+            assert_eq!(contour.points.area(), -90);
+
+            // only the edge on x = 10 is shared
+            assert_eq!(contour.borders.iter().filter(|&&b| b).count(), 1);
+            let i = contour.borders.iter().position(|&b| b).unwrap();
+            assert_eq!(contour.points[i].x, 10);
+        }
+
+        let shapes = overlay
+            .overlay_tiled(&subj, &[], OverlayRule::Subject, FillRule::NonZero, &grid)
+            .expect("OK");
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 1);
+        assert_eq!(shapes[0][0].len(), 4);
+        assert_eq!(shapes[0][0].area(), -180);
+    }
+
+    #[test]
+    fn test_1() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        for i in 0..200 {
+            let subj = random_rects(12, 40);
+            let clip = random_rects(6, 40);
+
+            let mut grid = TileGrid::new(7 + i % 11, 5 + i % 13);
+            grid.halo = i % 3;
+            overlay.solver.multithreading = i % 2 == 0;
+
+            for rule in [OverlayRule::Union, OverlayRule::Difference, OverlayRule::Xor] {
+                overlay.init_with_ortho_contours(&subj, &clip).expect("OK");
+                let expected = overlay.overlay(rule, FillRule::NonZero);
+                let stitched = overlay
+                    .overlay_tiled(&subj, &clip, rule, FillRule::NonZero, &grid)
+                    .expect("OK");
+                assert_eq!(sorted(&stitched), sorted(&expected));
+            }
+        }
+    }
+
+    #[test]
+    fn test_2() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        let subj = [rect(0, 0, 10, 10)];
+
+        for grid in [TileGrid::new(0, 5), TileGrid::new(5, 0)] {
+            assert!(matches!(
+                overlay.overlay_tiles(&subj, &[], OverlayRule::Subject, FillRule::NonZero, &grid),
+                Err(OrthoError::OutOfRange)
+            ));
+            assert!(matches!(
+                overlay.overlay_tiled(&subj, &[], OverlayRule::Subject, FillRule::NonZero, &grid),
+                Err(OrthoError::OutOfRange)
+            ));
+        }
+    }
+}
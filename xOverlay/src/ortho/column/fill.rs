@@ -1,6 +1,7 @@
 use crate::core::fill::{FillStrategy, NONE, SegmentFill};
 use crate::core::winding::WindingCount;
 use crate::ortho::column::Column;
+use crate::ortho::error::OrthoError;
 use crate::ortho::segment::OrthoSegment;
 use alloc::vec::Vec;
 
@@ -14,7 +15,8 @@ struct CountBuffer<C> {
 }
 
 impl<C: WindingCount> Column<C> {
-    pub(crate) fn fill_with_strategy<F: FillStrategy<C>>(&mut self) {
+    /// - Returns: `OrthoError::WindingOverflow` if a count overflows.
+    pub(crate) fn fill_with_strategy<F: FillStrategy<C>>(&mut self) -> Result<(), OrthoError> {
         self.vr_fills.resize(self.vr_segments.len(), NONE);
         self.hz_fills.resize(self.hz_segments.len(), NONE);
 
@@ -28,7 +30,7 @@ impl<C: WindingCount> Column<C> {
             // add all vr in range s.min < y0
             while j < self.vr_segments.len() && self.vr_segments[j].min < y0 {
                 let vr = &self.vr_segments[j];
-                let fill = buffer.add_vr::<F>(vr);
+                let fill = buffer.add_vr::<F>(vr).ok_or(OrthoError::WindingOverflow)?;
                 unsafe {
                     *self.vr_fills.get_unchecked_mut(j) = fill;
                 }
@@ -38,7 +40,7 @@ impl<C: WindingCount> Column<C> {
             // add all hz with same y
             while i < self.hz_segments.len() && self.hz_segments[i].pos == y0 {
                 let hz = &self.hz_segments[i];
-                let fill = buffer.add_hz::<F>(hz);
+                let fill = buffer.add_hz::<F>(hz).ok_or(OrthoError::WindingOverflow)?;
                 unsafe {
                     *self.hz_fills.get_unchecked_mut(i) = fill;
                 }
@@ -48,7 +50,7 @@ impl<C: WindingCount> Column<C> {
 
         while j < self.vr_segments.len() {
             let vr = &self.vr_segments[j];
-            let (_, fill) = F::add_and_fill(vr.count, C::empty());
+            let (_, fill) = F::add_and_fill(vr.count, C::empty()).ok_or(OrthoError::WindingOverflow)?;
             unsafe {
                 *self.vr_fills.get_unchecked_mut(j) = fill;
             }
@@ -56,6 +58,8 @@ impl<C: WindingCount> Column<C> {
         }
 
         debug_assert_eq!(buffer.counts.len(), 1);
+
+        Ok(())
     }
 }
 
@@ -71,7 +75,12 @@ impl<C: WindingCount> CountBuffer<C> {
     }
 
     #[inline]
-    fn add_hz<F: FillStrategy<C>>(&mut self, s: &OrthoSegment<C>) -> SegmentFill {
+    fn add_hz<F: FillStrategy<C>>(&mut self, s: &OrthoSegment<C>) -> Option<SegmentFill> {
+        if !s.count.is_not_empty() {
+            // the segment does not change counts (e.g. an open string), so it only reads them
+            return self.fill_at::<F>(s, s.min);
+        }
+
         // __a0____a1____a2
         match self.counts.binary_search_by(|a| a.pos.cmp(&s.min)) {
             Ok(i0) => {
@@ -94,11 +103,11 @@ impl<C: WindingCount> CountBuffer<C> {
     }
 
     #[inline]
-    fn add_hz_00<F: FillStrategy<C>>(&mut self, s: &OrthoSegment<C>, i0: usize) -> SegmentFill {
+    fn add_hz_00<F: FillStrategy<C>>(&mut self, s: &OrthoSegment<C>, i0: usize) -> Option<SegmentFill> {
         // __c2____a0____c2(cx)____a1____c2____[a2]
 
         let c2 = unsafe { self.counts.get_unchecked(i0).count };
-        let (cx, fill) = F::add_and_fill(s.count, c2);
+        let (cx, fill) = F::add_and_fill(s.count, c2)?;
 
         let a0 = Anchor {
             pos: s.min,
@@ -110,17 +119,17 @@ impl<C: WindingCount> CountBuffer<C> {
         };
 
         self.counts.splice(i0..i0, [a0, a1]);
-        fill
+        Some(fill)
     }
 
     #[inline]
-    fn add_hz_01<F: FillStrategy<C>>(&mut self, s: &OrthoSegment<C>, i1: usize) -> SegmentFill {
+    fn add_hz_01<F: FillStrategy<C>>(&mut self, s: &OrthoSegment<C>, i1: usize) -> Option<SegmentFill> {
         // __c1____a0____c1(cx)____[a1]____c2____[a2]
 
         let c1 = unsafe { self.counts.get_unchecked(i1).count };
         let c2 = unsafe { self.counts.get_unchecked(i1 + 1).count };
 
-        let (cx, fill) = F::add_and_fill(s.count, c1);
+        let (cx, fill) = F::add_and_fill(s.count, c1)?;
 
         if cx == c2 {
             // move a1 to a0
@@ -135,17 +144,17 @@ impl<C: WindingCount> CountBuffer<C> {
             self.counts.insert(i1, a0);
         }
 
-        fill
+        Some(fill)
     }
 
     #[inline]
-    fn add_hz_10<F: FillStrategy<C>>(&mut self, s: &OrthoSegment<C>, i0: usize) -> SegmentFill {
+    fn add_hz_10<F: FillStrategy<C>>(&mut self, s: &OrthoSegment<C>, i0: usize) -> Option<SegmentFill> {
         // __c0____[a0]____c2(cx)____a1____c2____[a2]
 
         let c0 = unsafe { self.counts.get_unchecked(i0).count };
         let c2 = unsafe { self.counts.get_unchecked(i0 + 1).count };
 
-        let (cx, fill) = F::add_and_fill(s.count, c2);
+        let (cx, fill) = F::add_and_fill(s.count, c2)?;
 
         if c0 == cx {
             // move a0 to a1
@@ -159,18 +168,18 @@ impl<C: WindingCount> CountBuffer<C> {
             self.counts.insert(i0 + 1, a1);
         }
 
-        fill
+        Some(fill)
     }
 
     #[inline]
-    fn add_hz_11<F: FillStrategy<C>>(&mut self, s: &OrthoSegment<C>, i0: usize) -> SegmentFill {
+    fn add_hz_11<F: FillStrategy<C>>(&mut self, s: &OrthoSegment<C>, i0: usize) -> Option<SegmentFill> {
         // __c0____[a0]____c1(cx)____[a1]____c2____[a2]
 
         let c0 = unsafe { self.counts.get_unchecked(i0).count };
         let c1 = unsafe { self.counts.get_unchecked(i0 + 1).count };
         let c2 = unsafe { self.counts.get_unchecked(i0 + 2).count };
 
-        let (cx, fill) = F::add_and_fill(s.count, c1);
+        let (cx, fill) = F::add_and_fill(s.count, c1)?;
 
         let rem_a0 = c0 == cx;
         let rem_a1 = c2 == cx;
@@ -189,19 +198,24 @@ impl<C: WindingCount> CountBuffer<C> {
             }
         };
 
-        fill
+        Some(fill)
+    }
+
+    #[inline]
+    fn add_vr<F: FillStrategy<C>>(&self, s: &OrthoSegment<C>) -> Option<SegmentFill> {
+        self.fill_at::<F>(s, s.pos)
     }
 
     #[inline]
-    fn add_vr<F: FillStrategy<C>>(&self, s: &OrthoSegment<C>) -> SegmentFill {
-        let index = match self.counts.binary_search_by(|a| a.pos.cmp(&s.pos)) {
+    fn fill_at<F: FillStrategy<C>>(&self, s: &OrthoSegment<C>, pos: i32) -> Option<SegmentFill> {
+        let index = match self.counts.binary_search_by(|a| a.pos.cmp(&pos)) {
             Ok(index) => index + 1,
             Err(index) => index,
         };
 
         let count = self.counts[index].count;
-        let (_, fill) = F::add_and_fill(s.count, count);
-        fill
+        let (_, fill) = F::add_and_fill(s.count, count)?;
+        Some(fill)
     }
 }
 
@@ -221,12 +235,16 @@ mod tests {
                     min: 0,
                     max: 10,
                     count: ShapeCountBoolean { subj: -1, clip: 0 },
+                    #[cfg(feature = "provenance")]
+                    source: 0,
                 },
                 OrthoSegment {
                     pos: 10,
                     min: 0,
                     max: 10,
                     count: ShapeCountBoolean { subj: 1, clip: 0 },
+                    #[cfg(feature = "provenance")]
+                    source: 0,
                 },
             ],
             hz_segments: vec![
@@ -235,21 +253,29 @@ mod tests {
                     min: 0,
                     max: 10,
                     count: ShapeCountBoolean { subj: -1, clip: 0 },
+                    #[cfg(feature = "provenance")]
+                    source: 0,
                 },
                 OrthoSegment {
                     pos: 0,
                     min: 0,
                     max: 10,
                     count: ShapeCountBoolean { subj: 1, clip: 0 },
+                    #[cfg(feature = "provenance")]
+                    source: 0,
                 },
             ],
             vr_fills: vec![],
             hz_fills: vec![],
             border_points: vec![],
+            end_points: vec![],
+            sources: Default::default(),
             min: 0,
             max: 10,
             links_start: 0,
             links_count: 0,
+            #[cfg(feature = "stats")]
+            counters: Default::default(),
         };
 
         assert_eq!(column.border_points.len(), 0);
@@ -26,6 +26,10 @@ impl<C> Column<C> {
             if F::is_included(fill) {
                 if let Some(slot) = it.next() {
                     *slot = OverlayLink::with_vr(vr.pos, vr.min, vr.max, fill);
+                    #[cfg(feature = "provenance")]
+                    {
+                        slot.source = vr.source;
+                    }
                 } else {
                     debug_assert!(false, "iterator underrun");
                     break;
@@ -37,6 +41,10 @@ impl<C> Column<C> {
             if F::is_included(fill) {
                 if let Some(slot) = it.next() {
                     *slot = OverlayLink::with_hz(hz.pos, hz.min, hz.max, fill);
+                    #[cfg(feature = "provenance")]
+                    {
+                        slot.source = hz.source;
+                    }
                 } else {
                     debug_assert!(false, "iterator underrun");
                     break;
@@ -1,11 +1,17 @@
 mod fill;
 mod filter;
+mod raster;
 mod split;
+mod string;
 
 use crate::core::fill::SegmentFill;
 use crate::ortho::mapper::Counter;
 use crate::ortho::segment::OrthoSegment;
+#[cfg(feature = "stats")]
+use crate::ortho::stats::SplitCounters;
+use crate::sub::source::SourceGroups;
 use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
 use i_shape::util::reserve::Reserve;
 
 #[derive(Clone)]
@@ -15,10 +21,14 @@ pub(crate) struct Column<C> {
     pub(crate) vr_fills: Vec<SegmentFill>,
     pub(crate) hz_fills: Vec<SegmentFill>,
     pub(crate) border_points: Vec<i32>,
+    pub(crate) end_points: Vec<IntPoint>,
+    pub(crate) sources: SourceGroups,
     pub(crate) min: i32,
     pub(crate) max: i32,
     pub(crate) links_start: usize,
     pub(crate) links_count: usize,
+    #[cfg(feature = "stats")]
+    pub(crate) counters: SplitCounters,
 }
 
 impl<C> Default for Column<C> {
@@ -30,10 +40,14 @@ impl<C> Default for Column<C> {
             vr_fills: Default::default(),
             hz_fills: Default::default(),
             border_points: Default::default(),
+            end_points: Default::default(),
+            sources: Default::default(),
             min: 0,
             max: 0,
             links_start: 0,
             links_count: 0,
+            #[cfg(feature = "stats")]
+            counters: Default::default(),
         }
     }
 }
@@ -47,14 +61,54 @@ impl<C> Column<C> {
         self.hz_segments.reserve_capacity(counter.hz);
         self.border_points.clear();
         self.border_points.reserve_capacity(counter.border_points);
+        self.end_points.clear();
         self.hz_fills.clear();
         self.vr_fills.clear();
+        self.sources.clear();
         self.min = min;
         self.max = max;
+        #[cfg(feature = "stats")]
+        {
+            self.counters = Default::default();
+        }
     }
 
     #[inline(always)]
     pub(crate) fn links_end(&self) -> usize {
         self.links_start + self.links_count
     }
+
+    /// Records the segment counters of the split, the merged segments are already removed.
+    #[cfg(feature = "stats")]
+    #[inline(always)]
+    fn record_split(&mut self, segments: usize, split_segments: usize) {
+        self.counters = SplitCounters {
+            segments,
+            split_segments,
+            merged_segments: split_segments - self.vr_segments.len() - self.hz_segments.len(),
+        };
+    }
+
+    #[cfg(not(feature = "stats"))]
+    #[inline(always)]
+    fn record_split(&mut self, _segments: usize, _split_segments: usize) {}
+
+    /// Count of bytes allocated by the column buffers, the column itself is not included.
+    pub(crate) fn held_bytes(&self) -> usize {
+        (self.vr_segments.capacity() + self.hz_segments.capacity()) * size_of::<OrthoSegment<C>>()
+            + (self.vr_fills.capacity() + self.hz_fills.capacity()) * size_of::<SegmentFill>()
+            + self.border_points.capacity() * size_of::<i32>()
+            + self.end_points.capacity() * size_of::<IntPoint>()
+            + self.sources.held_bytes()
+    }
+
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.vr_segments.shrink_to_fit();
+        self.hz_segments.shrink_to_fit();
+        self.vr_fills.shrink_to_fit();
+        self.hz_fills.shrink_to_fit();
+        self.border_points.shrink_to_fit();
+        self.end_points.shrink_to_fit();
+        self.sources.shrink_to_fit();
+    }
 }
\ No newline at end of file
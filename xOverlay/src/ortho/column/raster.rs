@@ -0,0 +1,18 @@
+use crate::core::fill::InclusionFilterStrategy;
+use crate::core::overlay_rule::OverlayRule;
+use crate::ortho::column::Column;
+use crate::ortho::raster::RasterBuffer;
+
+impl<C> Column<C> {
+    pub(crate) fn rasterize_with_filter<F: InclusionFilterStrategy>(
+        &self,
+        overlay_rule: OverlayRule,
+        buffer: &mut RasterBuffer,
+    ) {
+        for (hz, &fill) in self.hz_segments.iter().zip(&self.hz_fills) {
+            if F::is_included(fill) {
+                buffer.add_edge(hz.pos, hz.min, hz.max, overlay_rule.is_fill_top(fill));
+            }
+        }
+    }
+}
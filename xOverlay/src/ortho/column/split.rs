@@ -1,5 +1,6 @@
 use crate::core::winding::WindingCount;
 use crate::ortho::column::Column;
+use crate::ortho::error::OrthoError;
 use crate::ortho::segment::OrthoSegment;
 use crate::sub::merge::Merge;
 use alloc::vec::Vec;
@@ -12,10 +13,12 @@ struct Mark {
 }
 
 impl<C: WindingCount + Clone> Column<C> {
-    pub(crate) fn split(&mut self) {
+    /// - Returns: `OrthoError::WindingOverflow` if a merged count overflows.
+    pub(crate) fn split(&mut self) -> Result<(), OrthoError> {
         if self.hz_segments.is_empty() && self.border_points.is_empty() && self.vr_segments.is_empty() {
-            return
+            return Ok(())
         }
+        let segments_count = self.vr_segments.len() + self.hz_segments.len();
         self.hz_segments.sort_unstable_by_key(|hz| hz.pos);
 
         let mut vr_marks = Vec::with_capacity(self.vr_segments.len().max(4).ilog2() as usize);
@@ -79,19 +82,62 @@ impl<C: WindingCount + Clone> Column<C> {
             }
         }
 
+        if !self.end_points.is_empty() {
+            self.mark_end_points(&mut vr_marks, &mut hz_marks);
+        }
+
         if !vr_marks.is_empty() {
             split_segments(&mut self.vr_segments, vr_marks);
         }
+        let mut split_count = self.vr_segments.len();
         self.vr_segments
             .sort_unstable_by(|vr0, vr1| vr0.min.cmp(&vr1.min).then(vr0.pos.cmp(&vr1.pos)));
-        self.vr_segments.merge_if_needed();
+        self.vr_segments.merge_if_needed(&mut self.sources)?;
 
         if !hz_marks.is_empty() {
             split_segments(&mut self.hz_segments, hz_marks);
         }
+        split_count += self.hz_segments.len();
         self.hz_segments
             .sort_unstable_by(|hz0, hz1| hz0.pos.cmp(&hz1.pos).then(hz0.min.cmp(&hz1.min)));
-        self.hz_segments.merge_if_needed();
+        self.hz_segments.merge_if_needed(&mut self.sources)?;
+
+        self.record_split(segments_count, split_count);
+
+        Ok(())
+    }
+}
+
+impl<C> Column<C> {
+    // free ends of open strings split every segment passing through them
+    fn mark_end_points(&mut self, vr_marks: &mut Vec<Mark>, hz_marks: &mut Vec<Mark>) {
+        self.end_points.sort_unstable_by(|p0, p1| p0.x.cmp(&p1.x).then(p0.y.cmp(&p1.y)));
+        for (ivr, vr) in self.vr_segments.iter().enumerate() {
+            let mut i = self
+                .end_points
+                .partition_point(|p| p.x < vr.pos || p.x == vr.pos && p.y <= vr.min);
+            while i < self.end_points.len() && self.end_points[i].x == vr.pos && self.end_points[i].y < vr.max {
+                vr_marks.push(Mark {
+                    index: ivr as u32,
+                    value: self.end_points[i].y,
+                });
+                i += 1;
+            }
+        }
+
+        self.end_points.sort_unstable_by(|p0, p1| p0.y.cmp(&p1.y).then(p0.x.cmp(&p1.x)));
+        for (ihz, hz) in self.hz_segments.iter().enumerate() {
+            let mut i = self
+                .end_points
+                .partition_point(|p| p.y < hz.pos || p.y == hz.pos && p.x <= hz.min);
+            while i < self.end_points.len() && self.end_points[i].y == hz.pos && self.end_points[i].x < hz.max {
+                hz_marks.push(Mark {
+                    index: ihz as u32,
+                    value: self.end_points[i].x,
+                });
+                i += 1;
+            }
+        }
     }
 }
 
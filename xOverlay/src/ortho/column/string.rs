@@ -0,0 +1,65 @@
+use crate::core::fill::{CLIP_BOTTOM, CLIP_TOP, SegmentFill};
+use crate::core::fill_rule::FillRule;
+use crate::graph::boolean::winding_count::ShapeCountBoolean;
+use crate::ortho::column::Column;
+use crate::ortho::error::OrthoError;
+use crate::ortho::string::Place;
+
+impl Column<ShapeCountBoolean> {
+    /// Splits strings (subject) by clip segments and fills only the clip sides.
+    pub(crate) fn prepare_string_fills(&mut self, fill_rule: FillRule) -> Result<(), OrthoError> {
+        self.split()?;
+
+        // strings are open, their counts are only needed to keep them alive while merging
+        for s in self.vr_segments.iter_mut().chain(self.hz_segments.iter_mut()) {
+            s.count.subj = 0;
+        }
+
+        self.fill_boolean(fill_rule)
+    }
+}
+
+impl<C> Column<C> {
+    /// Finds a split horizontal piece which starts at `(min, pos)`.
+    /// - Returns: The end of the piece and its place.
+    #[inline]
+    pub(crate) fn hz_piece(&self, pos: i32, min: i32) -> Option<(i32, Place)> {
+        let index = self
+            .hz_segments
+            .partition_point(|s| s.pos < pos || s.pos == pos && s.min < min);
+        let s = self.hz_segments.get(index)?;
+        if s.pos != pos || s.min != min {
+            return None;
+        }
+        Some((s.max, self.hz_fills[index].place()))
+    }
+
+    /// Finds a split vertical piece which starts at `(pos, min)`.
+    /// - Returns: The end of the piece and its place.
+    #[inline]
+    pub(crate) fn vr_piece(&self, pos: i32, min: i32) -> Option<(i32, Place)> {
+        let index = self
+            .vr_segments
+            .partition_point(|s| s.min < min || s.min == min && s.pos < pos);
+        let s = self.vr_segments.get(index)?;
+        if s.pos != pos || s.min != min {
+            return None;
+        }
+        Some((s.max, self.vr_fills[index].place()))
+    }
+}
+
+trait ClipPlace {
+    fn place(&self) -> Place;
+}
+
+impl ClipPlace for SegmentFill {
+    #[inline(always)]
+    fn place(&self) -> Place {
+        match (self & CLIP_TOP != 0, self & CLIP_BOTTOM != 0) {
+            (true, true) => Place::Inside,
+            (false, false) => Place::Outside,
+            _ => Place::Boundary,
+        }
+    }
+}
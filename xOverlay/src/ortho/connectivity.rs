@@ -0,0 +1,46 @@
+use alloc::vec::Vec;
+use i_shape::int::shape::{IntContour, IntShape};
+
+/// A group of input contours which overlap or touch each other along an edge.
+#[derive(Debug, Clone)]
+pub struct ConnectedComponent {
+    /// Indices of the input contours, in ascending order.
+    pub contours: Vec<usize>,
+    /// The union of the contours.
+    pub shape: IntShape,
+}
+
+/// A via layer which connects two conductor layers.
+#[derive(Debug, Clone, Copy)]
+pub struct ViaLayer<'a> {
+    pub contours: &'a [IntContour],
+    /// Index of the conductor layer below the vias.
+    pub bottom: usize,
+    /// Index of the conductor layer above the vias.
+    pub top: usize,
+}
+
+/// Reference to a contour of a layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayerContour {
+    pub layer: usize,
+    pub index: usize,
+}
+
+/// A merged shape of a conductor layer.
+#[derive(Debug, Clone)]
+pub struct LayerShape {
+    pub layer: usize,
+    pub shape: IntShape,
+}
+
+/// A group of contours which are electrically connected through their layers and vias.
+#[derive(Debug, Clone, Default)]
+pub struct Net {
+    /// Conductor contours of the net, the layer is an index in the conductor layers.
+    pub contours: Vec<LayerContour>,
+    /// Via contours of the net, the layer is an index in the via layers.
+    pub vias: Vec<LayerContour>,
+    /// Merged conductor shapes of the net.
+    pub shapes: Vec<LayerShape>,
+}
@@ -1,4 +1,11 @@
 #[derive(Debug)]
 pub enum OrthoError {
-    NotValidPath
-}
\ No newline at end of file
+    NotValidPath,
+    OutOfRange,
+    /// The run is aborted by `Solver::cancel`.
+    Cancelled,
+    /// A winding count exceeds the `i16` range, e.g. more than 32767 contours are stacked.
+    WindingOverflow,
+    /// Sources are requested, but `options.track_sources` was not set at the init.
+    SourcesNotTracked,
+}
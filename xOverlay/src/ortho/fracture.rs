@@ -0,0 +1,50 @@
+/// Direction of the cuts used by [`OrthoOverlay::fracture`](crate::ortho::overlay::OrthoOverlay).
+/// - `Horizontal`: Shapes are cut by horizontal lines into horizontal strips.
+/// - `Vertical`: Shapes are cut by vertical lines into vertical strips.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Slicing {
+    #[default]
+    Horizontal,
+    Vertical,
+}
+
+/// Configuration options for fracturing an overlay result into rectangles.
+#[derive(Debug, Clone, Copy)]
+pub struct FractureOptions {
+    /// Preferred direction of the cuts.
+    pub slicing: Slicing,
+
+    /// Maximum width of a figure, `0` means unlimited.
+    pub max_width: u32,
+
+    /// Maximum height of a figure, `0` means unlimited.
+    pub max_height: u32,
+
+    /// Figures thinner than this are treated as slivers, `0` disables the check.
+    ///
+    /// This is a best-effort heuristic: if the preferred slicing produces slivers for a shape,
+    /// the other slicing is tried and the one with fewer slivers is taken. Slivers are not merged
+    /// into their neighbours, so the output may still contain figures thinner than this.
+    pub sliver_size: u32,
+}
+
+impl Default for FractureOptions {
+    fn default() -> Self {
+        Self {
+            slicing: Slicing::Horizontal,
+            max_width: 0,
+            max_height: 0,
+            sliver_size: 0,
+        }
+    }
+}
+
+impl Slicing {
+    #[inline(always)]
+    pub(crate) fn invert(&self) -> Self {
+        match self {
+            Slicing::Horizontal => Slicing::Vertical,
+            Slicing::Vertical => Slicing::Horizontal,
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use crate::core::fill_rule::FillRule;
+use crate::core::options::IntOverlayOptions;
+use crate::core::overlay_rule::OverlayRule;
+use crate::core::solver::Solver;
+use crate::graph::boolean::winding_count::ShapeCountBoolean;
+use crate::ortho::error::OrthoError;
+use crate::ortho::overlay::OrthoOverlay;
+use alloc::vec::Vec;
+use i_shape::int::shape::{IntContour, IntShapes};
+
+/// A complete overlay task: the input contours, the rules and the options.
+/// With the `serde` feature a job can be stored and replayed later, e.g. to reproduce a failing case.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OverlayJob {
+    pub subj: Vec<IntContour>,
+    pub clip: Vec<IntContour>,
+    pub overlay_rule: OverlayRule,
+    pub fill_rule: FillRule,
+    pub options: IntOverlayOptions,
+    pub solver: Solver,
+}
+
+impl OverlayJob {
+    /// Creates a job with the default options and solver.
+    #[inline]
+    pub fn new(subj: Vec<IntContour>, clip: Vec<IntContour>, overlay_rule: OverlayRule, fill_rule: FillRule) -> Self {
+        Self {
+            subj,
+            clip,
+            overlay_rule,
+            fill_rule,
+            options: Default::default(),
+            solver: Default::default(),
+        }
+    }
+
+    /// Runs the job on a new overlay.
+    /// - Returns: The result shapes, or an error if an input contour is not orthogonal.
+    pub fn run(&self) -> Result<IntShapes, OrthoError> {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean> {
+            options: self.options,
+            solver: self.solver.clone(),
+            ..Default::default()
+        };
+        overlay.init_with_ortho_contours(&self.subj, &self.clip)?;
+        overlay.try_overlay(self.overlay_rule, self.fill_rule)
+    }
+}
+
+impl OrthoOverlay<ShapeCountBoolean> {
+    /// Captures a job with the options and the solver of this overlay.
+    #[inline]
+    pub fn job(&self, subj: &[IntContour], clip: &[IntContour], overlay_rule: OverlayRule, fill_rule: FillRule) -> OverlayJob {
+        OverlayJob {
+            subj: subj.to_vec(),
+            clip: clip.to_vec(),
+            overlay_rule,
+            fill_rule,
+            options: self.options,
+            solver: self.solver.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::test_util::rect;
+
+    #[test]
+    fn test_0() {
+        let subj = [rect(0, 0, 10, 10)];
+        let clip = [rect(5, 5, 15, 15)];
+
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.options.min_count_per_column_power = 2;
+        let job = overlay.job(&subj, &clip, OverlayRule::Xor, FillRule::EvenOdd);
+
+        overlay.init_with_ortho_contours(&subj, &clip).expect("OK");
+        let expected = overlay.overlay(OverlayRule::Xor, FillRule::EvenOdd);
+
+        assert_eq!(job.options.min_count_per_column_power, 2);
+        assert_eq!(job.run().expect("OK"), expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_1() {
+        use crate::ortho::job::OverlayJob;
+        use alloc::vec;
+
+        let subj = vec![rect(0, 0, 10, 10)];
+        let clip = vec![rect(5, 5, 15, 15)];
+        let mut job = OverlayJob::new(subj, clip, OverlayRule::Difference, FillRule::NonZero);
+        job.options.min_output_area = 3;
+        job.solver.multithreading = false;
+
+        let json = serde_json::to_string(&job).expect("OK");
+        let restored: OverlayJob = serde_json::from_str(&json).expect("OK");
+
+        assert_eq!(restored.subj, job.subj);
+        assert_eq!(restored.clip, job.clip);
+        assert_eq!(restored.overlay_rule, job.overlay_rule);
+        assert_eq!(restored.fill_rule, job.fill_rule);
+        assert_eq!(restored.options.min_output_area, 3);
+        assert!(!restored.solver.multithreading);
+        assert_eq!(restored.run().expect("OK"), job.run().expect("OK"));
+    }
+}
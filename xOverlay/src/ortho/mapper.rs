@@ -1,6 +1,7 @@
 use alloc::vec;
 use alloc::vec::Vec;
 use crate::core::layout::Layout;
+use i_float::int::point::IntPoint;
 use i_shape::int::shape::IntContour;
 
 #[derive(Default, Clone)]
@@ -11,7 +12,7 @@ pub(crate) struct Counter {
 }
 
 pub(crate) struct Mapper {
-    layout: Layout,
+    pub(crate) layout: Layout,
     pub(crate) columns: Vec<Counter>,
 }
 
@@ -27,14 +28,16 @@ impl Mapper {
 
     pub(crate) fn add_ortho_contours(&mut self, contours: &[IntContour]) {
         for contour in contours {
-            if contour.len() >= 4 {
-                self.add_ortho_contour(contour);
-            }
+            self.add_ortho_contour(contour);
         }
     }
 
     #[inline(always)]
-    fn add_ortho_contour(&mut self, contour: &IntContour) {
+    pub(crate) fn add_ortho_contour(&mut self, contour: &[IntPoint]) {
+        if contour.len() < 4 {
+            return;
+        }
+
         let mut p0 = contour[0];
         for &pi in contour.iter() {
             if pi.x == p0.x {
@@ -60,6 +63,30 @@ impl Mapper {
             p0 = pi;
         }
     }
+
+    /// Splits the mapped columns into `count` groups of about the same count of segments.
+    /// - Returns: Index of the first column of every group, it starts with zero. Groups are never
+    ///   empty, so there can be fewer of them if a single column holds most of the segments.
+    pub(crate) fn column_starts(&self, count: usize) -> Vec<usize> {
+        let total = self.columns.iter().map(|c| (c.hz + c.vr) as u64).sum::<u64>();
+        let mut starts = Vec::with_capacity(count);
+        starts.push(0);
+
+        let mut passed = 0u64;
+        let mut group = 0;
+        for (index, counter) in self.columns.iter().enumerate() {
+            // the next group starts at the next quantile of the segments count
+            if group > 0 && starts.len() < count && passed * count as u64 >= total * starts.len() as u64 {
+                starts.push(index);
+                group = 0;
+            }
+            let segments = counter.hz + counter.vr;
+            passed += segments as u64;
+            group += segments;
+        }
+
+        starts
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,175 @@
+use crate::core::fill::SegmentFill;
+use crate::core::layout::Layout;
+use crate::core::sweep;
+use crate::graph::end::End;
+use crate::graph::link::OverlayLink;
+use crate::graph::node::OverlayNode;
+use crate::ortho::column::Column;
+use crate::ortho::mapper::Mapper;
+use crate::ortho::overlay::OrthoOverlay;
+use crate::ortho::segment::OrthoSegment;
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+use i_shape::int::shape::IntContour;
+
+/// Bytes of the overlay buffers.
+/// - `columns`: Segments, fills and points of the columns.
+/// - `links`: Graph links and their sources.
+/// - `ends`: Sorted link ends and their bins.
+/// - `nodes`: Graph nodes.
+/// - `extraction`: Visited mask and points of the contour extraction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub columns: usize,
+    pub links: usize,
+    pub ends: usize,
+    pub nodes: usize,
+    pub extraction: usize,
+}
+
+impl MemoryUsage {
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.columns + self.links + self.ends + self.nodes + self.extraction
+    }
+}
+
+impl<C> OrthoOverlay<C> {
+    /// Estimates the peak memory of an overlay of `subj` and `clip` without running it.
+    /// The estimate counts segments by columns as the init does. Segments are also split at their
+    /// crossings and source groups are collected if `options.track_sources` is set, neither is
+    /// known before the run, so the real usage of crossing input can be higher.
+    /// - Returns: The estimated bytes, zero if there is nothing to overlay.
+    pub fn estimate_memory(&self, subj: &[IntContour], clip: &[IntContour]) -> MemoryUsage {
+        if self.solver.sweep_axis.is_rows(subj, clip) {
+            return self.estimate_columns_memory(&sweep::transposed(subj), &sweep::transposed(clip));
+        }
+        self.estimate_columns_memory(subj, clip)
+    }
+
+    fn estimate_columns_memory(&self, subj: &[IntContour], clip: &[IntContour]) -> MemoryUsage {
+        let layout = if let Some(layout) = Layout::with_options(subj, clip, &self.options) {
+            layout
+        } else {
+            return MemoryUsage::default();
+        };
+
+        let mut mapper = Mapper::new(layout);
+        mapper.add_ortho_contours(subj);
+        mapper.add_ortho_contours(clip);
+
+        let mut usage = MemoryUsage {
+            columns: mapper.columns.len() * size_of::<Column<C>>(),
+            ..Default::default()
+        };
+
+        let mut segments_count = 0;
+        for counter in mapper.columns.iter() {
+            let count = counter.hz + counter.vr;
+            usage.columns += count * (size_of::<OrthoSegment<C>>() + size_of::<SegmentFill>())
+                + counter.border_points * size_of::<i32>();
+            segments_count += count;
+        }
+
+        // every segment is a link at most, every node joins two link ends at least
+        usage.links = segments_count * size_of::<OverlayLink>();
+        usage.ends = segments_count * size_of::<End>();
+        usage.nodes = segments_count * size_of::<OverlayNode>();
+        usage.extraction = segments_count * (size_of::<IntPoint>() + size_of::<bool>());
+
+        usage
+    }
+
+    /// Bytes held by the overlay buffers, they are reused by the next runs.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let mut usage = MemoryUsage {
+            columns: self.columns.capacity() * size_of::<Column<C>>(),
+            ..Default::default()
+        };
+        for column in self.columns.iter() {
+            usage.columns += column.held_bytes();
+        }
+        if let Some(graph) = &self.graph {
+            graph.add_held_bytes(&mut usage);
+        }
+
+        usage
+    }
+
+    /// Releases the spare capacity of the buffers. The initialized input is kept.
+    pub fn shrink_to_fit(&mut self) {
+        for column in self.columns.iter_mut() {
+            column.shrink_to_fit();
+        }
+        self.columns.shrink_to_fit();
+        if let Some(graph) = &mut self.graph {
+            graph.shrink_to_fit();
+        }
+    }
+
+    /// Releases all buffers, the overlay must be initialized again.
+    pub fn reset(&mut self) {
+        self.layout = Default::default();
+        self.columns = Vec::new();
+        self.graph = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::core::solver::Solver;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::memory::MemoryUsage;
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::test_util::grid;
+
+    #[test]
+    fn test_0() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean> {
+            solver: Solver::serial(),
+            ..Default::default()
+        };
+        overlay.options.min_count_per_column_power = 2;
+
+        assert_eq!(overlay.estimate_memory(&[], &[]), MemoryUsage::default());
+        assert_eq!(overlay.memory_usage().total(), 0);
+
+        // the rects never cross, so every segment is a link
+        let subj = grid(8, 0);
+        let estimate = overlay.estimate_memory(&subj, &[]);
+
+        overlay.init_with_ortho_contours(&subj, &[]).expect("OK");
+        assert_eq!(overlay.overlay(OverlayRule::Subject, FillRule::NonZero).len(), 64);
+
+        let usage = overlay.memory_usage();
+        assert_eq!(usage.links, estimate.links);
+        // the ends are sorted with bins, which are not estimated
+        assert!(usage.ends >= estimate.ends);
+        assert!(usage.columns > 0 && usage.nodes > 0 && usage.extraction > 0);
+    }
+
+    #[test]
+    fn test_1() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+        overlay.options.min_count_per_column_power = 2;
+
+        let (subj, clip) = (grid(16, 0), grid(16, 5));
+        overlay.init_with_ortho_contours(&subj, &clip).expect("OK");
+        let expected = overlay.overlay(OverlayRule::Xor, FillRule::EvenOdd);
+        let usage = overlay.memory_usage();
+
+        overlay.shrink_to_fit();
+        let shrunk = overlay.memory_usage();
+        assert!(shrunk.total() < usage.total());
+        assert_eq!(shrunk.extraction, 0);
+
+        overlay.reset();
+        assert_eq!(overlay.memory_usage().total(), 0);
+
+        // the overlay is reusable after a new init
+        overlay.init_with_ortho_contours(&subj, &clip).expect("OK");
+        assert_eq!(overlay.overlay(OverlayRule::Xor, FillRule::EvenOdd), expected);
+    }
+}
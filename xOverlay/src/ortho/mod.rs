@@ -1,8 +1,21 @@
 pub(crate) mod boolean;
 pub(crate) mod column;
+pub mod connectivity;
 pub mod error;
+pub mod fracture;
+pub mod job;
 pub(crate) mod mapper;
 mod mark;
+pub mod memory;
 pub mod orientation;
 pub mod overlay;
-pub(crate) mod segment;
\ No newline at end of file
+#[cfg(feature = "provenance")]
+pub mod provenance;
+pub mod raster;
+pub(crate) mod segment;
+#[cfg(feature = "stats")]
+pub mod stats;
+pub mod stream;
+pub mod string;
+pub mod svg;
+pub mod tile;
\ No newline at end of file
@@ -4,6 +4,8 @@ use crate::core::options::IntOverlayOptions;
 use crate::core::solver::Solver;
 use crate::graph::OverlayGraph;
 use crate::ortho::column::Column;
+#[cfg(feature = "stats")]
+use crate::ortho::stats::RunStats;
 
 /// This struct is essential for describing and uploading the geometry or shapes required to construct an `OverlayGraph`. It prepares the necessary data for boolean operations.
 pub struct OrthoOverlay<C> {
@@ -12,6 +14,10 @@ pub struct OrthoOverlay<C> {
     pub(crate) layout: Layout,
     pub(crate) columns: Vec<Column<C>>,
     pub(crate) graph: Option<OverlayGraph>,
+    /// The input is swept by rows, so it is stored transposed.
+    pub(crate) transposed: bool,
+    #[cfg(feature = "stats")]
+    pub(crate) stats: RunStats,
 }
 
 impl<C> Default for OrthoOverlay<C> {
@@ -23,6 +29,9 @@ impl<C> Default for OrthoOverlay<C> {
             layout: Default::default(),
             columns: Vec::new(),
             graph: None,
+            transposed: false,
+            #[cfg(feature = "stats")]
+            stats: Default::default(),
         }
     }
-}
\ No newline at end of file
+}
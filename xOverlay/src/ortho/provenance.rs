@@ -0,0 +1,61 @@
+use crate::core::shape_type::ShapeType;
+use crate::ortho::error::OrthoError;
+use crate::sub::source::GROUP_BIT;
+use alloc::vec::Vec;
+use i_shape::int::shape::IntContour;
+
+/// Reference to an input contour.
+/// - `shape_type`: Whether the contour is a subject or a clip contour.
+/// - `index`: Index of the contour in the subject or clip contours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InputContour {
+    pub shape_type: ShapeType,
+    pub index: usize,
+}
+
+/// An output contour with the input contours which produce its edges.
+#[derive(Debug, Clone)]
+pub struct SourcedContour {
+    /// Points of the contour.
+    pub points: IntContour,
+    /// `edges[i]` holds the input contours of the edge from `points[i]` to the next point.
+    /// Several input contours are reported if their edges overlap, subject contours go first.
+    pub edges: Vec<Vec<InputContour>>,
+}
+
+/// An output shape, the first contour is the outer boundary and all subsequent contours are holes.
+pub type SourcedShape = Vec<SourcedContour>;
+
+impl InputContour {
+    #[inline]
+    pub fn new(shape_type: ShapeType, index: usize) -> Self {
+        Self { shape_type, index }
+    }
+
+    /// The plain source id, the shape type is the lowest bit.
+    /// - Returns: `OrthoError::OutOfRange` if the index does not fit the id, it must be less than 2^30.
+    #[inline(always)]
+    pub(crate) fn source(&self) -> Result<u32, OrthoError> {
+        if self.index >= (GROUP_BIT >> 1) as usize {
+            return Err(OrthoError::OutOfRange);
+        }
+        let bit = match self.shape_type {
+            ShapeType::Subject => 0,
+            ShapeType::Clip => 1,
+        };
+        Ok(((self.index as u32) << 1) | bit)
+    }
+
+    #[inline(always)]
+    pub(crate) fn with_source(source: u32) -> Self {
+        let shape_type = if source & 1 == 0 {
+            ShapeType::Subject
+        } else {
+            ShapeType::Clip
+        };
+        Self {
+            shape_type,
+            index: (source >> 1) as usize,
+        }
+    }
+}
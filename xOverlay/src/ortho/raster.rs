@@ -0,0 +1,246 @@
+use crate::ortho::error::OrthoError;
+use alloc::vec;
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+
+/// Describes the pixel grid used by [`OrthoOverlay::rasterize`](crate::ortho::overlay::OrthoOverlay).
+///
+/// Pixel `(0, 0)` is the left-bottom one, its left-bottom corner is placed at `origin`.
+/// Columns grow along x and rows grow along y.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RasterLayout {
+    origin: IntPoint,
+    pixel_width: u32,
+    pixel_height: u32,
+    columns: usize,
+    rows: usize,
+}
+
+impl RasterLayout {
+    /// Creates a pixel grid.
+    /// - `origin`: Left-bottom corner of the grid.
+    /// - `pixel_width`: Width of a single pixel.
+    /// - `pixel_height`: Height of a single pixel.
+    /// - `columns`: Number of pixels along x.
+    /// - `rows`: Number of pixels along y.
+    /// - Returns: The layout, or `OrthoError::OutOfRange` if the pixel width or height is zero.
+    #[inline]
+    pub fn new(
+        origin: IntPoint,
+        pixel_width: u32,
+        pixel_height: u32,
+        columns: usize,
+        rows: usize,
+    ) -> Result<Self, OrthoError> {
+        if pixel_width == 0 || pixel_height == 0 {
+            return Err(OrthoError::OutOfRange);
+        }
+        Ok(Self {
+            origin,
+            pixel_width,
+            pixel_height,
+            columns,
+            rows,
+        })
+    }
+
+    /// Left-bottom corner of the grid.
+    #[inline(always)]
+    pub fn origin(&self) -> IntPoint {
+        self.origin
+    }
+
+    /// Width of a single pixel, it is positive.
+    #[inline(always)]
+    pub fn pixel_width(&self) -> u32 {
+        self.pixel_width
+    }
+
+    /// Height of a single pixel, it is positive.
+    #[inline(always)]
+    pub fn pixel_height(&self) -> u32 {
+        self.pixel_height
+    }
+
+    /// Number of pixels along x.
+    #[inline(always)]
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Number of pixels along y.
+    #[inline(always)]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The same grid with swapped axes.
+    #[inline]
+    pub(crate) fn transposed(&self) -> Self {
+        Self {
+            origin: IntPoint::new(self.origin.y, self.origin.x),
+            pixel_width: self.pixel_height,
+            pixel_height: self.pixel_width,
+            columns: self.rows,
+            rows: self.columns,
+        }
+    }
+
+    /// Area of a single pixel.
+    #[inline(always)]
+    pub fn pixel_area(&self) -> u64 {
+        self.pixel_width as u64 * self.pixel_height as u64
+    }
+
+    #[inline(always)]
+    fn max_x(&self) -> i64 {
+        self.origin.x as i64 + self.columns as i64 * self.pixel_width as i64
+    }
+}
+
+/// Exact per-pixel coverage of an overlay result.
+///
+/// Every pixel stores the covered area, so the coverage fraction is `area / pixel_area`
+/// without any rounding.
+#[derive(Debug, Clone)]
+pub struct Raster {
+    pub layout: RasterLayout,
+    areas: Vec<u64>,
+}
+
+impl Raster {
+    /// Covered area of a pixel, in the range `0..=pixel_area`.
+    #[inline]
+    pub fn area(&self, column: usize, row: usize) -> u64 {
+        self.areas[row * self.layout.columns + column]
+    }
+
+    /// Covered fraction of a pixel, in the range `0.0..=1.0`.
+    #[inline]
+    pub fn coverage(&self, column: usize, row: usize) -> f64 {
+        self.area(column, row) as f64 / self.layout.pixel_area() as f64
+    }
+
+    /// The same raster with swapped axes.
+    pub(crate) fn transposed(&self) -> Self {
+        let layout = self.layout.transposed();
+        let mut areas = vec![0; self.areas.len()];
+        for row in 0..layout.rows {
+            for column in 0..layout.columns {
+                areas[row * layout.columns + column] = self.area(row, column);
+            }
+        }
+        Self { layout, areas }
+    }
+
+    /// Covered areas of all pixels, row by row starting from the bottom row.
+    #[inline]
+    pub fn areas(&self) -> &[u64] {
+        &self.areas
+    }
+
+    /// Coverage scaled to `0..=255` and rounded to the nearest value, row by row starting from the bottom row.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let pixel_area = self.layout.pixel_area() as u128;
+        let half = pixel_area >> 1;
+        self.areas
+            .iter()
+            .map(|&area| ((area as u128 * 255 + half) / pixel_area) as u8)
+            .collect()
+    }
+
+    /// A pixel is set if at least half of it is covered, row by row starting from the bottom row.
+    pub fn to_bits(&self) -> Vec<bool> {
+        let pixel_area = self.layout.pixel_area();
+        self.areas.iter().map(|&area| area << 1 >= pixel_area).collect()
+    }
+}
+
+pub(crate) struct RasterBuffer {
+    layout: RasterLayout,
+    // area of the region above an edge which is inside the edge pixel
+    cells: Vec<i64>,
+    // full pixel heights which are added to every pixel above (including this one)
+    carry: Vec<i64>,
+}
+
+impl RasterBuffer {
+    pub(crate) fn new(layout: RasterLayout) -> Self {
+        let n = layout.columns * layout.rows;
+        Self {
+            layout,
+            cells: vec![0; n],
+            carry: vec![0; n],
+        }
+    }
+
+    /// Adds a horizontal boundary edge. The region above the edge is added when `fill_top` is true
+    /// and subtracted otherwise.
+    #[inline]
+    pub(crate) fn add_edge(&mut self, y: i32, min_x: i32, max_x: i32, fill_top: bool) {
+        let layout = &self.layout;
+        if layout.columns == 0 || layout.rows == 0 {
+            return;
+        }
+
+        let ox = layout.origin.x as i64;
+        let pw = layout.pixel_width as i64;
+        let ph = layout.pixel_height as i64;
+
+        let x0 = (min_x as i64).max(ox);
+        let x1 = (max_x as i64).min(layout.max_x());
+        if x0 >= x1 {
+            return;
+        }
+
+        let dy = y as i64 - layout.origin.y as i64;
+        let (row, partial) = if dy <= 0 {
+            (0, None)
+        } else {
+            let row = (dy / ph) as usize;
+            if row >= layout.rows {
+                return;
+            }
+            (row, Some((row as i64 + 1) * ph - dy))
+        };
+
+        let sign = if fill_top { 1 } else { -1 };
+        let c0 = ((x0 - ox) / pw) as usize;
+        let c1 = ((x1 - ox - 1) / pw) as usize;
+
+        for column in c0..=c1 {
+            let left = ox + column as i64 * pw;
+            let width = x1.min(left + pw) - x0.max(left);
+            let index = row * layout.columns + column;
+            match partial {
+                None => self.carry[index] += sign * width * ph,
+                Some(height) => {
+                    self.cells[index] += sign * width * height;
+                    if row + 1 < layout.rows {
+                        self.carry[index + layout.columns] += sign * width * ph;
+                    }
+                }
+            }
+        }
+    }
+
+    pub(crate) fn into_raster(self) -> Raster {
+        let columns = self.layout.columns;
+        let mut areas = vec![0; self.cells.len()];
+        let mut sum = vec![0i64; columns];
+        for (row_index, row) in areas.chunks_exact_mut(columns.max(1)).enumerate() {
+            let offset = row_index * columns;
+            for (column, area) in row.iter_mut().enumerate() {
+                sum[column] += self.carry[offset + column];
+                let value = sum[column] + self.cells[offset + column];
+                debug_assert!(0 <= value && value as u64 <= self.layout.pixel_area());
+                *area = value.max(0) as u64;
+            }
+        }
+
+        Raster {
+            layout: self.layout,
+            areas,
+        }
+    }
+}
@@ -7,6 +7,8 @@ pub(super) struct OrthoSegment<C> {
     pub(super) min: i32,
     pub(super) max: i32,
     pub(super) count: C,
+    #[cfg(feature = "provenance")]
+    pub(super) source: u32,
 }
 
 impl<C: Clone> OrthoSegment<C> {
@@ -22,6 +24,8 @@ impl<C: Clone> OrthoSegment<C> {
             min: mid,
             max: self.max,
             count: self.count.clone(),
+            #[cfg(feature = "provenance")]
+            source: self.source,
         };
 
         self.max = mid;
@@ -36,6 +40,8 @@ impl<C: Clone> OrthoSegment<C> {
             min: self.min,
             max: mid,
             count: self.count.clone(),
+            #[cfg(feature = "provenance")]
+            source: self.source,
         };
 
         self.min = mid;
@@ -55,8 +61,28 @@ impl<C: WindingCount> CountMergeable<C> for OrthoSegment<C> {
         self.count
     }
 
+    #[cfg(feature = "provenance")]
     #[inline(always)]
-    fn update(&mut self, count: C) {
+    fn source(&self) -> u32 {
+        self.source
+    }
+
+    #[cfg(feature = "provenance")]
+    #[inline(always)]
+    fn update(&mut self, count: C, source: u32) {
+        self.count = count;
+        self.source = source;
+    }
+
+    #[cfg(not(feature = "provenance"))]
+    #[inline(always)]
+    fn source(&self) -> u32 {
+        0
+    }
+
+    #[cfg(not(feature = "provenance"))]
+    #[inline(always)]
+    fn update(&mut self, count: C, _source: u32) {
         self.count = count;
     }
 }
\ No newline at end of file
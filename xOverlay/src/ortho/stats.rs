@@ -0,0 +1,139 @@
+use crate::core::progress::Phase;
+use core::time::Duration;
+
+/// Durations of the overlay phases, a phase which did not run has zero duration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhaseDurations {
+    pub layout: Duration,
+    pub mapping: Duration,
+    pub fill: Duration,
+    pub graph: Duration,
+    pub extraction: Duration,
+}
+
+impl PhaseDurations {
+    #[inline]
+    pub fn get(&self, phase: Phase) -> Duration {
+        match phase {
+            Phase::Layout => self.layout,
+            Phase::Mapping => self.mapping,
+            Phase::Fill => self.fill,
+            Phase::Graph => self.graph,
+            Phase::Extraction => self.extraction,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn set(&mut self, phase: Phase, duration: Duration) {
+        match phase {
+            Phase::Layout => self.layout = duration,
+            Phase::Mapping => self.mapping = duration,
+            Phase::Fill => self.fill = duration,
+            Phase::Graph => self.graph = duration,
+            Phase::Extraction => self.extraction = duration,
+        }
+    }
+
+    #[inline]
+    pub fn total(&self) -> Duration {
+        self.layout + self.mapping + self.fill + self.graph + self.extraction
+    }
+}
+
+/// Statistics of the last overlay run, it starts with the init and is collected only with
+/// the `stats` feature.
+/// - `durations`: Durations of the phases.
+/// - `columns`: Count of columns.
+/// - `segments`: Count of segments before the split.
+/// - `split_segments`: Count of segments after the split.
+/// - `merged_segments`: Count of split segments removed by merging equal ones.
+/// - `links`: Count of links kept by the overlay rule.
+/// - `bridge_nodes`: Count of nodes which join two links.
+/// - `cross_nodes`: Count of nodes which join more than two links.
+/// - `contours`: Count of extracted contours, holes included.
+/// - `holes`: Count of extracted holes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunStats {
+    pub durations: PhaseDurations,
+    pub columns: usize,
+    pub segments: usize,
+    pub split_segments: usize,
+    pub merged_segments: usize,
+    pub links: usize,
+    pub bridge_nodes: usize,
+    pub cross_nodes: usize,
+    pub contours: usize,
+    pub holes: usize,
+}
+
+/// Segment counters of a column, they are collected while columns are split in parallel.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SplitCounters {
+    pub(crate) segments: usize,
+    pub(crate) split_segments: usize,
+    pub(crate) merged_segments: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::core::solver::Solver;
+    use crate::graph::boolean::winding_count::ShapeCountBoolean;
+    use crate::ortho::overlay::OrthoOverlay;
+    use crate::ortho::stats::RunStats;
+    use crate::test_util::rect;
+    use core::time::Duration;
+
+    #[test]
+    fn test_0() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean> {
+            solver: Solver::serial(),
+            ..Default::default()
+        };
+
+        // the frame crosses the rect twice
+        let subj = [rect(0, 0, 10, 10)];
+        let clip = [rect(5, 5, 15, 15)];
+        overlay.init_with_ortho_contours(&subj, &clip).expect("OK");
+        let shapes = overlay.overlay(OverlayRule::Xor, FillRule::EvenOdd);
+        assert_eq!(shapes.len(), 2);
+
+        let stats = *overlay.last_run_stats();
+        assert_eq!(stats.columns, 1);
+        assert_eq!(stats.segments, 8);
+        assert_eq!(stats.split_segments, 12);
+        assert_eq!(stats.merged_segments, 0);
+        assert_eq!(stats.links, 12);
+        assert_eq!(stats.cross_nodes, 2);
+        assert_eq!(stats.bridge_nodes, 8);
+        assert_eq!(stats.contours, 2);
+        assert_eq!(stats.holes, 0);
+        assert!(stats.durations.total() > Duration::ZERO);
+        assert!(stats.durations.fill <= stats.durations.total());
+
+        // a new init resets the stats
+        overlay.init_with_ortho_contours(&[], &[]).expect("OK");
+        assert_eq!(overlay.last_run_stats(), &RunStats::default());
+    }
+
+    #[test]
+    fn test_1() {
+        let mut overlay = OrthoOverlay::<ShapeCountBoolean>::default();
+
+        // equal segments are merged, the hole is bound to the outer rect
+        let subj = [rect(0, 0, 30, 30), rect(10, 10, 20, 20)];
+        let clip = [rect(10, 10, 20, 20)];
+        overlay.init_with_ortho_contours(&subj, &clip).expect("OK");
+        let shapes = overlay.overlay(OverlayRule::Xor, FillRule::NonZero);
+        assert_eq!(shapes.len(), 1);
+
+        let stats = overlay.last_run_stats();
+        assert_eq!(stats.segments, 12);
+        assert_eq!(stats.split_segments, 12);
+        assert_eq!(stats.merged_segments, 4);
+        assert_eq!(stats.links, 8);
+        assert_eq!(stats.contours, 2);
+        assert_eq!(stats.holes, 1);
+    }
+}
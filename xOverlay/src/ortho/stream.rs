@@ -0,0 +1,32 @@
+use crate::core::shape_type::ShapeType;
+use i_float::int::point::IntPoint;
+use i_float::int::rect::IntRect;
+
+/// Bounds of a streamed input, they let the overlay lay out columns before any contour comes.
+/// - `rect`: A rect which contains every point of the input.
+/// - `points_count`: Estimated count of points, it's only used to choose the column width.
+#[derive(Debug, Clone)]
+pub struct BoundsHint {
+    pub rect: IntRect,
+    pub points_count: usize,
+}
+
+/// A source of contours which can be read more than once, e.g. a database cursor which can be restarted.
+pub trait ContourSource {
+    /// Calls `f` for every contour of the source, in the same order on every call.
+    fn for_each_contour(&mut self, f: &mut dyn FnMut(&[IntPoint], ShapeType));
+}
+
+impl<S: FnMut(&mut dyn FnMut(&[IntPoint], ShapeType))> ContourSource for S {
+    #[inline]
+    fn for_each_contour(&mut self, f: &mut dyn FnMut(&[IntPoint], ShapeType)) {
+        self(f)
+    }
+}
+
+impl BoundsHint {
+    #[inline]
+    pub fn new(rect: IntRect, points_count: usize) -> Self {
+        Self { rect, points_count }
+    }
+}
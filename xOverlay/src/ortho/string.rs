@@ -0,0 +1,65 @@
+use alloc::vec::Vec;
+use i_shape::int::path::IntPath;
+
+/// Defines how pieces of a string which lie on a clip boundary are treated by
+/// [`OrthoOverlay::clip_strings`](crate::ortho::overlay::OrthoOverlay).
+/// - `Inside`: Boundary pieces are inside the clip shapes.
+/// - `Outside`: Boundary pieces are outside the clip shapes.
+/// - `Skip`: Boundary pieces are not returned at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoundaryRule {
+    #[default]
+    Inside,
+    Outside,
+    Skip,
+}
+
+/// Configuration options for clipping open polylines by shapes.
+#[derive(Debug, Clone, Copy)]
+pub struct StringClipOptions {
+    /// Collect the pieces which are inside the clip shapes.
+    pub inside: bool,
+
+    /// Collect the pieces which are outside the clip shapes.
+    pub outside: bool,
+
+    /// How pieces collinear with a clip boundary are counted.
+    pub boundary: BoundaryRule,
+}
+
+impl Default for StringClipOptions {
+    fn default() -> Self {
+        Self {
+            inside: true,
+            outside: false,
+            boundary: BoundaryRule::Inside,
+        }
+    }
+}
+
+/// Pieces of clipped strings. Every piece keeps the direction of its string, and pieces of
+/// a string go in the same order as they appear along it.
+#[derive(Debug, Clone, Default)]
+pub struct ClippedStrings {
+    pub inside: Vec<IntPath>,
+    pub outside: Vec<IntPath>,
+}
+
+/// Location of a string piece relative to the clip shapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Place {
+    Inside,
+    Outside,
+    Boundary,
+}
+
+impl BoundaryRule {
+    #[inline(always)]
+    pub(crate) fn resolve(&self, place: Place) -> Option<bool> {
+        match (place, self) {
+            (Place::Inside, _) | (Place::Boundary, BoundaryRule::Inside) => Some(true),
+            (Place::Outside, _) | (Place::Boundary, BoundaryRule::Outside) => Some(false),
+            (Place::Boundary, BoundaryRule::Skip) => None,
+        }
+    }
+}
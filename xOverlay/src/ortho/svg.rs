@@ -0,0 +1,33 @@
+/// Configuration options for [`OrthoOverlay::debug_svg`](crate::ortho::overlay::OrthoOverlay).
+///
+/// Inputs and the result are always drawn, the internal stages can be switched off
+/// to keep the picture readable on big inputs.
+#[derive(Debug, Clone, Copy)]
+pub struct SvgOptions {
+    /// Width and height of the picture in pixels, the geometry keeps its aspect ratio.
+    pub size: u32,
+
+    /// Empty space around the geometry in pixels.
+    pub margin: u32,
+
+    /// Draw the column borders of the layout.
+    pub columns: bool,
+
+    /// Draw split segments coloured by their fill.
+    pub segments: bool,
+
+    /// Draw graph nodes, bridges as circles and crosses as squares.
+    pub nodes: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            size: 800,
+            margin: 20,
+            columns: true,
+            segments: true,
+            nodes: true,
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+use i_float::int::rect::IntRect;
+use i_shape::int::shape::IntContour;
+
+/// A fixed grid of tiles used by [`OrthoOverlay::overlay_tiles`](crate::ortho::overlay::OrthoOverlay).
+/// Tile `(column, row)` covers `[origin.x + column * tile_width, origin.x + (column + 1) * tile_width]`
+/// by x and the same by y, columns and rows may be negative.
+#[derive(Debug, Clone, Copy)]
+pub struct TileGrid {
+    /// Corner of the tile `(0, 0)`.
+    pub origin: IntPoint,
+
+    /// Width of a tile, must be positive, a zero size is rejected with `OrthoError::OutOfRange`.
+    pub tile_width: u32,
+
+    /// Height of a tile, must be positive, a zero size is rejected with `OrthoError::OutOfRange`.
+    pub tile_height: u32,
+
+    /// Input contours which are closer than `halo` to a tile are passed to the tile as well.
+    pub halo: u32,
+}
+
+/// A tile result, shapes are cropped by the tile rectangle.
+#[derive(Debug, Clone)]
+pub struct Tile {
+    pub column: i32,
+    pub row: i32,
+    pub rect: IntRect,
+    pub shapes: Vec<Vec<TileContour>>,
+}
+
+/// An output contour of a tile.
+#[derive(Debug, Clone)]
+pub struct TileContour {
+    /// Points of the contour.
+    pub points: IntContour,
+    /// `borders[i]` is `true` if the edge from `points[i]` to the next point lies on the tile border.
+    /// Only such edges can be shared with the neighbor tiles.
+    pub borders: Vec<bool>,
+}
+
+impl TileGrid {
+    #[inline]
+    pub fn new(tile_width: u32, tile_height: u32) -> Self {
+        Self {
+            origin: IntPoint::ZERO,
+            tile_width,
+            tile_height,
+            halo: 0,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn column(&self, x: i32) -> i32 {
+        (x as i64 - self.origin.x as i64).div_euclid(self.tile_width as i64) as i32
+    }
+
+    #[inline]
+    pub(crate) fn row(&self, y: i32) -> i32 {
+        (y as i64 - self.origin.y as i64).div_euclid(self.tile_height as i64) as i32
+    }
+
+    /// Tiles which touch the rect expanded by the halo.
+    /// - Returns: Min column, min row, max column, max row.
+    #[inline]
+    pub(crate) fn range(&self, rect: &IntRect) -> (i32, i32, i32, i32) {
+        let halo = self.halo as i64;
+        let min_x = (rect.min_x as i64 - halo).max(i32::MIN as i64) as i32;
+        let min_y = (rect.min_y as i64 - halo).max(i32::MIN as i64) as i32;
+        let max_x = (rect.max_x as i64 + halo).min(i32::MAX as i64) as i32;
+        let max_y = (rect.max_y as i64 + halo).min(i32::MAX as i64) as i32;
+        (self.column(min_x), self.row(min_y), self.column(max_x), self.row(max_y))
+    }
+
+    #[inline]
+    pub(crate) fn rect(&self, column: i32, row: i32) -> IntRect {
+        let clamp = |v: i64| v.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+        let min_x = self.origin.x as i64 + column as i64 * self.tile_width as i64;
+        let min_y = self.origin.y as i64 + row as i64 * self.tile_height as i64;
+        IntRect::new(
+            clamp(min_x),
+            clamp(min_x + self.tile_width as i64),
+            clamp(min_y),
+            clamp(min_y + self.tile_height as i64),
+        )
+    }
+}
@@ -0,0 +1,43 @@
+use alloc::vec::Vec;
+
+pub(crate) struct DisjointSet {
+    parents: Vec<usize>,
+}
+
+impl DisjointSet {
+    #[inline]
+    pub(crate) fn new(count: usize) -> Self {
+        Self {
+            parents: (0..count).collect(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn root(&mut self, index: usize) -> usize {
+        let mut root = index;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        // path compression
+        let mut i = index;
+        while self.parents[i] != root {
+            let next = self.parents[i];
+            self.parents[i] = root;
+            i = next;
+        }
+
+        root
+    }
+
+    #[inline]
+    pub(crate) fn union(&mut self, a: usize, b: usize) {
+        let ra = self.root(a);
+        let rb = self.root(b);
+        if ra < rb {
+            self.parents[rb] = ra;
+        } else if rb < ra {
+            self.parents[ra] = rb;
+        }
+    }
+}
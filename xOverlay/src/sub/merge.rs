@@ -1,40 +1,47 @@
 use crate::core::winding::WindingCount;
+use crate::ortho::error::OrthoError;
+use crate::sub::source::SourceGroups;
 use alloc::vec::Vec;
 
 pub(crate) trait Merge<C> {
-    fn merge_if_needed(&mut self);
-    fn merge_after(&mut self, after: usize) -> usize;
+    /// - Returns: `OrthoError::WindingOverflow` if a merged count overflows.
+    fn merge_if_needed(&mut self, sources: &mut SourceGroups) -> Result<(), OrthoError>;
+    fn merge_after(&mut self, after: usize, sources: &mut SourceGroups) -> Result<usize, OrthoError>;
 }
 
 impl<C: WindingCount, S: CountMergeable<C>> Merge<C> for Vec<S> {
-    fn merge_if_needed(&mut self) {
+    fn merge_if_needed(&mut self, sources: &mut SourceGroups) -> Result<(), OrthoError> {
         // data is already sorted by pos and min
 
         if self.len() < 2 {
-            return;
+            return Ok(());
         }
 
         let mut prev = &self[0];
         for i in 1..self.len() {
             let this = &self[i];
             if prev.is_same_geometry(this) {
-                let new_len = self.merge_after(i);
+                let new_len = self.merge_after(i, sources)?;
                 self.truncate(new_len);
-                return;
+                return Ok(());
             }
             prev = this;
         }
+
+        Ok(())
     }
 
-    fn merge_after(&mut self, after: usize) -> usize {
+    fn merge_after(&mut self, after: usize, sources: &mut SourceGroups) -> Result<usize, OrthoError> {
+        sources.seal();
         let mut i = after;
         let mut j = i - 1;
         let mut prev = self[j].clone();
 
         while i < self.len() {
             if prev.is_same_geometry(&self[i]) {
-                let count = prev.count().add(self[i].count());
-                prev.update(count);
+                let count = prev.count().add(self[i].count()).ok_or(OrthoError::WindingOverflow)?;
+                let source = sources.join(prev.source(), self[i].source());
+                prev.update(count, source);
             } else {
                 if prev.count().is_not_empty() {
                     self[j] = prev;
@@ -50,12 +57,13 @@ impl<C: WindingCount, S: CountMergeable<C>> Merge<C> for Vec<S> {
             j += 1;
         }
 
-        j
+        Ok(j)
     }
 }
 
 pub(crate) trait CountMergeable<C: WindingCount>: Clone {
     fn is_same_geometry(&self, other: &Self) -> bool;
     fn count(&self) -> C;
-    fn update(&mut self, count: C);
+    fn source(&self) -> u32;
+    fn update(&mut self, count: C, source: u32);
 }
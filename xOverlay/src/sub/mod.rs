@@ -1,2 +1,4 @@
+pub(crate) mod disjoint_set;
 pub(crate) mod merge;
-pub(crate) mod seg_iter;
\ No newline at end of file
+pub(crate) mod seg_iter;
+pub(crate) mod source;
\ No newline at end of file
@@ -0,0 +1,205 @@
+#[cfg(feature = "provenance")]
+use alloc::vec::Vec;
+#[cfg(feature = "provenance")]
+use core::ops::Range;
+
+#[cfg(feature = "provenance")]
+pub(crate) const GROUP_BIT: u32 = 1 << 31;
+
+/// Source ids of merged segments.
+///
+/// A source is either a plain id or, if the `GROUP_BIT` is set, an index of a group of plain ids.
+#[cfg(feature = "provenance")]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SourceGroups {
+    pub(crate) enabled: bool,
+    groups: Vec<Range<u32>>,
+    items: Vec<u32>,
+    open: Option<u32>,
+}
+
+#[cfg(feature = "provenance")]
+impl SourceGroups {
+    #[inline]
+    pub(crate) fn clear(&mut self) {
+        self.groups.clear();
+        self.items.clear();
+        self.open = None;
+    }
+
+    /// Count of bytes allocated by the groups.
+    #[inline]
+    pub(crate) fn held_bytes(&self) -> usize {
+        self.groups.capacity() * size_of::<Range<u32>>() + self.items.capacity() * size_of::<u32>()
+    }
+
+    #[inline]
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.groups.shrink_to_fit();
+        self.items.shrink_to_fit();
+    }
+
+    /// After this call all existing groups are immutable.
+    #[inline]
+    pub(crate) fn seal(&mut self) {
+        self.open = None;
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Returns a source which contains both sources.
+    #[inline]
+    pub(crate) fn join(&mut self, a: u32, b: u32) -> u32 {
+        if !self.enabled || a == b {
+            return a;
+        }
+
+        // merged segments are accumulated one by one, so the group of the previous join is extended
+        let start = if self.open == Some(a) {
+            self.groups[(a & !GROUP_BIT) as usize].start as usize
+        } else {
+            let start = self.items.len();
+            self.append_items(a, start);
+            self.groups.push(start as u32..start as u32);
+            start
+        };
+
+        self.append_items(b, start);
+        if let Some(range) = self.groups.last_mut() {
+            range.end = self.items.len() as u32;
+        }
+
+        let source = (self.groups.len() as u32 - 1) | GROUP_BIT;
+        self.open = Some(source);
+
+        source
+    }
+
+    #[inline]
+    fn append_items(&mut self, source: u32, start: usize) {
+        if source & GROUP_BIT == 0 {
+            if !self.items[start..].contains(&source) {
+                self.items.push(source);
+            }
+            return;
+        }
+        let range = self.groups[(source & !GROUP_BIT) as usize].clone();
+        for i in range {
+            let item = self.items[i as usize];
+            if !self.items[start..].contains(&item) {
+                self.items.push(item);
+            }
+        }
+    }
+
+    /// Appends all groups of other and returns the offset which must be added to its group sources.
+    pub(crate) fn append(&mut self, other: &SourceGroups) -> u32 {
+        let group_offset = self.groups.len() as u32;
+        let items_offset = self.items.len() as u32;
+        self.groups.extend(
+            other
+                .groups
+                .iter()
+                .map(|r| r.start + items_offset..r.end + items_offset),
+        );
+        self.items.extend_from_slice(&other.items);
+        group_offset
+    }
+
+    #[inline(always)]
+    pub(crate) fn shift(source: u32, group_offset: u32) -> u32 {
+        if source & GROUP_BIT == 0 {
+            source
+        } else {
+            source + group_offset
+        }
+    }
+
+    /// Writes all plain ids of the source into the buffer.
+    #[inline]
+    pub(crate) fn extend_into(&self, source: u32, buffer: &mut Vec<u32>) {
+        if source & GROUP_BIT == 0 {
+            buffer.push(source);
+        } else {
+            let range = self.groups[(source & !GROUP_BIT) as usize].clone();
+            buffer.extend_from_slice(&self.items[range.start as usize..range.end as usize]);
+        }
+    }
+}
+
+/// Sources are not tracked without the `provenance` feature, so merged segments keep no ids.
+#[cfg(not(feature = "provenance"))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SourceGroups;
+
+#[cfg(not(feature = "provenance"))]
+impl SourceGroups {
+    #[inline(always)]
+    pub(crate) fn clear(&mut self) {}
+
+    #[inline(always)]
+    pub(crate) fn held_bytes(&self) -> usize {
+        0
+    }
+
+    #[inline(always)]
+    pub(crate) fn shrink_to_fit(&mut self) {}
+
+    #[inline(always)]
+    pub(crate) fn seal(&mut self) {}
+
+    #[inline(always)]
+    pub(crate) fn join(&mut self, a: u32, _b: u32) -> u32 {
+        a
+    }
+}
+
+#[cfg(all(test, feature = "provenance"))]
+mod tests {
+    use crate::sub::source::SourceGroups;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    fn sorted(groups: &SourceGroups, source: u32) -> Vec<u32> {
+        let mut buffer = Vec::new();
+        groups.extend_into(source, &mut buffer);
+        buffer.sort_unstable();
+        buffer
+    }
+
+    #[test]
+    fn test_0() {
+        let mut groups = SourceGroups { enabled: true, ..Default::default() };
+
+        let s0 = groups.join(1, 2);
+        let s1 = groups.join(s0, 3);
+        let s2 = groups.join(s1, 2);
+        groups.seal();
+        let s3 = groups.join(5, 4);
+        let s4 = groups.join(s3, s2);
+
+        assert_eq!(s0, s2);
+        assert_eq!(sorted(&groups, 7), vec![7]);
+        assert_eq!(sorted(&groups, s3), vec![1, 2, 3, 4, 5]);
+        groups.seal();
+        let s5 = groups.join(s2, 6);
+        assert_eq!(sorted(&groups, s5), vec![1, 2, 3, 6]);
+        assert_eq!(sorted(&groups, s2), vec![1, 2, 3]);
+        assert_eq!(sorted(&groups, s4), vec![1, 2, 3, 4, 5]);
+
+        let mut other = SourceGroups::default();
+        other.append(&groups);
+        let offset = other.append(&groups);
+        assert_eq!(sorted(&other, SourceGroups::shift(s4, offset)), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_1() {
+        let mut groups = SourceGroups::default();
+        assert_eq!(groups.join(1, 2), 1);
+        assert!(groups.is_empty());
+    }
+}
@@ -0,0 +1,43 @@
+//! Contour builders shared by the unit tests.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use i_float::int::point::IntPoint;
+use i_shape::int::shape::IntContour;
+use rand::{Rng, thread_rng};
+
+/// A counter-clockwise rect.
+pub(crate) fn rect(x0: i32, y0: i32, x1: i32, y1: i32) -> IntContour {
+    vec![
+        IntPoint::new(x0, y0),
+        IntPoint::new(x1, y0),
+        IntPoint::new(x1, y1),
+        IntPoint::new(x0, y1),
+    ]
+}
+
+/// Random rects with the left-bottom corner in `-size..size` and sides in `1..size / 2`.
+pub(crate) fn random_rects(n: usize, size: i32) -> Vec<IntContour> {
+    let mut rng = thread_rng();
+    (0..n)
+        .map(|_| {
+            let x = rng.gen_range(-size..size);
+            let y = rng.gen_range(-size..size);
+            let w = rng.gen_range(1..size / 2);
+            let h = rng.gen_range(1..size / 2);
+            rect(x, y, x + w, y + h)
+        })
+        .collect()
+}
+
+/// `n x n` squares of size 10 with the step 20, shifted by `offset`.
+pub(crate) fn grid(n: i32, offset: i32) -> Vec<IntContour> {
+    let mut contours = Vec::new();
+    for i in 0..n {
+        for j in 0..n {
+            let (x, y) = (20 * i + offset, 20 * j + offset);
+            contours.push(rect(x, y, x + 10, y + 10));
+        }
+    }
+    contours
+}